@@ -1,65 +1,309 @@
+use std::collections::HashMap;
+use colored::Colorize;
+
 use crate::exception;
 use crate::exception::Exception;
 
 
 
-pub fn parse(call_argument : String, full_arguments : Vec<String>) -> () {
+// A subcommand, like `run` in `vesuviuspl run script.vsv`.
+pub struct Command {
+    pub name     : &'static str,
+    pub filename : bool, // whether it takes the filename of a script
+    pub summary  : &'static str,
+    pub options  : Vec<CommandOption>
+}
+
+pub struct CommandOption {
+    pub short   : Option<&'static str>,
+    pub long    : &'static str,
+    pub value   : Option<&'static str>, // the name of the value it takes
+    pub summary : &'static str
+}
+
+fn commands() -> Vec<Command> {
+    let option = |short, long, value, summary| CommandOption {short, long, value, summary};
+    return vec![
+        Command {name : "run",   filename : true,  summary : "Run a script.",                                 options : vec![]},
+        Command {name : "check", filename : true,  summary : "Check a script for errors without running it.", options : vec![]},
+        Command {name : "fmt",   filename : true,  summary : "Format a script.",                              options : vec![
+            option(None, "--check", None, "Only report whether the script is formatted.")
+        ]},
+        Command {name : "repl",  filename : false, summary : "Start an interactive session.",                 options : vec![]},
+        Command {name : "test",  filename : true,  summary : "Run the tests of a script.",                    options : vec![
+            option(None, "--filter", Some("NAME"), "Only run the tests whose name contains NAME.")
+        ]}
+    ];
+}
+
+
+
+// What the command line asked for.
+pub struct Invocation {
+    pub command        : String,
+    pub filename       : Option<usize>, // index of the filename in `full_arguments`
+    pub options        : HashMap<String, String>, // long name, value (empty for options without one)
+    pub configs        : Vec<(String, String, usize)>, // name, value, index of the value
+    pub arguments      : Vec<String>, // given to the script
+    pub full_arguments : Vec<String>
+}
+impl Invocation {
+
+    pub fn get_filename(&self) -> String {
+        return self.filename.map(|index| self.full_arguments[index].clone()).unwrap_or_default();
+    }
+
+}
+
+
+
+pub fn parse(call_argument : String, full_arguments : Vec<String>) -> Invocation {
+    // `vesuviuspl` alone is short for `vesuviuspl repl`.
     if (full_arguments.len() <= 0) {
-        crate::version();
-        std::process::exit(0);
+        let repl = commands().into_iter().find(|command| command.name == "repl").unwrap();
+        return parse_command(call_argument, full_arguments, repl, 0);
+    }
+
+    if (full_arguments[0].starts_with("-") && ! full_arguments[0].starts_with("--cfg.")) {
+        parse_flags(call_argument, full_arguments.clone());
     }
 
-    parse_config(call_argument, full_arguments.clone(), full_arguments);
+    // `vesuviuspl script.vsv` is short for `vesuviuspl run script.vsv`.
+    return if (let Some(command) = commands().into_iter().find(|command| command.name == full_arguments[0])) {
+        parse_command(call_argument, full_arguments, command, 1)
+    } else if (full_arguments[0].starts_with("--cfg.") || full_arguments[0].ends_with(".vsv") || std::path::Path::new(&full_arguments[0]).is_file()) {
+        let run = commands().into_iter().find(|command| command.name == "run").unwrap();
+        parse_command(call_argument, full_arguments, run, 0)
+    } else {
+        exception::CommandLineException::new(
+            exception::CommandLineExceptionType::Argument,
+            format!("Invalid command `{}`. Expected {}.", full_arguments[0], commands().iter().map(|command| format!("`{}`", command.name)).collect::<Vec<String>>().join(", ")),
+            full_arguments.clone(),
+            0
+        ).dump_error();
+    };
 }
 
 
-fn parse_config(call_argument : String, full_arguments : Vec<String>, arguments : Vec<String>) -> () {
-    if (arguments[0].starts_with("--cfg.")) {
+fn parse_flags(call_argument : String, full_arguments : Vec<String>) -> ! {
+    if (full_arguments.len() >= 2) {
         exception::CommandLineException::new(
-            exception::CommandLineExceptionType::FutureFeature,
-            format!("Configs are not yet supported."),
+            exception::CommandLineExceptionType::Argument,
+            format!("Flag `{}` takes 0 arguments. {} given.", full_arguments[0], full_arguments.len() - 1),
             full_arguments.clone(),
-            full_arguments.len() - arguments.len()
+            1
+        ).dump_error();
+    }
+    if (["-h", "--help"].contains(&full_arguments[0].as_str())) {
+        help(call_argument);
+    }
+    else if (["-v", "--version"].contains(&full_arguments[0].as_str())) {
+        crate::version();
+        std::process::exit(0);
+    } else {
+        exception::CommandLineException::new(
+            exception::CommandLineExceptionType::Argument,
+            format!("Invalid flag `{}`.", full_arguments[0]),
+            full_arguments.clone(),
+            0
         ).dump_error();
     }
-
-    parse_flags(call_argument, full_arguments, arguments);
 }
 
 
-fn parse_flags(call_argument : String, full_arguments : Vec<String>, arguments : Vec<String>) {
-    if (arguments[0].starts_with("-")) {
-        if (arguments.len() >= 2) {
-            exception::CommandLineException::new(
-                exception::CommandLineExceptionType::Argument,
-                format!("Non config flags take 0 arguments. {} given.", arguments.len() - 1),
-                full_arguments.clone(),
-                full_arguments.len() - arguments.len()
-            ).dump_error();
+// Options come before the filename. Everything after the filename, or after
+// `--`, is given to the script.
+fn parse_command(call_argument : String, full_arguments : Vec<String>, command : Command, start : usize) -> Invocation {
+    let mut invocation = Invocation {
+        command        : command.name.to_string(),
+        filename       : None,
+        options        : HashMap::new(),
+        configs        : Vec::new(),
+        arguments      : Vec::new(),
+        full_arguments : full_arguments.clone()
+    };
+    let mut index = start;
+    while (index < full_arguments.len()) {
+        let argument = &full_arguments[index];
+        if (invocation.filename.is_some() || argument == "--") {
+            if (command.name != "run") {
+                exception::CommandLineException::new(
+                    exception::CommandLineExceptionType::Argument,
+                    format!("Command `{}` takes no arguments for the script. Found `{}`.", command.name, argument),
+                    full_arguments.clone(),
+                    index
+                ).dump_error();
+            }
+            let skip = if (argument == "--") {1} else {0};
+            invocation.arguments = full_arguments[(index + skip)..].to_vec();
+            break;
+        }
+        if (["-h", "--help"].contains(&argument.as_str())) {
+            command_help(call_argument, &command);
         }
-        if (["-h", "--help"].contains(&arguments[0].as_str())) {
-            crate::help(call_argument);
+        // `--cfg.NAME VALUE` sets a config, over `vesuvius.cfg`.
+        if (let Some(name) = argument.strip_prefix("--cfg.")) {
+            if (index + 1 >= full_arguments.len()) {
+                exception::CommandLineException::new(
+                    exception::CommandLineExceptionType::Argument,
+                    format!("Config `{}` takes a VALUE.", argument),
+                    full_arguments.clone(),
+                    index
+                ).dump_error();
+            }
+            invocation.configs.push((name.to_string(), full_arguments[index + 1].clone(), index + 1));
+            index += 2;
+            continue;
         }
-        else if (["-v", "--version"].contains(&arguments[0].as_str())) {
-            crate::version();
-            std::process::exit(0);
-        } else {
+        if (argument.starts_with("-")) {
+            let option = if (let Some(option) = command.options.iter().find(|option| option.long == argument || option.short == Some(argument.as_str()))) {
+                option
+            } else {
+                exception::CommandLineException::new(
+                    exception::CommandLineExceptionType::Argument,
+                    format!("Invalid option `{}` for command `{}`.", argument, command.name),
+                    full_arguments.clone(),
+                    index
+                ).dump_error();
+            };
+            if (let Some(value) = option.value) {
+                if (index + 1 >= full_arguments.len()) {
+                    exception::CommandLineException::new(
+                        exception::CommandLineExceptionType::Argument,
+                        format!("Option `{}` takes a {}.", argument, value),
+                        full_arguments.clone(),
+                        index
+                    ).dump_error();
+                }
+                invocation.options.insert(option.long.to_string(), full_arguments[index + 1].clone());
+                index += 2;
+            } else {
+                invocation.options.insert(option.long.to_string(), String::new());
+                index += 1;
+            }
+            continue;
+        }
+        if (! command.filename) {
             exception::CommandLineException::new(
                 exception::CommandLineExceptionType::Argument,
-                format!("Invalid flag `{}`.", arguments[0]),
+                format!("Command `{}` takes no filename. Found `{}`.", command.name, argument),
                 full_arguments.clone(),
-                full_arguments.len() - arguments.len()
+                index
             ).dump_error();
         }
+        invocation.filename = Some(index);
+        index += 1;
     }
-
-    if (arguments.len() >= 2) {
+    if (command.filename && invocation.filename.is_none()) {
         exception::CommandLineException::new(
             exception::CommandLineExceptionType::Argument,
-            format!("Non config flags take 0 arguments. {} given.", arguments.len() - 1),
+            format!("Command `{}` takes the filename of a script.", command.name),
             full_arguments.clone(),
-            full_arguments.len() - arguments.len()
+            full_arguments.len() - 1
         ).dump_error();
     }
-    crate::run(full_arguments, 0)
+    return invocation;
+}
+
+
+
+fn help(call_argument : String) -> ! {
+    crate::version();
+    let commands = commands();
+    let width    = commands.iter().map(|command| command.name.len()).max().unwrap_or(0);
+    println!("{}:\n  {}\n  {}\n  {}\n  {}\n",
+        "USAGE".blue().bold(),
+        call_argument.to_string().cyan(),
+        format!("{} {}", call_argument, "[FLAG]").cyan(),
+        format!("{} {} {}", call_argument, "[COMMAND]", "[OPTION]* [FILENAME]").cyan(),
+        format!("{} {} {}", call_argument, "[FILENAME]", "[--] [ARGUMENT]*").cyan()
+    );
+    println!("{}:\n  {}\n  {}\n",
+        "FLAGS".blue().bold(),
+        format!("{} {}    : {}", "-h".bold(), "--help".bold(), "Display this help message.").cyan(),
+        format!("{} {} : {}", "-v".bold(), "--version".bold(), "Display the version number.").cyan()
+    );
+    println!("{}:", "COMMANDS".blue().bold());
+    for command in &commands {
+        println!("  {}", format!("{}{} : {}", command.name.bold(), " ".repeat(width - command.name.len()), command.summary).cyan());
+    }
+    println!("\n  {}\n", format!("Use `{} [COMMAND] --help` for the options of a command.", call_argument).cyan());
+    std::process::exit(0);
+}
+
+
+fn command_help(call_argument : String, command : &Command) -> ! {
+    crate::version();
+    let usage = match (command.filename, command.name) {
+        (true, "run") => "[OPTION]* [FILENAME] [--] [ARGUMENT]*",
+        (true, _)     => "[OPTION]* [FILENAME]",
+        (false, _)    => "[OPTION]*"
+    };
+    println!("{}:\n  {}\n\n  {}\n",
+        "USAGE".blue().bold(),
+        format!("{} {} {}", call_argument, command.name, usage).cyan(),
+        command.summary.cyan()
+    );
+    let mut options = vec![
+        (String::from("-h --help"), "Display this help message."),
+        (String::from("--cfg.[NAME] [VALUE]"), "Set a config, over the ones in `vesuvius.cfg`.")
+    ];
+    for option in &command.options {
+        let mut name = option.short.map(|short| format!("{} {}", short, option.long)).unwrap_or(option.long.to_string());
+        if (let Some(value) = option.value) {
+            name = format!("{} [{}]", name, value);
+        }
+        options.push((name, option.summary));
+    }
+    let width = options.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    println!("{}:", "OPTIONS".blue().bold());
+    for (name, summary) in options {
+        println!("  {}", format!("{}{} : {}", name.bold(), " ".repeat(width - name.len()), summary).cyan());
+    }
+    println!();
+    std::process::exit(0);
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_line(line : &str) -> Result<Invocation, i32> {
+        let arguments = line.split_whitespace().map(|argument| argument.to_string()).collect();
+        return exception::catch_exit(|| parse(String::from("vesuviuspl"), arguments));
+    }
+
+    #[test]
+    fn parse_reads_the_options_and_the_filename() {
+        let invocation = parse_line("test --filter add script.vsv").ok().unwrap();
+        assert!(invocation.command == "test");
+        assert!(invocation.get_filename() == "script.vsv");
+        assert!(invocation.options.get("--filter") == Some(&String::from("add")));
+        assert!(invocation.arguments.is_empty());
+    }
+
+    #[test]
+    fn parse_gives_what_follows_the_filename_to_the_script() {
+        let invocation = parse_line("--cfg.mode fast script.vsv a -- b").ok().unwrap();
+        assert!(invocation.command == "run");
+        assert!(invocation.configs == vec![(String::from("mode"), String::from("fast"), 1)]);
+        assert!(invocation.arguments == vec!["a", "--", "b"]);
+        let invocation = parse_line("run script.vsv -- a").ok().unwrap();
+        assert!(invocation.arguments == vec!["a"]);
+    }
+
+    #[test]
+    fn parse_starts_the_repl_without_arguments() {
+        assert!(parse_line("").ok().unwrap().command == "repl");
+    }
+
+    #[test]
+    fn parse_rejects_invalid_command_lines() {
+        for line in ["build script.vsv", "run --output out script.vsv", "test script.vsv extra", "check", "repl script.vsv", "--cfg.mode"] {
+            assert!(parse_line(line).err() == Some(exception::EXIT_ERROR), "`{}` was accepted", line);
+        }
+    }
+
 }
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+
+use crate::argument;
+use crate::data;
+use crate::exception;
+use crate::exception::Exception;
+
+
+
+// The configs that the compiler uses, with their defaults and the values
+// they can be set to (any value if there are none). Any other name can be
+// set for scripts and `#[cfg(...)]` to read.
+const DEFAULTS : [(&str, &str, &[&str]); 2] = [ // name, default, values
+    ("warnings", "warn",               &["allow", "warn", "deny"]),
+    ("os",       std::env::consts::OS, &[])
+];
+
+pub const FILENAME : &str = "vesuvius.cfg";
+
+static CONFIG : std::sync::OnceLock<HashMap<String, String>> = std::sync::OnceLock::new();
+
+
+
+pub fn load(invocation : &argument::Invocation) -> () {
+    let _ = CONFIG.set(layer(invocation));
+}
+
+
+// Layers the defaults, then `vesuvius.cfg` next to the script, then the
+// `--cfg.NAME VALUE` options.
+fn layer(invocation : &argument::Invocation) -> HashMap<String, String> {
+    let mut config = HashMap::new();
+    for (name, default, _) in DEFAULTS {
+        config.insert(name.to_string(), default.to_string());
+    }
+
+    let directory = std::path::Path::new(&invocation.get_filename()).parent().map(|parent| parent.to_path_buf()).unwrap_or_default();
+    let path      = directory.join(FILENAME);
+    if (let Ok(text) = std::fs::read_to_string(&path)) {
+        load_file(&mut config, path.display().to_string(), text);
+    }
+
+    for (name, value, index) in &invocation.configs {
+        if (let Err(message) = check(name, value)) {
+            exception::CommandLineException::new(
+                exception::CommandLineExceptionType::Argument,
+                message,
+                invocation.full_arguments.clone(),
+                *index
+            ).dump_error();
+        }
+        config.insert(name.clone(), value.clone());
+    }
+
+    return config;
+}
+
+
+// Each line is `NAME = VALUE`. Lines starting with `//` are comments.
+fn load_file(config : &mut HashMap<String, String>, filename : String, text : String) -> () {
+    let mut index = 0;
+    for (line, content) in text.lines().enumerate() {
+        let start = index;
+        index += content.len() + 1;
+        let trimmed = content.trim();
+        if (trimmed.len() <= 0 || trimmed.starts_with("//")) {
+            continue;
+        }
+        let range = data::Range::new(
+            data::Position::new(start, line, 1, filename.clone(), text.clone()),
+            data::Position::new(start + content.len(), line, content.len(), filename.clone(), text.clone())
+        );
+        let (name, value) = if (let Some((name, value)) = trimmed.split_once("=")) {
+            (name.trim().to_string(), value.trim().trim_matches('"').to_string())
+        } else {
+            exception::ConfigException::new(
+                exception::ConfigExceptionType::MissingEquals,
+                String::from("Expected `=` not found. Configs are written like `warnings = deny`."),
+                range
+            ).dump_error();
+        };
+        if (let Err(message) = check(&name, &value)) {
+            exception::ConfigException::new(
+                exception::ConfigExceptionType::InvalidConfig,
+                message,
+                range
+            ).dump_error();
+        }
+        config.insert(name, value);
+    }
+}
+
+
+fn check(name : &String, value : &String) -> Result<(), String> {
+    if (name.len() <= 0 || ! name.chars().all(|ch| ch.is_alphanumeric() || ch == '_')) {
+        return Err(format!("Config name `{}` is invalid. Names are made of letters, digits and `_`.", name));
+    }
+    if (let Some((_, _, values)) = DEFAULTS.iter().find(|(other, _, _)| other == name)
+        && values.len() >= 1
+        && ! values.contains(&value.as_str())
+    ) {
+        return Err(format!("Config `{}` can not be `{}`. Expected {}.", name, value, values.iter().map(|value| format!("`{}`", value)).collect::<Vec<String>>().join(", ")));
+    }
+    return Ok(());
+}
+
+
+
+pub fn get(name : &str) -> Option<String> {
+    return CONFIG.get().and_then(|config| config.get(name).cloned());
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Layers the configs of a script in a directory of its own, with `file`
+    // as its `vesuvius.cfg`.
+    fn layer_with(name : &str, file : &str, line : &str) -> Result<HashMap<String, String>, i32> {
+        let directory = std::env::temp_dir().join(format!("vesuviuspl-config-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join(FILENAME), file).unwrap();
+        let script    = directory.join("script.vsv").display().to_string();
+        let arguments = line.split_whitespace().map(|argument| argument.to_string()).chain([script]).collect();
+        let config    = exception::catch_exit(|| layer(&argument::parse(String::from("vesuviuspl"), arguments)));
+        std::fs::remove_dir_all(&directory).unwrap();
+        return config;
+    }
+
+    #[test]
+    fn options_are_layered_over_the_file_over_the_defaults() {
+        let config = layer_with("layers", "// comment\nwarnings = deny\nmode = \"fast\"\nlevel = 2\n", "run --cfg.mode slow").ok().unwrap();
+        assert!(config["warnings"] == "deny");
+        assert!(config["mode"] == "slow");
+        assert!(config["level"] == "2");
+        assert!(config["os"] == std::env::consts::OS);
+    }
+
+    #[test]
+    fn invalid_configs_are_rejected() {
+        assert!(layer_with("equals", "warnings deny\n", "run").err() == Some(exception::EXIT_ERROR));
+        assert!(layer_with("file", "warnings = loud\n", "run").err() == Some(exception::EXIT_ERROR));
+        assert!(layer_with("option", "", "run --cfg.warnings loud").err() == Some(exception::EXIT_ERROR));
+        assert!(layer_with("name", "", "run --cfg.a-b 1").err() == Some(exception::EXIT_ERROR));
+    }
+
+}
+
@@ -1,9 +1,16 @@
-use std;
-
 mod token;
 pub use token::{
     Token,
-    TokenType
+    TokenType,
+    Trivia,
+    TriviaType
+};
+mod syntax;
+pub use syntax::{
+    SyntaxBuilder,
+    SyntaxElement,
+    SyntaxNode,
+    SyntaxType
 };
 mod node;
 pub use node::{
@@ -11,10 +18,34 @@ pub use node::{
     NodeType,
     NodeHeaders
 };
+mod attribute;
+pub use attribute::{
+    Attribute,
+    AttributeArguments,
+    AttributeArgument,
+    AttributeTarget,
+    AttributeError,
+    Predicate,
+    check_attribute
+};
 mod value;
 pub use value::{
     Literal,
-    Type
+    Pattern,
+    Type,
+    Value,
+    Function,
+    Class,
+    Instance,
+    Enum
+};
+mod types;
+pub use types::{
+    ValueType
+};
+mod class;
+pub use class::{
+    method_resolution_order
 };
 mod object;
 pub use object::{
@@ -25,11 +56,30 @@ mod context;
 pub use context::{
     Context
 };
+mod scope;
+pub use scope::{
+    Scope
+};
+mod module;
+pub use module::{
+    Module,
+    qualify
+};
+
 
 
+pub const ALPHABETIC : &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+pub const NUMERIC    : &str = "0123456789";
 
-pub const ALPHABETIC : &'static str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
-pub const NUMERIC    : &'static str = "0123456789";
+// Operators that a class can overload by implementing a trait : operator, trait, method.
+pub const OPERATOR_TRAITS : [(&str, &str, &str); 6] = [
+    ("+",  "Add",      "add"),
+    ("-",  "Subtract", "subtract"),
+    ("*",  "Multiply", "multiply"),
+    ("/",  "Divide",   "divide"),
+    ("%",  "Modulo",   "modulo"),
+    ("**", "Power",    "power")
+];
 
 
 
@@ -44,11 +94,11 @@ pub struct Position {
 impl Position {
     pub fn new(index : usize, line : usize, column : usize, filename : String, script : String) -> Position {
         return Position {
-            index    : index,
-            line     : line,
-            column   : column,
-            filename : filename,
-            script   : script
+            index,
+            line,
+            column,
+            filename,
+            script
         }
     }
 }
@@ -61,8 +111,8 @@ pub struct Range {
 impl Range {
     pub fn new(min : Position, max : Position) -> Range {
         return Range {
-            min : min,
-            max : max
+            min,
+            max
         }
     }
     pub fn new_void() -> Range {
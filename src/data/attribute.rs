@@ -0,0 +1,184 @@
+use crate::config;
+use crate::data;
+
+
+
+// `#[name]`, `#[name(args)]` or `#[name = literal]`, before an item.
+#[derive(Clone)]
+pub struct Attribute {
+    pub name      : String,
+    pub arguments : AttributeArguments,
+    pub range     : data::Range
+}
+
+#[derive(Clone)]
+pub enum AttributeArguments {
+    None,
+    List(Vec<AttributeArgument>), // `#[name(args)]`
+    Value(data::Literal, data::Range) // `#[name = literal]`
+}
+
+#[derive(Clone)]
+pub enum AttributeArgument {
+    Literal(data::Literal, data::Range), // a bare name is `Literal::Name`
+    NameValue(String, data::Literal, data::Range),
+    List(String, Vec<AttributeArgument>, data::Range)
+}
+impl AttributeArgument {
+    pub fn range(&self) -> data::Range {
+        return match (self) {
+            AttributeArgument::Literal(_, range)      => range.clone(),
+            AttributeArgument::NameValue(_, _, range) => range.clone(),
+            AttributeArgument::List(_, _, range)      => range.clone()
+        };
+    }
+}
+
+
+
+// The items that attributes are written on.
+#[derive(Clone, Copy, PartialEq)]
+pub enum AttributeTarget {
+    Import,
+    Variable,
+    Function,
+    Method,
+    Class,
+    Enum,
+    Trait,
+    Impl
+}
+impl std::fmt::Display for AttributeTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        return write!(f, "{}", match (self) {
+            AttributeTarget::Import   => "imports",
+            AttributeTarget::Variable => "globals",
+            AttributeTarget::Function => "functions",
+            AttributeTarget::Method   => "methods",
+            AttributeTarget::Class    => "classes",
+            AttributeTarget::Enum     => "enums",
+            AttributeTarget::Trait    => "traits",
+            AttributeTarget::Impl     => "impls"
+        });
+    }
+}
+
+// The arguments that an attribute takes.
+#[derive(Clone, Copy)]
+enum AttributeShape {
+    Word,                         // `#[name]`
+    Predicate,                    // `#[name(predicate)]`
+    Note,                         // `#[name]` or `#[name = "note"]`
+    Mode(&'static [&'static str]) // `#[name]` or `#[name(mode)]`
+}
+
+const ALL : &[AttributeTarget] = &[
+    AttributeTarget::Import, AttributeTarget::Variable, AttributeTarget::Function, AttributeTarget::Method,
+    AttributeTarget::Class, AttributeTarget::Enum, AttributeTarget::Trait, AttributeTarget::Impl
+];
+
+// The attributes that exist, the items they can be written on and their arguments.
+const ATTRIBUTES : [(&str, &[AttributeTarget], AttributeShape); 7] = [ // name, targets, shape
+    ("entry",      &[AttributeTarget::Function], AttributeShape::Word),
+    ("static",     &[AttributeTarget::Method],   AttributeShape::Word),
+    ("public",     &[AttributeTarget::Variable, AttributeTarget::Function, AttributeTarget::Class, AttributeTarget::Enum, AttributeTarget::Trait], AttributeShape::Word),
+    ("cfg",        ALL,                          AttributeShape::Predicate),
+    ("deprecated", &[AttributeTarget::Variable, AttributeTarget::Function, AttributeTarget::Method, AttributeTarget::Class, AttributeTarget::Enum, AttributeTarget::Trait], AttributeShape::Note),
+    ("inline",     &[AttributeTarget::Function, AttributeTarget::Method], AttributeShape::Mode(&["always", "never"])),
+    ("test",       &[AttributeTarget::Function], AttributeShape::Word)
+];
+
+
+
+pub enum AttributeError {
+    Unknown(String),             // message
+    Invalid(String, data::Range) // message, range
+}
+
+// Checks that the attribute exists, can be written on `target` and has the
+// arguments it takes. A `#[cfg(...)]` gives its predicate.
+pub fn check_attribute(attribute : &Attribute, target : AttributeTarget) -> Result<Option<Predicate>, AttributeError> {
+    let (_, targets, shape) = if (let Some(entry) = ATTRIBUTES.iter().find(|(name, _, _)| *name == attribute.name)) {
+        *entry
+    } else {
+        return Err(AttributeError::Unknown(format!("Unknown attribute `{}`.", attribute.name)));
+    };
+
+    if (! targets.contains(&target)) {
+        return Err(AttributeError::Invalid(
+            format!("Attribute `{}` can not be used on {}. It is used on {}.", attribute.name, target, targets.iter().map(|target| target.to_string()).collect::<Vec<String>>().join(", ")),
+            attribute.range.clone()
+        ));
+    }
+
+    let usage = match (shape) {
+        AttributeShape::Word        => format!("`#[{}]`", attribute.name),
+        AttributeShape::Predicate   => format!("`#[{}(predicate)]`", attribute.name),
+        AttributeShape::Note        => format!("`#[{}]` or `#[{} = \"note\"]`", attribute.name, attribute.name),
+        AttributeShape::Mode(modes) => format!("`#[{}]` or `#[{}(mode)]` with a mode of {}", attribute.name, attribute.name, modes.iter().map(|mode| format!("`{}`", mode)).collect::<Vec<String>>().join(", "))
+    };
+    let invalid = |range : data::Range| AttributeError::Invalid(format!("Attribute `{}` is written as {}.", attribute.name, usage), range);
+
+    return match (shape, &attribute.arguments) {
+        (AttributeShape::Word, AttributeArguments::None)
+            | (AttributeShape::Note, AttributeArguments::None)
+            | (AttributeShape::Note, AttributeArguments::Value(data::Literal::String(_), _))
+            | (AttributeShape::Mode(_), AttributeArguments::None) => Ok(None),
+        (AttributeShape::Mode(modes), AttributeArguments::List(arguments)) => match (arguments.as_slice()) {
+            [AttributeArgument::Literal(data::Literal::Name(mode), _)] if modes.contains(&mode.as_str()) => Ok(None),
+            [argument, ..] => Err(invalid(argument.range())),
+            []             => Err(invalid(attribute.range.clone()))
+        },
+        (AttributeShape::Predicate, AttributeArguments::List(arguments)) => match (arguments.as_slice()) {
+            [argument] => Predicate::from(argument).map(Some),
+            _          => Err(invalid(attribute.range.clone()))
+        },
+        (_, AttributeArguments::Value(_, range)) => Err(invalid(range.clone())),
+        _                                        => Err(invalid(attribute.range.clone()))
+    };
+}
+
+
+
+// The condition of a `#[cfg(...)]` attribute, on the configs.
+#[derive(Clone)]
+pub enum Predicate {
+    Set(String), // name
+    Equals(String, String), // name, value
+    Not(Box<Predicate>),
+    All(Vec<Predicate>),
+    Any(Vec<Predicate>)
+}
+impl Predicate {
+
+    // `name`, `name = "value"`, `not(predicate)`, `all(predicate*)` or `any(predicate*)`.
+    fn from(argument : &AttributeArgument) -> Result<Predicate, AttributeError> {
+        return match (argument) {
+            AttributeArgument::Literal(data::Literal::Name(name), _)            => Ok(Predicate::Set(name.clone())),
+            AttributeArgument::NameValue(name, data::Literal::String(value), _) => Ok(Predicate::Equals(name.clone(), value.clone())),
+            AttributeArgument::List(name, arguments, range) if name == "not" => {
+                if (arguments.len() != 1) {
+                    return Err(AttributeError::Invalid(format!("Predicate `not` takes 1 predicate. {} given.", arguments.len()), range.clone()));
+                }
+                Ok(Predicate::Not(Box::new(Predicate::from(&arguments[0])?)))
+            },
+            AttributeArgument::List(name, arguments, _) if name == "all" => Ok(Predicate::All(arguments.iter().map(Predicate::from).collect::<Result<Vec<Predicate>, AttributeError>>()?)),
+            AttributeArgument::List(name, arguments, _) if name == "any" => Ok(Predicate::Any(arguments.iter().map(Predicate::from).collect::<Result<Vec<Predicate>, AttributeError>>()?)),
+            _ => Err(AttributeError::Invalid(
+                String::from("Expected a predicate, like `debug`, `os = \"linux\"`, `not(...)`, `all(...)` or `any(...)`."),
+                argument.range()
+            ))
+        };
+    }
+
+    pub fn holds(&self) -> bool {
+        return match (self) {
+            Predicate::Set(name)           => config::get(name).is_some(),
+            Predicate::Equals(name, value) => config::get(name).as_ref() == Some(value),
+            Predicate::Not(predicate)      => ! predicate.holds(),
+            Predicate::All(predicates)     => predicates.iter().all(|predicate| predicate.holds()),
+            Predicate::Any(predicates)     => predicates.iter().any(|predicate| predicate.holds())
+        };
+    }
+
+}
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+
+
+// C3 linearization : a class comes before its bases, and the bases keep the
+// order they are listed in after `extends`. Used by both the validator and the
+// interpreter, so that methods resolve the same way in both.
+pub fn method_resolution_order(name : &String, bases : &HashMap<String, Vec<String>>) -> Result<Vec<String>, String> {
+    return linearize(name, bases, &mut Vec::new());
+}
+
+
+fn linearize(name : &String, bases : &HashMap<String, Vec<String>>, visiting : &mut Vec<String>) -> Result<Vec<String>, String> {
+    if (visiting.contains(name)) {
+        return Err(format!("Class `{}` inherits from itself.", name));
+    }
+    visiting.push(name.clone());
+    let     direct    = bases.get(name).cloned().unwrap_or_default();
+    let mut sequences = Vec::new();
+    for base in &direct {
+        sequences.push(linearize(base, bases, visiting)?);
+    }
+    sequences.push(direct.clone());
+    visiting.pop();
+
+    let mut order = vec![name.clone()];
+    loop {
+        sequences.retain(|sequence| sequence.len() >= 1);
+        if (sequences.len() <= 0) {
+            return Ok(order);
+        }
+        // The next class is the first head that is not in the tail of any sequence.
+        let head = sequences.iter()
+            .map(|sequence| sequence[0].clone())
+            .find(|head| sequences.iter().all(|sequence| ! sequence[1..].contains(head)));
+        if (let Some(head) = head) {
+            for sequence in &mut sequences {
+                if (sequence[0] == head) {
+                    sequence.remove(0);
+                }
+            }
+            order.push(head);
+        } else {
+            return Err(format!("Can not create a consistent method resolution order for `{}` from `{}`.", name, direct.join("`, `")));
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inheritance(classes : &[(&str, &[&str])]) -> HashMap<String, Vec<String>> {
+        return classes.iter().map(|(name, bases)| (name.to_string(), bases.iter().map(|base| base.to_string()).collect())).collect();
+    }
+
+    #[test]
+    fn method_resolution_order_keeps_the_bases_in_order() {
+        let bases = inheritance(&[("Base", &[]), ("Left", &["Base"]), ("Right", &["Base"]), ("Both", &["Left", "Right"])]);
+        assert!(method_resolution_order(&String::from("Both"), &bases) == Ok(vec![
+            String::from("Both"), String::from("Left"), String::from("Right"), String::from("Base")
+        ]));
+    }
+
+    #[test]
+    fn method_resolution_order_rejects_bases_in_conflicting_orders() {
+        let bases = inheritance(&[("A", &[]), ("B", &[]), ("X", &["A", "B"]), ("Y", &["B", "A"]), ("Z", &["X", "Y"])]);
+        assert!(method_resolution_order(&String::from("Z"), &bases).is_err());
+        let bases = inheritance(&[("A", &["B"]), ("B", &["A"])]);
+        assert!(method_resolution_order(&String::from("A"), &bases).is_err());
+    }
+
+    #[test]
+    fn methods_resolve_along_the_method_resolution_order() {
+        let script = "
+            class Base {
+                func who() : Int {
+                    1
+                }
+                func side() : Int {
+                    10
+                }
+            }
+            class Left extends Base {
+                func who() : Int {
+                    2
+                }
+            }
+            class Right extends Base {
+                func who() : Int {
+                    3
+                }
+                func side() : Int {
+                    30
+                }
+            }
+            class Both extends Left, Right {}
+            #[entry]
+            func main() : Int {
+                let both = Both();
+                both.who() + both.side()
+            }
+        ";
+        assert!(crate::run::exit_code(script) == 32);
+    }
+
+}
@@ -1,3 +1,6 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+
 use crate::data;
 
 
@@ -6,14 +9,34 @@ use crate::data;
 pub struct Context {
     pub name      : String,
     pub parent    : Box<Option<(data::Range, Context)>>,
+    pub symbols   : Rc<RefCell<std::collections::HashMap<String, data::Object>>>
 }
 impl Context {
 
     pub fn new(name : String, parent : Option<(data::Range, Context)>) -> Context {
         return Context {
-            name   : name,
-            parent : Box::new(parent)
+            name,
+            parent  : Box::new(parent),
+            symbols : Rc::new(RefCell::new(std::collections::HashMap::new()))
         };
     }
 
+    pub fn contains_name(&self, name : &String) -> bool {
+        return self.symbols.borrow().contains_key(name);
+    }
+
+    pub fn get_name(&self, name : &String) -> Option<data::Object> {
+        if (let Some(object) = self.symbols.borrow().get(name)) {
+            return Some(object.clone());
+        }
+        if (let Some((_, parent)) = &*self.parent) {
+            return parent.get_name(name);
+        }
+        return None;
+    }
+
+    pub fn set_name(&self, name : String, object : data::Object) -> () {
+        self.symbols.borrow_mut().insert(name, object);
+    }
+
 }
@@ -0,0 +1,35 @@
+use crate::data;
+
+
+
+// A script and the names of the modules it uses.
+#[derive(Clone)]
+pub struct Module {
+    pub key     : String, // `util::helpers` for `util/helpers.vsv`, relative to the entry script, which has none
+    pub path    : String,
+    pub nodes   : Vec<data::Node>,
+    pub imports : std::collections::HashMap<String, String> // name, key
+}
+impl Module {
+
+    pub fn new(key : String, path : String, nodes : Vec<data::Node>, imports : std::collections::HashMap<String, String>) -> Module {
+        return Module {
+            key,
+            path,
+            nodes,
+            imports
+        };
+    }
+
+    // The name that a global of this module is known by in every other module.
+    pub fn qualify(&self, name : &String) -> String {
+        return qualify(&self.key, name);
+    }
+
+}
+
+
+
+pub fn qualify(key : &String, name : &String) -> String {
+    return if (key.len() <= 0) {name.clone()} else {format!("{}::{}", key, name)};
+}
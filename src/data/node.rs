@@ -11,11 +11,69 @@ pub struct Node {
 impl Node {
     pub fn new(node : NodeType, range : data::Range) -> Node {
         return Node {
-            node    : node,
-            range   : range,
+            node,
+            range,
             headers : NodeHeaders::new()
         }
     }
+
+    // Rebuilds the node bottom up, passing every statement and expression
+    // through `f` once its children are done. Types and patterns are kept.
+    pub fn map(self, f : &mut dyn FnMut(Node) -> Node) -> Node {
+        let node = match (self.node) {
+
+            NodeType::DefineFunction(name, generics, args, return_type, body) => NodeType::DefineFunction(name, generics, args, return_type, Box::new(map_nodes(*body, f))),
+            NodeType::DefineClass(name, generics, bases, fields, methods)     => NodeType::DefineClass(name, generics, bases,
+                Box::new(fields.into_iter().map(|(field, typ, default)| (field, typ, default.map(|default| default.map(f)))).collect()),
+                Box::new(map_nodes(*methods, f))
+            ),
+            NodeType::ImplementTrait(name, class, methods)     => NodeType::ImplementTrait(name, class, Box::new(map_nodes(*methods, f))),
+            NodeType::InitializeVariable(mutable, name, typ, value) => NodeType::InitializeVariable(mutable, name, typ, Box::new(value.map(|value| value.map(f)))),
+            NodeType::AssignVariable(target, operator, value)       => NodeType::AssignVariable(Box::new(target.map(f)), operator, Box::new(value.map(f))),
+
+            NodeType::If(branches, fallback)            => NodeType::If(
+                Box::new(branches.into_iter().map(|(condition, body)| (condition.map(f), map_nodes(body, f))).collect()),
+                Box::new(fallback.map(|body| map_nodes(body, f)))
+            ),
+            NodeType::While(label, condition, body)     => NodeType::While(label, Box::new(condition.map(f)), Box::new(map_nodes(*body, f))),
+            NodeType::Loop(label, body)                 => NodeType::Loop(label, Box::new(map_nodes(*body, f))),
+            NodeType::For(label, name, iterable, body)  => NodeType::For(label, name, Box::new(iterable.map(f)), Box::new(map_nodes(*body, f))),
+            NodeType::Return(value)                     => NodeType::Return(Box::new(value.map(|value| value.map(f)))),
+            NodeType::Match(value, arms)                => NodeType::Match(
+                Box::new(value.map(f)),
+                Box::new(arms.into_iter().map(|(pattern, body)| (pattern, map_nodes(body, f))).collect())
+            ),
+            NodeType::BlockResult(value)                => NodeType::BlockResult(Box::new(value.map(f))),
+            NodeType::BoundsCheck(value, bounds)        => NodeType::BoundsCheck(Box::new(value.map(f)), bounds),
+
+            NodeType::OppositeOperation(value)          => NodeType::OppositeOperation(Box::new(value.map(f))),
+            NodeType::InvertOperation(value)            => NodeType::InvertOperation(Box::new(value.map(f))),
+
+            NodeType::ModuleMember(parent, name)        => NodeType::ModuleMember(Box::new(parent.map(f)), name),
+            NodeType::ClassMember(parent, name)         => NodeType::ClassMember(Box::new(parent.map(f)), name),
+            NodeType::Slice(parent, index)              => NodeType::Slice(Box::new(parent.map(f)), Box::new(index.map(f))),
+            NodeType::Call(parent, args)                => NodeType::Call(Box::new(parent.map(f)), Box::new(map_nodes(*args, f))),
+
+            NodeType::Range(min, max, step)             => NodeType::Range(Box::new(min.map(f)), Box::new(max.map(f)), Box::new(step.map(|step| step.map(f)))),
+            NodeType::Tuple(values)                     => NodeType::Tuple(Box::new(map_nodes(*values, f))),
+            NodeType::Array(values)                     => NodeType::Array(Box::new(map_nodes(*values, f))),
+            NodeType::Dict(entries)                     => NodeType::Dict(Box::new(entries.into_iter().map(|(key, value)| (key.map(f), value.map(f))).collect())),
+            NodeType::Function(args, return_type, body, captures) => NodeType::Function(args, return_type, Box::new(map_nodes(*body, f)), captures),
+
+            node => if (let Some((operator, left, right)) = node.binary_operation()) {
+                NodeType::new_binary_operation(operator, left.map(f), right.map(f))
+            } else {
+                node
+            }
+
+        };
+        return f(Node {
+            node,
+            range   : self.range,
+            headers : self.headers
+        });
+    }
+
 }
 impl std::fmt::Display for Node {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -31,9 +89,25 @@ pub enum NodeType {
     LocalImport(String),    // filename
 
 
-    DefineFunction(String, Box<Vec<(String, Node)>>, Box<Node>, Box<Vec<Node>>), // name, args(name, type), return_type, content
+    DefineFunction(String, Vec<(String, Vec<String>)>, Box<Vec<(String, Node)>>, Box<Node>, Box<Vec<Node>>), // name, generics(name, bounds), args(name, type), return_type, content
+    DefineClass(String, Vec<(String, Vec<String>)>, Vec<String>, Box<Vec<(String, Node, Option<Node>)>>, Box<Vec<Node>>), // name, generics(name, bounds), bases, fields(name, type, default), methods
+    DefineEnum(String, Box<Vec<(String, Vec<(Option<String>, Node)>)>>), // name, variants(name, payload(field, type))
+    DefineTrait(String, Box<Vec<Node>>), // name, methods(without content)
+    ImplementTrait(String, String, Box<Vec<Node>>), // trait, class, methods
     InitializeVariable(bool, String, Box<Node>, Box<Option<Node>>), // mutable, name, type, value
-    AssignVariable(Box<Node>, Box<Node>), // node, value
+    AssignVariable(Box<Node>, Option<&'static str>, Box<Node>), // node, operator, value
+
+
+    If(Box<Vec<(Node, Vec<Node>)>>, Box<Option<Vec<Node>>>), // branches(condition, content), else_content
+    While(Option<String>, Box<Node>, Box<Vec<Node>>), // label, condition, content
+    Loop(Option<String>, Box<Vec<Node>>), // label, content
+    For(Option<String>, String, Box<Node>, Box<Vec<Node>>), // label, name, iterable, content
+    Break(Option<String>), // label
+    Continue(Option<String>), // label
+    Return(Box<Option<Node>>), // value
+    Match(Box<Node>, Box<Vec<(Node, Vec<Node>)>>), // value, arms(pattern, content)
+    BlockResult(Box<Node>), // value
+    BoundsCheck(Box<Node>, data::ValueType), // value, bounds
 
 
     AdditionOperation(Box<Node>, Box<Node>), // left, right
@@ -41,9 +115,25 @@ pub enum NodeType {
     MultiplicationOperation(Box<Node>, Box<Node>), // left, right
     DivisionOperation(Box<Node>, Box<Node>), // left, right
     PowerOperation(Box<Node>, Box<Node>), // left, right
+    ModuloOperation(Box<Node>, Box<Node>), // left, right
     InvertOperation(Box<Node>), // value
     OppositeOperation(Box<Node>), // value
 
+    EqualOperation(Box<Node>, Box<Node>), // left, right
+    NotEqualOperation(Box<Node>, Box<Node>), // left, right
+    LessOperation(Box<Node>, Box<Node>), // left, right
+    LessEqualOperation(Box<Node>, Box<Node>), // left, right
+    GreaterOperation(Box<Node>, Box<Node>), // left, right
+    GreaterEqualOperation(Box<Node>, Box<Node>), // left, right
+    AndOperation(Box<Node>, Box<Node>), // left, right
+    OrOperation(Box<Node>, Box<Node>), // left, right
+
+    BitwiseAndOperation(Box<Node>, Box<Node>), // left, right
+    BitwiseOrOperation(Box<Node>, Box<Node>), // left, right
+    BitwiseXorOperation(Box<Node>, Box<Node>), // left, right
+    ShiftLeftOperation(Box<Node>, Box<Node>), // left, right
+    ShiftRightOperation(Box<Node>, Box<Node>), // left, right
+
 
     ModuleMember(Box<Node>, String), // parent, child
     ClassMember(Box<Node>, String), // parent, child
@@ -52,7 +142,73 @@ pub enum NodeType {
 
 
     Type(data::Type, Vec<Node>), // base, arguments
-    Literal(data::Literal) // value
+    Literal(data::Literal), // value
+    Pattern(data::Pattern), // pattern
+    Range(Box<Node>, Box<Node>, Box<Option<Node>>), // min, max, step
+    Tuple(Box<Vec<Node>>), // values
+    Array(Box<Vec<Node>>), // values
+    Dict(Box<Vec<(Node, Node)>>), // entries(key, value)
+    Function(Box<Vec<(String, Node)>>, Box<Node>, Box<Vec<Node>>, Vec<String>) // args(name, type), return_type, content, captures(by value)
+
+}
+impl NodeType {
+
+    // The symbol and operands of a binary operation.
+    pub fn binary_operation(&self) -> Option<(&'static str, Node, Node)> {
+        let (operator, left, right) = match (self) {
+            NodeType::AdditionOperation(left, right)       => ("+", left, right),
+            NodeType::SubtractionOperation(left, right)    => ("-", left, right),
+            NodeType::MultiplicationOperation(left, right) => ("*", left, right),
+            NodeType::DivisionOperation(left, right)       => ("/", left, right),
+            NodeType::PowerOperation(left, right)          => ("**", left, right),
+            NodeType::ModuloOperation(left, right)         => ("%", left, right),
+            NodeType::EqualOperation(left, right)          => ("==", left, right),
+            NodeType::NotEqualOperation(left, right)       => ("!=", left, right),
+            NodeType::LessOperation(left, right)           => ("<", left, right),
+            NodeType::LessEqualOperation(left, right)      => ("<=", left, right),
+            NodeType::GreaterOperation(left, right)        => (">", left, right),
+            NodeType::GreaterEqualOperation(left, right)   => (">=", left, right),
+            NodeType::AndOperation(left, right)            => ("&&", left, right),
+            NodeType::OrOperation(left, right)             => ("||", left, right),
+            NodeType::BitwiseAndOperation(left, right)     => ("&", left, right),
+            NodeType::BitwiseOrOperation(left, right)      => ("|", left, right),
+            NodeType::BitwiseXorOperation(left, right)     => ("^", left, right),
+            NodeType::ShiftLeftOperation(left, right)      => ("<<", left, right),
+            NodeType::ShiftRightOperation(left, right)     => (">>", left, right),
+            _                                              => return None
+        };
+        return Some((operator, *left.clone(), *right.clone()));
+    }
+
+    pub fn new_binary_operation(operator : &str, left : Node, right : Node) -> NodeType {
+        let (left, right) = (Box::new(left), Box::new(right));
+        return match (operator) {
+            "+"  => NodeType::AdditionOperation(left, right),
+            "-"  => NodeType::SubtractionOperation(left, right),
+            "*"  => NodeType::MultiplicationOperation(left, right),
+            "/"  => NodeType::DivisionOperation(left, right),
+            "**" => NodeType::PowerOperation(left, right),
+            "%"  => NodeType::ModuloOperation(left, right),
+            "==" => NodeType::EqualOperation(left, right),
+            "!=" => NodeType::NotEqualOperation(left, right),
+            "<"  => NodeType::LessOperation(left, right),
+            "<=" => NodeType::LessEqualOperation(left, right),
+            ">"  => NodeType::GreaterOperation(left, right),
+            ">=" => NodeType::GreaterEqualOperation(left, right),
+            "&&" => NodeType::AndOperation(left, right),
+            "||" => NodeType::OrOperation(left, right),
+            "&"  => NodeType::BitwiseAndOperation(left, right),
+            "|"  => NodeType::BitwiseOrOperation(left, right),
+            "^"  => NodeType::BitwiseXorOperation(left, right),
+            "<<" => NodeType::ShiftLeftOperation(left, right),
+            ">>" => NodeType::ShiftRightOperation(left, right),
+            _    => {
+                exception::InternalException::new(
+                    format!("Invalid binary operator `{}`.", operator)
+                ).dump_critical();
+            }
+        };
+    }
 
 }
 impl std::fmt::Display for NodeType {
@@ -63,21 +219,86 @@ impl std::fmt::Display for NodeType {
             NodeType::LocalImport(name)    => format!("use {}", name),
 
 
-            NodeType::DefineFunction(target, args, return_type, body) => {
+            NodeType::DefineFunction(target, generics, args, return_type, body) => {
                 let mut res_args = Vec::new();
                 for i in 0..(args.len()) {
                     let (name, typ) = args[i].clone();
                     res_args.push(format!("{}: {}", name, typ));
                 }
-                let mut res_body = Vec::new();
-                for i in 0..(body.len()) {
-                    let expression = body[i].clone();
-                    res_body.push(format!("{};", expression));
+                format!("func {}{}({}): {} {}", target, format_generics(generics), res_args.join(", "), return_type, format_block(body))
+            },
+            NodeType::DefineClass(name, generics, bases, fields, methods) => {
+                let mut res_members = Vec::new();
+                for (field, typ, default) in fields.iter() {
+                    res_members.push(format!("{}: {}{};", field, typ, if (let Some(default) = default) {format!(" = {}", default)} else {String::new()}));
+                }
+                for method in methods.iter() {
+                    res_members.push(format!("{}{}", if (method.headers.has("static")) {"#[static] "} else {""}, method));
+                }
+                format!("class {}{}{} {{{}}}", name, format_generics(generics), if (bases.len() >= 1) {format!(" extends {}", bases.join(", "))} else {String::new()}, res_members.join(" "))
+            },
+            NodeType::DefineEnum(name, variants) => {
+                let mut res_variants = Vec::new();
+                for (variant, payload) in variants.iter() {
+                    let named = payload.iter().any(|(field, _)| field.is_some());
+                    let mut res_payload = Vec::new();
+                    for (field, typ) in payload {
+                        res_payload.push(if (let Some(field) = field) {format!("{}: {}", field, typ)} else {typ.to_string()});
+                    }
+                    res_variants.push(format!("{}{}", variant,
+                        if (res_payload.len() <= 0) {String::new()}
+                        else if (named) {format!(" {{{}}}", res_payload.join(", "))}
+                        else {format!("({})", res_payload.join(", "))}
+                    ));
                 }
-                format!("func {}({}): {} {{{}}}", target, res_args.join(", "), return_type, res_body.join(" "))
+                format!("enum {} {{{}}}", name, res_variants.join(", "))
+            },
+            NodeType::DefineTrait(name, methods) => {
+                let mut res_methods = Vec::new();
+                for method in methods.iter() {
+                    if (let NodeType::DefineFunction(method_name, generics, args, return_type, _) = &method.node) {
+                        let mut res_args = Vec::new();
+                        for (arg, typ) in args.iter() {
+                            res_args.push(format!("{}: {}", arg, typ));
+                        }
+                        res_methods.push(format!("func {}{}({}): {};", method_name, format_generics(generics), res_args.join(", "), return_type));
+                    }
+                }
+                format!("trait {} {{{}}}", name, res_methods.join(" "))
+            },
+            NodeType::ImplementTrait(name, class, methods) => {
+                let mut res_methods = Vec::new();
+                for method in methods.iter() {
+                    res_methods.push(format!("{}", method));
+                }
+                format!("impl {} for {} {{{}}}", name, class, res_methods.join(" "))
             },
             NodeType::InitializeVariable(mutable, name, typ, value) => format!("let{} {}: {} {}", if (*mutable) {" mut"} else {""}, name, typ, if (let Some(val) = *value.clone()) {format!("= {}", val)} else {String::new()}),
-            NodeType::AssignVariable(parent, value)                 => format!("{} = {}", parent, value),
+            NodeType::AssignVariable(parent, operator, value)       => format!("{} {}= {}", parent, operator.unwrap_or(""), value),
+
+
+            NodeType::If(branches, fallback) => {
+                let mut res_branches = Vec::new();
+                for (condition, body) in branches.iter() {
+                    res_branches.push(format!("if {} {}", condition, format_block(body)));
+                }
+                format!("{}{}", res_branches.join(" else "), if (let Some(body) = &**fallback) {format!(" else {}", format_block(body))} else {String::new()})
+            },
+            NodeType::While(label, condition, body)     => format!("{}while {} {}", format_label(label), condition, format_block(body)),
+            NodeType::Loop(label, body)                 => format!("{}loop {}", format_label(label), format_block(body)),
+            NodeType::For(label, name, iterable, body)  => format!("{}for {} in {} {}", format_label(label), name, iterable, format_block(body)),
+            NodeType::Break(label)                      => format!("break{}", if (let Some(label) = label) {format!(" {}", label)} else {String::new()}),
+            NodeType::Continue(label)                   => format!("continue{}", if (let Some(label) = label) {format!(" {}", label)} else {String::new()}),
+            NodeType::Return(value)                     => format!("return{}", if (let Some(value) = &**value) {format!(" {}", value)} else {String::new()}),
+            NodeType::BlockResult(value)                => format!("{}", value),
+            NodeType::BoundsCheck(value, bounds)        => format!("({} in {})", value, bounds),
+            NodeType::Match(value, arms)                => {
+                let mut res_arms = Vec::new();
+                for (pattern, body) in arms.iter() {
+                    res_arms.push(format!("{} => {}", pattern, format_block(body)));
+                }
+                format!("match {} {{{}}}", value, res_arms.join(", "))
+            },
 
 
             NodeType::AdditionOperation(left, right)       => format!("({} + {})", left, right),
@@ -85,9 +306,25 @@ impl std::fmt::Display for NodeType {
             NodeType::MultiplicationOperation(left, right) => format!("({} * {})", left, right),
             NodeType::DivisionOperation(left, right)       => format!("({} / {})", left, right),
             NodeType::PowerOperation(left, right)          => format!("({} ** {})", left, right),
+            NodeType::ModuloOperation(left, right)         => format!("({} % {})", left, right),
             NodeType::OppositeOperation(value)             => format!("(- {})", value),
             NodeType::InvertOperation(value)               => format!("(! {})", value),
 
+            NodeType::EqualOperation(left, right)          => format!("({} == {})", left, right),
+            NodeType::NotEqualOperation(left, right)       => format!("({} != {})", left, right),
+            NodeType::LessOperation(left, right)           => format!("({} < {})", left, right),
+            NodeType::LessEqualOperation(left, right)      => format!("({} <= {})", left, right),
+            NodeType::GreaterOperation(left, right)        => format!("({} > {})", left, right),
+            NodeType::GreaterEqualOperation(left, right)   => format!("({} >= {})", left, right),
+            NodeType::AndOperation(left, right)            => format!("({} && {})", left, right),
+            NodeType::OrOperation(left, right)             => format!("({} || {})", left, right),
+
+            NodeType::BitwiseAndOperation(left, right)     => format!("({} & {})", left, right),
+            NodeType::BitwiseOrOperation(left, right)      => format!("({} | {})", left, right),
+            NodeType::BitwiseXorOperation(left, right)     => format!("({} ^ {})", left, right),
+            NodeType::ShiftLeftOperation(left, right)      => format!("({} << {})", left, right),
+            NodeType::ShiftRightOperation(left, right)     => format!("({} >> {})", left, right),
+
 
             NodeType::ModuleMember(parent, name) => format!("{}::{}", parent, name),
             NodeType::ClassMember(parent, name)  => format!("{}.{}", parent, name),
@@ -104,13 +341,43 @@ impl std::fmt::Display for NodeType {
 
             NodeType::Type(base, arguments) => {
                 let mut res_arguments = Vec::new();
-                for i in 0..(arguments.len()) {
-                    let base = arguments[i].clone();
-                    res_arguments.push(format!("{}", base));
+                for argument in arguments.iter() {
+                    res_arguments.push(format!("{}", argument));
+                }
+                // The argument list of `Func<<A, B>, R>` has no base.
+                format!("{}{}", base, if (arguments.len() >= 1 || matches!(base, data::Type::Base(parts) if parts.len() <= 0)) {format!("<{}>", res_arguments.join(", "))} else {String::new()})
+            }
+            NodeType::Literal(value)        => format!("{}", value),
+            NodeType::Pattern(pattern)      => format!("{}", pattern),
+            NodeType::Range(min, max, step) => format!("{}..{}{}", min, max, if (let Some(step) = *step.clone()) {format!("..{}", step)} else {String::new()}),
+            NodeType::Tuple(values)         => {
+                let mut res_values = Vec::new();
+                for value in values.iter() {
+                    res_values.push(format!("{}", value));
+                }
+                format!("<{}>", res_values.join(", "))
+            },
+            NodeType::Array(values)         => {
+                let mut res_values = Vec::new();
+                for value in values.iter() {
+                    res_values.push(format!("{}", value));
+                }
+                format!("[{}]", res_values.join(", "))
+            },
+            NodeType::Dict(entries)         => {
+                let mut res_entries = Vec::new();
+                for (key, value) in entries.iter() {
+                    res_entries.push(format!("{}: {}", key, value));
+                }
+                format!("{{{}}}", res_entries.join(", "))
+            },
+            NodeType::Function(args, return_type, body, _) => {
+                let mut res_args = Vec::new();
+                for (name, typ) in args.iter() {
+                    res_args.push(format!("{}: {}", name, typ));
                 }
-                format!("{}{}", base, if (arguments.len() >= 1) {format!("{}", res_arguments.join(", "))} else {String::new()})
+                format!("func ({}): {} {}", res_args.join(", "), return_type, format_block(body))
             }
-            NodeType::Literal(value)        => format!("{}", value)
 
         });
     }
@@ -118,37 +385,84 @@ impl std::fmt::Display for NodeType {
 
 
 
+fn map_nodes(nodes : Vec<Node>, f : &mut dyn FnMut(Node) -> Node) -> Vec<Node> {
+    return nodes.into_iter().map(|node| node.map(f)).collect();
+}
+
+
+
+fn format_block(body : &[Node]) -> String {
+    let mut res_body = Vec::new();
+    for expression in body.iter() {
+        res_body.push(if (matches!(expression.node, NodeType::BlockResult(_))) {format!("{}", expression)} else {format!("{};", expression)});
+    }
+    return format!("{{{}}}", res_body.join(" "));
+}
+
+fn format_generics(generics : &[(String, Vec<String>)]) -> String {
+    return if (generics.len() >= 1) {
+        format!("<{}>", generics.iter().map(|(name, bounds)| if (bounds.len() >= 1) {format!("{}: {}", name, bounds.join(" + "))} else {name.clone()}).collect::<Vec<String>>().join(", "))
+    } else {
+        String::new()
+    };
+}
+
+fn format_label(label : &Option<String>) -> String {
+    return if (let Some(label) = label) {format!("{}: ", label)} else {String::new()};
+}
+
+
+
 #[derive(Clone)]
 pub struct NodeHeaders {
-    pub is_entry  : bool,
-    pub is_static : bool,
-    pub is_public : bool
+    pub attributes : Vec<data::Attribute>,
+    pub cfg        : Vec<data::Predicate> // the node is left out unless all of them hold
 }
 impl NodeHeaders {
     pub fn new() -> NodeHeaders {
         return NodeHeaders {
-            is_entry  : false,
-            is_static : false,
-            is_public : false
+            attributes : Vec::new(),
+            cfg        : Vec::new()
         }
     }
-    pub fn from(array : Vec<(String, data::Range)>) -> NodeHeaders {
+    // Checks the attributes of an item. Unknown attributes are only warned about.
+    pub fn from(attributes : Vec<data::Attribute>, target : data::AttributeTarget) -> NodeHeaders {
         let mut headers = NodeHeaders::new();
-        for i in 0..array.len() {
-            let (name, range) = array[i].clone();
-            match (name.as_str()) {
-                "entry"  => headers.is_entry  = true,
-                "static" => headers.is_static = true,
-                "public" => headers.is_public = true,
-                _        => {
+        for attribute in attributes {
+            if (attribute.name != "cfg" && headers.has(&attribute.name)) {
+                exception::ParserException::new(
+                    exception::ParserExceptionType::InvalidHeader,
+                    format!("Attribute `{}` is given more than once.", attribute.name),
+                    attribute.range.clone()
+                ).dump_error();
+            }
+            match (data::check_attribute(&attribute, target)) {
+                Ok(predicate) => {
+                    headers.cfg.extend(predicate);
+                    headers.attributes.push(attribute);
+                },
+                Err(data::AttributeError::Unknown(message)) => {
                     exception::ParserException::new(
                         exception::ParserExceptionType::InvalidHeader,
-                        format!("Invalid header `{}`.", name),
-                        range
+                        message,
+                        attribute.range.clone()
                     ).dump_warning();
+                },
+                Err(data::AttributeError::Invalid(message, range)) => {
+                    exception::ParserException::new(
+                        exception::ParserExceptionType::InvalidHeader,
+                        message,
+                        range
+                    ).dump_error();
                 }
             }
         }
         return headers;
     }
-}
\ No newline at end of file
+    pub fn has(&self, name : &str) -> bool {
+        return self.attributes.iter().any(|attribute| attribute.name == name);
+    }
+    pub fn is_enabled(&self) -> bool {
+        return self.cfg.iter().all(|predicate| predicate.holds());
+    }
+}
@@ -1,28 +1,45 @@
-use std;
-
 use crate::data;
-use crate::exception;
-use crate::exception::Exception;
 
 
+
+#[derive(Clone)]
 pub struct Object {
-    value : ObjectType
+    pub value : ObjectType,
+    pub range : data::Range
 }
 impl Object {
 
-    pub fn new(value : ObjectType) -> Object {
+    pub fn new(value : ObjectType, range : data::Range) -> Object {
         return Object {
-            value : value
+            value,
+            range
         };
     }
 
 }
 
 
+#[derive(Clone)]
 pub enum ObjectType {
 
     Module(std::collections::HashMap<String, ObjectType>),
 
-    Function(Vec<(String, data::Node)>, data::Node, Vec<data::Node>)
+    LocalModule(String), // key, a script used with `use`
+
+    BuiltinType(String), // name, a built in type named through `std::types`
+
+    Keyword(String), // qualified name, a native function like `std::scope::return` that the validator checks itself
+
+    Function(Vec<(String, Vec<String>)>, Vec<(String, data::Node)>, data::Node), // generics(name, bounds), args(name, type), return_type
+
+    Class(Vec<(String, Vec<String>)>, Vec<String>, Vec<String>, Vec<(String, data::Node, Option<data::Node>)>, Vec<data::Node>), // generics(name, bounds), bases, method_resolution_order, fields(name, type, default), methods
+
+    Enum(Vec<(String, Vec<(Option<String>, data::ValueType)>)>), // variants(name, payload(field, type))
+
+    Trait(Vec<data::Node>, Vec<String>), // methods(without content), implementing classes
+
+    Variable(bool, data::ValueType), // mutable, type
+
+    Parameter(Vec<String>) // bounds, a type parameter of a generic function or class
 
 }
@@ -0,0 +1,49 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+
+use crate::data;
+
+
+
+// The values of the names at runtime, the counterpart of `Context`.
+#[derive(Clone)]
+pub struct Scope {
+    pub parent  : Box<Option<Scope>>,
+    pub symbols : Rc<RefCell<std::collections::HashMap<String, data::Value>>>
+}
+impl Scope {
+
+    pub fn new(parent : Option<Scope>) -> Scope {
+        return Scope {
+            parent  : Box::new(parent),
+            symbols : Rc::new(RefCell::new(std::collections::HashMap::new()))
+        };
+    }
+
+    pub fn get_name(&self, name : &String) -> Option<data::Value> {
+        if (let Some(value) = self.symbols.borrow().get(name)) {
+            return Some(value.clone());
+        }
+        if (let Some(parent) = &*self.parent) {
+            return parent.get_name(name);
+        }
+        return None;
+    }
+
+    pub fn declare_name(&self, name : String, value : data::Value) -> () {
+        self.symbols.borrow_mut().insert(name, value);
+    }
+
+    // Changes the closest existing name. Returns whether one was found.
+    pub fn set_name(&self, name : &String, value : data::Value) -> bool {
+        if (self.symbols.borrow().contains_key(name)) {
+            self.symbols.borrow_mut().insert(name.clone(), value);
+            return true;
+        }
+        if (let Some(parent) = &*self.parent) {
+            return parent.set_name(name, value);
+        }
+        return false;
+    }
+
+}
@@ -0,0 +1,198 @@
+use std::rc::Rc;
+
+use crate::data;
+
+
+
+// The concrete syntax tree : every token of the script, trivia included,
+// grouped by the syntax that it belongs to.
+#[derive(Clone)]
+pub enum SyntaxElement {
+    Node(Rc<SyntaxNode>),
+    Token(Rc<data::Token>)
+}
+impl std::fmt::Display for SyntaxElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        return match (self) {
+            SyntaxElement::Node(node)   => write!(f, "{}", node),
+            SyntaxElement::Token(token) => write!(f, "{}", token.full_text())
+        };
+    }
+}
+
+pub struct SyntaxNode {
+    pub syntax   : SyntaxType,
+    pub children : Vec<SyntaxElement>
+}
+impl SyntaxNode {
+    pub fn new(syntax : SyntaxType, children : Vec<SyntaxElement>) -> SyntaxNode {
+        return SyntaxNode {
+            syntax,
+            children
+        };
+    }
+}
+impl std::fmt::Display for SyntaxNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for child in &self.children {
+            write!(f, "{}", child)?;
+        }
+        return Ok(());
+    }
+}
+
+
+
+#[derive(Clone)]
+pub struct SyntaxBuilder {
+    parents  : Vec<(SyntaxType, usize)>,
+    children : Vec<SyntaxElement>
+}
+impl SyntaxBuilder {
+    pub fn new() -> SyntaxBuilder {
+        return SyntaxBuilder {
+            parents  : Vec::new(),
+            children : Vec::new()
+        };
+    }
+    pub fn start_node(&mut self, syntax : SyntaxType) -> () {
+        self.parents.push((syntax, self.children.len()));
+    }
+    pub fn checkpoint(&self) -> usize {
+        return self.children.len();
+    }
+    pub fn start_node_at(&mut self, checkpoint : usize, syntax : SyntaxType) -> () {
+        self.parents.push((syntax, checkpoint));
+    }
+    pub fn token(&mut self, token : data::Token) -> () {
+        self.children.push(SyntaxElement::Token(Rc::new(token)));
+    }
+    pub fn finish_node(&mut self) -> () {
+        let (syntax, first) = self.parents.pop().unwrap();
+        let children        = self.children.drain(first..).collect::<Vec<SyntaxElement>>();
+        self.children.push(SyntaxElement::Node(Rc::new(SyntaxNode::new(syntax, children))));
+    }
+    pub fn finish(mut self) -> Rc<SyntaxNode> {
+        while (self.parents.len() >= 1) {
+            self.finish_node();
+        }
+        return if (let Some(SyntaxElement::Node(root)) = self.children.pop()) {
+            root
+        } else {
+            Rc::new(SyntaxNode::new(SyntaxType::Root, Vec::new()))
+        };
+    }
+}
+
+
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum SyntaxType {
+
+    Root,
+
+    ExternalImport,
+    LocalImport,
+    Header,
+
+    DefineFunction,
+    FunctionArgument,
+    Generics,
+    DefineClass,
+    ClassField,
+    DefineEnum,
+    EnumVariant,
+    DefineTrait,
+    ImplementTrait,
+    Block,
+    ExpressionStatement,
+    InitializeVariable,
+    AssignVariable,
+
+    If,
+    While,
+    Loop,
+    For,
+    Label,
+    Jump,
+    Return,
+    Match,
+    MatchArm,
+    Pattern,
+
+    BinaryOperation,
+    UnaryOperation,
+
+    ModuleMember,
+    ClassMember,
+    Slice,
+    Call,
+
+    Type,
+    Literal,
+    Group,
+    Range,
+    Tuple,
+    Array,
+    Dict,
+    DictEntry,
+    Function
+
+}
+impl std::fmt::Display for SyntaxType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        return write!(f, "{}", match (self) {
+
+            SyntaxType::Root                => "Root",
+
+            SyntaxType::ExternalImport      => "ExternalImport",
+            SyntaxType::LocalImport         => "LocalImport",
+            SyntaxType::Header              => "Header",
+
+            SyntaxType::DefineFunction      => "DefineFunction",
+            SyntaxType::FunctionArgument    => "FunctionArgument",
+            SyntaxType::Generics            => "Generics",
+            SyntaxType::DefineClass         => "DefineClass",
+            SyntaxType::ClassField          => "ClassField",
+            SyntaxType::DefineEnum          => "DefineEnum",
+            SyntaxType::EnumVariant         => "EnumVariant",
+            SyntaxType::DefineTrait         => "DefineTrait",
+            SyntaxType::ImplementTrait      => "ImplementTrait",
+            SyntaxType::Block               => "Block",
+            SyntaxType::ExpressionStatement => "ExpressionStatement",
+            SyntaxType::InitializeVariable  => "InitializeVariable",
+            SyntaxType::AssignVariable      => "AssignVariable",
+
+            SyntaxType::If                  => "If",
+            SyntaxType::While               => "While",
+            SyntaxType::Loop                => "Loop",
+            SyntaxType::For                 => "For",
+            SyntaxType::Label               => "Label",
+            SyntaxType::Jump                => "Jump",
+            SyntaxType::Return              => "Return",
+            SyntaxType::Match               => "Match",
+            SyntaxType::MatchArm            => "MatchArm",
+            SyntaxType::Pattern             => "Pattern",
+
+            SyntaxType::BinaryOperation     => "BinaryOperation",
+            SyntaxType::UnaryOperation      => "UnaryOperation",
+
+            SyntaxType::ModuleMember        => "ModuleMember",
+            SyntaxType::ClassMember         => "ClassMember",
+            SyntaxType::Slice               => "Slice",
+            SyntaxType::Call                => "Call",
+
+            SyntaxType::Type                => "Type",
+            SyntaxType::Literal             => "Literal",
+            SyntaxType::Group               => "Group",
+            SyntaxType::Range               => "Range",
+            SyntaxType::Tuple               => "Tuple",
+            SyntaxType::Array               => "Array",
+            SyntaxType::Dict                => "Dict",
+            SyntaxType::DictEntry           => "DictEntry",
+            SyntaxType::Function            => "Function"
+
+        });
+    }
+}
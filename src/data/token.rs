@@ -4,21 +4,35 @@ use crate::data;
 
 #[derive(Clone)]
 pub struct Token {
-    pub token : TokenType,
-    pub range : data::Range
+    pub token    : TokenType,
+    pub range    : data::Range,
+    pub text     : String,      // Only set by a lossless lexer.
+    pub leading  : Vec<Trivia>, // Only set by a lossless lexer.
+    pub trailing : Vec<Trivia>  // Only set by a lossless lexer.
 }
 impl Token {
     pub fn new(token : TokenType, range : data::Range) -> Token {
         return Token {
-            token : token,
-            range : range
+            token,
+            range,
+            text     : String::new(),
+            leading  : Vec::new(),
+            trailing : Vec::new()
         }
     }
     pub fn new_void() -> Token {
-        return Token {
-            token : TokenType::Eof,
-            range : data::Range::new_void()
+        return Token::new(TokenType::Eof, data::Range::new_void());
+    }
+    pub fn full_text(&self) -> String {
+        let mut text = String::new();
+        for trivia in &self.leading {
+            text += trivia.text.as_str();
+        }
+        text += self.text.as_str();
+        for trivia in &self.trailing {
+            text += trivia.text.as_str();
         }
+        return text;
     }
 }
 impl std::fmt::Display for Token {
@@ -43,8 +57,10 @@ pub enum TokenType {
 
     Colon,
     Equals,
+    EqualsRCarat,
     DoubleColon,
     Period,
+    DoublePeriod,
     Comma,
 
     Plus,
@@ -52,9 +68,35 @@ pub enum TokenType {
     Astrisk,
     Slash,
     DoubleAstrisk,
+    Percent,
 
+    DoubleEquals,
+    BangEquals,
+    LCaratEquals,
+    RCaratEquals,
+
+    DoubleAmpersand,
+    DoublePipe,
     Bang,
 
+    Ampersand,
+    Pipe,
+    Circumflex,
+    DoubleLCarat,
+    DoubleRCarat,
+
+    PlusEquals,
+    MinusEquals,
+    AstriskEquals,
+    SlashEquals,
+    DoubleAstriskEquals,
+    PercentEquals,
+    AmpersandEquals,
+    PipeEquals,
+    CircumflexEquals,
+    DoubleLCaratEquals,
+    DoubleRCaratEquals,
+
     Identifier(String),
     Character(char),
     String(String),
@@ -81,8 +123,10 @@ impl std::fmt::Display for TokenType {
 
             TokenType::Colon            => String::from(":"),
             TokenType::Equals           => String::from("="),
+            TokenType::EqualsRCarat     => String::from("=>"),
             TokenType::DoubleColon      => String::from("::"),
             TokenType::Period           => String::from("."),
+            TokenType::DoublePeriod     => String::from(".."),
             TokenType::Comma            => String::from(","),
 
             TokenType::Plus             => String::from("+"),
@@ -90,9 +134,35 @@ impl std::fmt::Display for TokenType {
             TokenType::Astrisk          => String::from("*"),
             TokenType::Slash            => String::from("/"),
             TokenType::DoubleAstrisk    => String::from("**"),
+            TokenType::Percent          => String::from("%"),
 
+            TokenType::DoubleEquals     => String::from("=="),
+            TokenType::BangEquals       => String::from("!="),
+            TokenType::LCaratEquals     => String::from("<="),
+            TokenType::RCaratEquals     => String::from(">="),
+
+            TokenType::DoubleAmpersand  => String::from("&&"),
+            TokenType::DoublePipe       => String::from("||"),
             TokenType::Bang             => String::from("!"),
 
+            TokenType::Ampersand        => String::from("&"),
+            TokenType::Pipe             => String::from("|"),
+            TokenType::Circumflex       => String::from("^"),
+            TokenType::DoubleLCarat     => String::from("<<"),
+            TokenType::DoubleRCarat     => String::from(">>"),
+
+            TokenType::PlusEquals          => String::from("+="),
+            TokenType::MinusEquals         => String::from("-="),
+            TokenType::AstriskEquals       => String::from("*="),
+            TokenType::SlashEquals         => String::from("/="),
+            TokenType::DoubleAstriskEquals => String::from("**="),
+            TokenType::PercentEquals       => String::from("%="),
+            TokenType::AmpersandEquals     => String::from("&="),
+            TokenType::PipeEquals          => String::from("|="),
+            TokenType::CircumflexEquals    => String::from("^="),
+            TokenType::DoubleLCaratEquals  => String::from("<<="),
+            TokenType::DoubleRCaratEquals  => String::from(">>="),
+
             TokenType::Identifier(name) => name.clone(),
             TokenType::Character(ch)    => format!("\'{}\'", ch),
             TokenType::String(text)     => format!("\"{}\"", text),
@@ -104,4 +174,27 @@ impl std::fmt::Display for TokenType {
 
         });
     }
-}
\ No newline at end of file
+}
+
+
+
+#[derive(Clone)]
+pub struct Trivia {
+    pub trivia : TriviaType,
+    pub text   : String
+}
+impl Trivia {
+    pub fn new(trivia : TriviaType, text : String) -> Trivia {
+        return Trivia {
+            trivia,
+            text
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub enum TriviaType {
+    Whitespace,
+    Newline,
+    Comment
+}
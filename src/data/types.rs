@@ -0,0 +1,292 @@
+use std::collections::HashMap;
+
+
+
+// The type of a value, as worked out by the validator.
+#[allow(dead_code)]
+#[derive(Clone, PartialEq)]
+pub enum ValueType {
+
+    Void,
+    Crash,
+
+    Bool,
+    Int,
+    RInt(i64, i64), // min, max
+    Float,
+    RFloat(f64, f64), // min, max
+    Char,
+    String,
+    IntRange,
+    FloatRange,
+
+    Tuple(Vec<ValueType>),
+    List(Box<ValueType>),
+    Array(Box<ValueType>, usize), // item, length
+    Dict(Box<ValueType>, Box<ValueType>),
+    Func(Vec<ValueType>, Box<ValueType>), // args, return_type
+    Class(Vec<String>, Vec<ValueType>), // method_resolution_order, arguments
+    Enum(String), // name
+
+    Parameter(String), // name
+    Generic(Vec<(String, Vec<String>)>, Box<ValueType>), // parameters(name, bounds), type
+
+    Unknown // Not checked, accepts and is accepted by everything.
+
+}
+impl ValueType {
+
+    pub fn accepts(&self, other : &ValueType) -> bool {
+        return match (self, other) {
+            (ValueType::Unknown, _) | (_, ValueType::Unknown) => true,
+            (_, ValueType::Crash)                             => true,
+            // The bounds of refined types are checked by the validator, or at runtime.
+            (ValueType::Int | ValueType::RInt(_, _), ValueType::Int | ValueType::RInt(_, _))         => true,
+            (ValueType::Float | ValueType::RFloat(_, _), ValueType::Float | ValueType::RFloat(_, _)) => true,
+            (ValueType::Tuple(a), ValueType::Tuple(b))         => a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| a.accepts(b)),
+            (ValueType::List(a), ValueType::List(b))           => a.accepts(b),
+            (ValueType::Array(a, la), ValueType::Array(b, lb)) => la == lb && a.accepts(b),
+            (ValueType::Dict(ak, av), ValueType::Dict(bk, bv)) => ak.accepts(bk) && av.accepts(bv),
+            (ValueType::Func(aa, ar), ValueType::Func(ba, br)) => aa.len() == ba.len() && aa.iter().zip(ba.iter()).all(|(a, b)| b.accepts(a)) && ar.accepts(br),
+            // The type arguments of a class must be the same on both sides.
+            (ValueType::Class(a, aa), ValueType::Class(b, ba)) => b.contains(&a[0]) && (a[0] != b[0] || (aa.len() == ba.len() && aa.iter().zip(ba.iter()).all(|(a, b)| a.accepts(b) && b.accepts(a)))),
+            (a, b)                                             => a == b
+        };
+    }
+
+    // The type with every parameter in `arguments` replaced by its argument.
+    pub fn substitute(&self, arguments : &HashMap<String, ValueType>) -> ValueType {
+        let substitute_all = |types : &Vec<ValueType>| types.iter().map(|typ| typ.substitute(arguments)).collect::<Vec<ValueType>>();
+        return match (self) {
+            ValueType::Parameter(name)            => arguments.get(name).cloned().unwrap_or(self.clone()),
+            ValueType::Tuple(items)               => ValueType::Tuple(substitute_all(items)),
+            ValueType::List(item)                 => ValueType::List(Box::new(item.substitute(arguments))),
+            ValueType::Array(item, length)        => ValueType::Array(Box::new(item.substitute(arguments)), *length),
+            ValueType::Dict(key, value)           => ValueType::Dict(Box::new(key.substitute(arguments)), Box::new(value.substitute(arguments))),
+            ValueType::Func(args, return_type)    => ValueType::Func(substitute_all(args), Box::new(return_type.substitute(arguments))),
+            ValueType::Class(order, class_args)   => ValueType::Class(order.clone(), substitute_all(class_args)),
+            ValueType::Generic(parameters, typ)   => {
+                // Inner parameters hide outer ones with the same name.
+                let mut inner = arguments.clone();
+                for (parameter, _) in parameters {
+                    inner.remove(parameter);
+                }
+                ValueType::Generic(parameters.clone(), Box::new(typ.substitute(&inner)))
+            },
+            typ                                   => typ.clone()
+        };
+    }
+
+    // Whether the parameter `name` is part of the type.
+    pub fn mentions(&self, name : &String) -> bool {
+        return match (self) {
+            ValueType::Parameter(parameter)                  => parameter == name,
+            ValueType::Tuple(items)                          => items.iter().any(|item| item.mentions(name)),
+            ValueType::List(item) | ValueType::Array(item, _) => item.mentions(name),
+            ValueType::Dict(key, value)                      => key.mentions(name) || value.mentions(name),
+            ValueType::Func(args, return_type)               => args.iter().any(|arg| arg.mentions(name)) || return_type.mentions(name),
+            ValueType::Class(_, class_args)                  => class_args.iter().any(|arg| arg.mentions(name)),
+            ValueType::Generic(parameters, typ)              => ! parameters.iter().any(|(parameter, _)| parameter == name) && typ.mentions(name),
+            _                                                => false
+        };
+    }
+
+    // Works out the arguments of `parameters` by matching `self` against
+    // `other`, the type that is found in its place. The first match of a
+    // parameter wins.
+    pub fn infer(&self, other : &ValueType, parameters : &Vec<String>, arguments : &mut HashMap<String, ValueType>) -> () {
+        match (self, other) {
+            (_, ValueType::Unknown | ValueType::Crash)                => {},
+            (ValueType::Parameter(name), other)
+                if (parameters.contains(name) && ! arguments.contains_key(name)) => {
+                arguments.insert(name.clone(), other.widened());
+            },
+            (ValueType::Tuple(a), ValueType::Tuple(b))
+            | (ValueType::Func(a, _), ValueType::Func(b, _))
+            | (ValueType::Class(_, a), ValueType::Class(_, b))      => {
+                for (a, b) in a.iter().zip(b.iter()) {
+                    a.infer(b, parameters, arguments);
+                }
+                if (let (ValueType::Func(_, a), ValueType::Func(_, b)) = (self, other)) {
+                    a.infer(b, parameters, arguments);
+                }
+            },
+            (ValueType::List(a), ValueType::List(b))
+            | (ValueType::Array(a, _), ValueType::Array(b, _))       => a.infer(b, parameters, arguments),
+            (ValueType::Dict(ak, av), ValueType::Dict(bk, bv))       => {
+                ak.infer(bk, parameters, arguments);
+                av.infer(bv, parameters, arguments);
+            },
+            _                                                        => {}
+        };
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        return matches!(self, ValueType::Int | ValueType::RInt(_, _) | ValueType::Float | ValueType::RFloat(_, _));
+    }
+
+    pub fn is_refined(&self) -> bool {
+        return matches!(self, ValueType::RInt(_, _) | ValueType::RFloat(_, _));
+    }
+
+    // The type without its bounds.
+    pub fn widened(&self) -> ValueType {
+        return match (self) {
+            ValueType::RInt(_, _)   => ValueType::Int,
+            ValueType::RFloat(_, _) => ValueType::Float,
+            typ                     => typ.clone()
+        };
+    }
+
+    // Whether every value in `self` is inside of `bounds`.
+    pub fn within(&self, bounds : &ValueType) -> bool {
+        return match (self, bounds) {
+            (ValueType::RInt(a, b), ValueType::RInt(c, d))     => c <= a && b <= d,
+            (ValueType::RFloat(a, b), ValueType::RFloat(c, d)) => c <= a && b <= d,
+            (ValueType::RInt(a, b), ValueType::RFloat(c, d))   => *c <= *a as f64 && *b as f64 <= *d,
+            _                                                  => false
+        };
+    }
+
+    // Whether no value in `self` is inside of `bounds`.
+    pub fn outside(&self, bounds : &ValueType) -> bool {
+        return match (self, bounds) {
+            (ValueType::RInt(a, b), ValueType::RInt(c, d))     => b < c || d < a,
+            (ValueType::RFloat(a, b), ValueType::RFloat(c, d)) => b < c || d < a,
+            _                                                  => false
+        };
+    }
+
+    pub fn refined_opposite(&self) -> Option<ValueType> {
+        return match (self) {
+            ValueType::RInt(a, b)   => Some(ValueType::RInt(b.checked_neg()?, a.checked_neg()?)),
+            ValueType::RFloat(a, b) => Some(ValueType::RFloat(- b, - a)),
+            _                       => None
+        };
+    }
+
+    // The bounds of `self operator other`, or `None` if they can not be
+    // worked out, or do not fit in an `Int`.
+    pub fn refined_operation(&self, operator : &str, other : &ValueType) -> Option<ValueType> {
+        return match (self, other) {
+            (ValueType::RInt(a, b), ValueType::RInt(c, d)) => {
+                let (a, b, c, d) = (*a as i128, *b as i128, *c as i128, *d as i128);
+                let (min, max) = match (operator) {
+                    "+" => (a + c, b + d),
+                    "-" => (a - d, b - c),
+                    "*" => corners([a * c, a * d, b * c, b * d]),
+                    // Division by zero fails at runtime.
+                    "/" if (c > 0 || d < 0) => corners([a / c, a / d, b / c, b / d]),
+                    "%" if (c > 0 || d < 0) => {
+                        let limit = c.abs().max(d.abs()) - 1;
+                        (if (a < 0) {a.max(- limit)} else {0}, if (b > 0) {b.min(limit)} else {0})
+                    },
+                    _ => return None
+                };
+                Some(ValueType::RInt(i64::try_from(min).ok()?, i64::try_from(max).ok()?))
+            },
+            (ValueType::RFloat(a, b), ValueType::RFloat(c, d)) => {
+                let (min, max) = match (operator) {
+                    "+" => (a + c, b + d),
+                    "-" => (a - d, b - c),
+                    "*" => corners([a * c, a * d, b * c, b * d]),
+                    "/" if (*c > 0.0 || *d < 0.0) => corners([a / c, a / d, b / c, b / d]),
+                    _ => return None
+                };
+                Some(ValueType::RFloat(min, max))
+            },
+            _ => None
+        };
+    }
+
+}
+impl std::fmt::Display for ValueType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        return write!(f, "{}", match (self) {
+
+            ValueType::Void              => String::from("Void"),
+            ValueType::Crash             => String::from("Crash"),
+
+            ValueType::Bool              => String::from("Bool"),
+            ValueType::Int               => String::from("Int"),
+            ValueType::RInt(min, max)    => format!("RInt<{}, {}>", min, max),
+            ValueType::Float             => String::from("Float"),
+            ValueType::RFloat(min, max)  => format!("RFloat<{:?}, {:?}>", min, max),
+            ValueType::Char              => String::from("Char"),
+            ValueType::String            => String::from("String"),
+            ValueType::IntRange          => String::from("IntRange"),
+            ValueType::FloatRange        => String::from("FloatRange"),
+
+            ValueType::Tuple(values)     => format!("Tuple<{}>", values.iter().map(|value| value.to_string()).collect::<Vec<String>>().join(", ")),
+            ValueType::List(value)       => format!("List<{}>", value),
+            ValueType::Array(value, len) => format!("Array<{}, {}>", value, len),
+            ValueType::Dict(key, value)  => format!("Dict<{}, {}>", key, value),
+            ValueType::Func(args, value) => format!("Func<<{}>, {}>", args.iter().map(|arg| arg.to_string()).collect::<Vec<String>>().join(", "), value),
+            ValueType::Class(order, args) => format!("{}{}", order[0], if (args.len() >= 1) {format!("<{}>", args.iter().map(|arg| arg.to_string()).collect::<Vec<String>>().join(", "))} else {String::new()}),
+            ValueType::Enum(name)        => name.clone(),
+
+            ValueType::Parameter(name)   => name.clone(),
+            ValueType::Generic(parameters, typ) => format!("<{}> {}", parameters.iter().map(|(name, bounds)| if (bounds.len() >= 1) {format!("{}: {}", name, bounds.join(" + "))} else {name.clone()}).collect::<Vec<String>>().join(", "), typ),
+
+            ValueType::Unknown           => String::from("?")
+
+        });
+    }
+}
+
+
+
+// The smallest and largest of four values.
+fn corners<T : PartialOrd + Copy>(values : [T; 4]) -> (T, T) {
+    let mut min = values[0];
+    let mut max = values[0];
+    for value in values {
+        if (value < min) {
+            min = value;
+        }
+        if (value > max) {
+            max = value;
+        }
+    }
+    return (min, max);
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refined_operation_bounds_every_result() {
+        let small    = ValueType::RInt(-2, 3);
+        let positive = ValueType::RInt(1, 4);
+        assert!(small.refined_operation("+", &positive) == Some(ValueType::RInt(-1, 7)));
+        assert!(small.refined_operation("-", &positive) == Some(ValueType::RInt(-6, 2)));
+        assert!(small.refined_operation("*", &positive) == Some(ValueType::RInt(-8, 12)));
+        assert!(small.refined_operation("/", &positive) == Some(ValueType::RInt(-2, 3)));
+        assert!(small.refined_operation("%", &positive) == Some(ValueType::RInt(-2, 3)));
+        assert!(ValueType::RFloat(0.5, 1.0).refined_operation("*", &ValueType::RFloat(-2.0, 2.0)) == Some(ValueType::RFloat(-2.0, 2.0)));
+    }
+
+    #[test]
+    fn refined_operation_gives_up_where_bounds_are_unknown() {
+        let small = ValueType::RInt(-2, 3);
+        assert!(small.refined_operation("/", &small).is_none());
+        assert!(small.refined_operation("+", &ValueType::Int).is_none());
+        assert!(ValueType::RInt(0, i64::MAX).refined_operation("+", &ValueType::RInt(0, 1)).is_none());
+        assert!(ValueType::RInt(i64::MIN, 0).refined_opposite().is_none());
+        assert!(ValueType::RInt(-2, 3).refined_opposite() == Some(ValueType::RInt(-3, 2)));
+    }
+
+    #[test]
+    fn refined_bounds_are_within_or_outside_of_others() {
+        let bounds = ValueType::RInt(0, 10);
+        assert!(ValueType::RInt(2, 5).within(&bounds));
+        assert!(! ValueType::RInt(-1, 5).within(&bounds));
+        assert!(! ValueType::RInt(-1, 5).outside(&bounds));
+        assert!(ValueType::RInt(11, 12).outside(&bounds));
+        assert!(ValueType::RInt(2, 5).within(&ValueType::RFloat(0.0, 5.0)));
+    }
+
+}
@@ -1,7 +1,16 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::data;
+
+
+
 #[allow(dead_code)]
 #[derive(Clone)]
 pub enum Literal {
     Name(String),
+    Bool(bool),
     Character(char),
     String(String),
     Integer(i64),
@@ -12,8 +21,9 @@ impl std::fmt::Display for Literal {
         return write!(f, "{}", match (self) {
 
             Literal::Name(name)      => name.clone(),
+            Literal::Bool(value)     => value.to_string(),
             Literal::Character(ch)   => format!("'{}'", ch),
-            Literal::String(text)    => format!("\"{}\'", text),
+            Literal::String(text)    => format!("\"{}\"", text),
             Literal::Integer(number) => number.to_string(),
             Literal::Float(number)   => number.to_string()
 
@@ -22,18 +32,222 @@ impl std::fmt::Display for Literal {
 }
 
 
+#[derive(Clone)]
+pub enum Pattern {
+    Wildcard,
+    Binding(String),
+    Literal(Literal),
+    Range(Literal, Literal), // min, max
+    Variant(String, String, Vec<(Option<String>, Pattern)>, bool) // enum, variant, payload(field, pattern), rest
+}
+impl std::fmt::Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        return write!(f, "{}", match (self) {
+            Pattern::Wildcard                                 => String::from("_"),
+            Pattern::Binding(name)                            => name.clone(),
+            Pattern::Literal(literal)                         => literal.to_string(),
+            Pattern::Range(min, max)                          => format!("{}..{}", min, max),
+            Pattern::Variant(name, variant, payload, rest)    => {
+                let mut res_payload = Vec::new();
+                for (field, pattern) in payload {
+                    res_payload.push(match (field, pattern) {
+                        (Some(field), Pattern::Binding(name)) if field == name => field.clone(),
+                        (Some(field), pattern)                                 => format!("{}: {}", field, pattern),
+                        (None, pattern)                                        => pattern.to_string()
+                    });
+                }
+                if (*rest) {
+                    res_payload.push(String::from(".."));
+                }
+                let named = payload.iter().any(|(field, _)| field.is_some());
+                format!("{}::{}{}", name, variant,
+                    if (res_payload.len() <= 0) {String::new()}
+                    else if (named) {format!(" {{{}}}", res_payload.join(", "))}
+                    else {format!("({})", res_payload.join(", "))}
+                )
+            }
+        });
+    }
+}
+
+
 #[derive(Clone)]
 pub enum Type {
     Base(Vec<String>),
-    Inferred,
-    Cancelled
+    Inferred
 }
 impl std::fmt::Display for Type {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         return write!(f, "{}", match (self) {
             Type::Base(parts) => parts.join("::"),
-            Type::Inferred    => String::from("?"),
-            Type::Cancelled   => String::from("Cancelled")
+            Type::Inferred    => String::from("?")
         });
     }
 }
+
+
+
+// A value at runtime.
+#[allow(dead_code)]
+#[derive(Clone)]
+pub enum Value {
+
+    Void,
+
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Char(char),
+    String(String),
+    IntRange(i64, i64, i64), // min, max, step
+    FloatRange(f64, f64, f64), // min, max, step
+
+    Tuple(Vec<Value>),
+    List(Vec<Value>),
+    Array(Vec<Value>),
+    Dict(Vec<(Value, Value)>), // entries(key, value)
+
+    Function(Rc<Function>),
+    Method(Box<Value>, Rc<Function>), // receiver, function
+    Class(Rc<Class>),
+    Instance(Rc<Instance>),
+    Enum(Rc<Enum>),
+    Variant(Rc<Enum>, usize, Vec<Value>), // enum, variant, payload
+    VariantConstructor(Rc<Enum>, usize), // enum, variant
+    Native(String, Vec<Value>), // name, bound arguments
+    Module(Rc<HashMap<String, Value>>),
+    LocalModule(String) // key
+
+}
+impl Value {
+
+    pub fn equals(&self, other : &Value) -> bool {
+        return match (self, other) {
+            (Value::Void, Value::Void)                       => true,
+            (Value::Bool(a), Value::Bool(b))                 => a == b,
+            (Value::Int(a), Value::Int(b))                   => a == b,
+            (Value::Float(a), Value::Float(b))               => a == b,
+            (Value::Char(a), Value::Char(b))                 => a == b,
+            (Value::String(a), Value::String(b))             => a == b,
+            (Value::IntRange(a, b, c), Value::IntRange(d, e, f))     => a == d && b == e && c == f,
+            (Value::FloatRange(a, b, c), Value::FloatRange(d, e, f)) => a == d && b == e && c == f,
+            (Value::Tuple(a), Value::Tuple(b))
+            | (Value::List(a), Value::List(b))
+            | (Value::Array(a), Value::Array(b))             => a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| a.equals(b)),
+            (Value::Dict(a), Value::Dict(b))                 => a.len() == b.len() && a.iter().all(|(key, value)| b.iter().any(|(other_key, other_value)| key.equals(other_key) && value.equals(other_value))),
+            (Value::Function(a), Value::Function(b))         => Rc::ptr_eq(a, b),
+            (Value::Method(a, f), Value::Method(b, g))       => a.equals(b) && Rc::ptr_eq(f, g),
+            (Value::Class(a), Value::Class(b))               => Rc::ptr_eq(a, b),
+            (Value::Instance(a), Value::Instance(b))         => Rc::ptr_eq(a, b),
+            (Value::Enum(a), Value::Enum(b))                 => Rc::ptr_eq(a, b),
+            (Value::Variant(a, i, x), Value::Variant(b, j, y)) => a.name == b.name && i == j && x.len() == y.len() && x.iter().zip(y.iter()).all(|(x, y)| x.equals(y)),
+            (Value::VariantConstructor(a, i), Value::VariantConstructor(b, j)) => Rc::ptr_eq(a, b) && i == j,
+            (Value::Native(a, x), Value::Native(b, y))       => a == b && x.len() == y.len() && x.iter().zip(y.iter()).all(|(x, y)| x.equals(y)),
+            (Value::Module(a), Value::Module(b))             => Rc::ptr_eq(a, b),
+            (Value::LocalModule(a), Value::LocalModule(b))   => a == b,
+            _                                                => false
+        };
+    }
+
+    pub fn type_name(&self) -> String {
+        return match (self) {
+            Value::Void                 => String::from("Void"),
+            Value::Bool(_)              => String::from("Bool"),
+            Value::Int(_)               => String::from("Int"),
+            Value::Float(_)             => String::from("Float"),
+            Value::Char(_)              => String::from("Char"),
+            Value::String(_)            => String::from("String"),
+            Value::IntRange(_, _, _)    => String::from("IntRange"),
+            Value::FloatRange(_, _, _)  => String::from("FloatRange"),
+            Value::Tuple(_)             => String::from("Tuple"),
+            Value::List(_)              => String::from("List"),
+            Value::Array(_)             => String::from("Array"),
+            Value::Dict(_)              => String::from("Dict"),
+            Value::Function(_)
+            | Value::Method(_, _)
+            | Value::VariantConstructor(_, _)
+            | Value::Native(_, _)       => String::from("Func"),
+            Value::Class(class)         => format!("class {}", class.name),
+            Value::Instance(instance)   => instance.class.name.clone(),
+            Value::Enum(enumeration)    => format!("enum {}", enumeration.name),
+            Value::Variant(enumeration, _, _) => enumeration.name.clone(),
+            Value::Module(_)
+            | Value::LocalModule(_)     => String::from("Module")
+        };
+    }
+
+}
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        return write!(f, "{}", match (self) {
+
+            Value::Void                    => String::from("Void"),
+
+            Value::Bool(value)             => value.to_string(),
+            Value::Int(value)              => value.to_string(),
+            Value::Float(value)            => value.to_string(),
+            Value::Char(value)             => value.to_string(),
+            Value::String(value)           => value.clone(),
+            Value::IntRange(min, max, step)   => format!("{}..{}..{}", min, max, step),
+            Value::FloatRange(min, max, step) => format!("{}..{}..{}", min, max, step),
+
+            Value::Tuple(values)           => format!("<{}>", values.iter().map(|value| value.to_string()).collect::<Vec<String>>().join(", ")),
+            Value::List(values)
+            | Value::Array(values)         => format!("[{}]", values.iter().map(|value| value.to_string()).collect::<Vec<String>>().join(", ")),
+            Value::Dict(entries)           => format!("{{{}}}", entries.iter().map(|(key, value)| format!("{}: {}", key, value)).collect::<Vec<String>>().join(", ")),
+
+            Value::Function(function)
+            | Value::Method(_, function)   => format!("func {}", function.name),
+            Value::Class(class)            => format!("class {}", class.name),
+            Value::Instance(instance)      => format!("{} {{{}}}", instance.class.name, instance.class.fields.iter()
+                .filter_map(|(field, _)| instance.fields.borrow().get(field).map(|value| format!("{}: {}", field, value)))
+                .collect::<Vec<String>>().join(", ")),
+            Value::Enum(enumeration)       => format!("enum {}", enumeration.name),
+            Value::Variant(enumeration, variant, payload) => {
+                let (name, fields) = &enumeration.variants[*variant];
+                if (payload.len() <= 0) {
+                    format!("{}::{}", enumeration.name, name)
+                } else if (fields.iter().any(|field| field.is_some())) {
+                    format!("{}::{} {{{}}}", enumeration.name, name, fields.iter().zip(payload.iter())
+                        .map(|(field, value)| format!("{}: {}", field.clone().unwrap_or_default(), value))
+                        .collect::<Vec<String>>().join(", "))
+                } else {
+                    format!("{}::{}({})", enumeration.name, name, payload.iter().map(|value| value.to_string()).collect::<Vec<String>>().join(", "))
+                }
+            },
+            Value::VariantConstructor(enumeration, variant) => format!("func {}::{}", enumeration.name, enumeration.variants[*variant].0),
+            Value::Native(name, _)         => format!("func {}", name),
+            Value::Module(_)               => String::from("Module"),
+            Value::LocalModule(key)        => format!("module {}", key)
+
+        });
+    }
+}
+
+
+pub struct Function {
+    pub name  : String,
+    pub args  : Vec<String>,
+    pub body  : Vec<data::Node>,
+    pub scope : data::Scope // the names an anonymous function captured, or the globals of its module
+}
+
+
+pub struct Class {
+    pub name    : String,
+    pub fields  : Vec<(String, Option<data::Node>)>, // name, default
+    pub methods : HashMap<String, (bool, Rc<Function>)>, // name, (is_static, function)
+    pub scope   : data::Scope // the globals of its module, for the defaults of the fields
+}
+
+
+pub struct Instance {
+    pub class  : Rc<Class>,
+    pub fields : RefCell<HashMap<String, Value>>
+}
+
+
+pub struct Enum {
+    pub name     : String,
+    pub variants : Vec<(String, Vec<Option<String>>)> // name, payload fields
+}
@@ -1,11 +1,48 @@
-use std;
-use colored;
 use colored::Colorize;
 
+use crate::config;
 use crate::data;
 
 
 
+// The status that the process exits with, other than the exit code that the
+// entry function returns.
+pub const EXIT_ERROR : i32 = 1;   // the command line or the script is invalid, or the compiler failed
+pub const EXIT_CRASH : i32 = 101; // the script crashed while running
+
+// In the REPL, an error ends the input that caused it instead of the process.
+static RECOVER : std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+pub fn set_recover(recover : bool) -> () {
+    RECOVER.store(recover, std::sync::atomic::Ordering::Relaxed);
+}
+
+// Ends the process, or unwinds to the REPL, which catches it.
+pub fn exit(code : i32) -> ! {
+    if (RECOVER.load(std::sync::atomic::Ordering::Relaxed)) {
+        std::panic::resume_unwind(Box::new(code));
+    }
+    std::process::exit(code);
+}
+
+// For the tests. Runs `function` with errors unwinding instead of ending the
+// process, and returns the exit code if one ended it. The tests take turns,
+// as the errors of the others would unwind too.
+#[cfg(test)]
+pub fn catch_exit<T>(function : impl FnOnce() -> T) -> Result<T, i32> {
+    static TURN : std::sync::Mutex<()> = std::sync::Mutex::new(());
+    let _turn  = TURN.lock().unwrap_or_else(|error| error.into_inner());
+    set_recover(true);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(function));
+    set_recover(false);
+    return result.map_err(|payload| match (payload.downcast::<i32>()) {
+        Ok(code)     => *code,
+        Err(payload) => std::panic::resume_unwind(payload)
+    });
+}
+
+
+
 #[derive(Clone, PartialEq)]
 pub enum ExceptionLevel {
     Warning,
@@ -22,53 +59,59 @@ pub trait Exception {
             ExceptionLevel::Error    => "Exception",
             ExceptionLevel::Critical => "CriticalException"
         };
-        /*let     position            = self.get_position();
-        let     range               = self.get_range();
-        let     real_text           = self.get_text();
-        let     leading_erase_count = get_leading_erase_count(real_text.clone());
-        let mut text                = real_text[leading_erase_count..(real_text.len())].to_string();
-        let     min_column          = range.min.column;
-        let     max_column          = if (range.max.line == range.min.line) {range.max.column} else {text.len() + 1};
-        // Line 4
-        while ([' ', '\t'].contains(&text.chars().nth(text.len() - 1).unwrap())) {
-            text.pop();
-        }
-        let     left                = &text[0..(min_column - leading_erase_count - 1)];
-        let     center              = &text[(min_column - leading_erase_count - 1)..(max_column - leading_erase_count)];
-        let     right               = &text[(max_column - leading_erase_count)..(text.len())];
-        // Line 5
-        let     underline_count     = max_column - min_column + 1;
-        let     underline           = "▔".repeat(underline_count);
-        // Print
-        println!("\n{}\n  {} `{}`, {} {},\n  {} {}, {} {}\n    {}{}{}\n    {}{}\n{}\n",
-            self.colourize(format!(" ═ {} {} ", prefix.bold(), "═".repeat(std::cmp::max(repeat - prefix.len(), 1))), level.clone()),
-            "File".blue(), self.get_filename().blue().bold(), "In".blue(), self.get_context().blue().bold(),
-            "Line".cyan(), (position.1 + 1).to_string().cyan().bold(), "Column".cyan(), (position.0).to_string().cyan().bold(),
-            left.green(), center.green().bold(), right.green(),
-            " ".repeat(min_column - leading_erase_count - 1), underline.green(),
-            self.colourize(format!(" ═ {} {} ", suffix.bold(), "═".repeat(std::cmp::max(repeat - suffix.len(), 1))), level)
-        );*/
         let prefix = format!("{}{}", self.get_prefix(), level_name);
         let suffix = format!("{}{}: {}", self.get_title(), level_name, self.get_message());
         let repeat = std::cmp::max(prefix.len(), suffix.len()) + 1;
-        println!("\n{}\n{}\n",
+        println!("\n{}\n{}{}\n",
             self.colourize(format!(" ═ {} {} ", prefix.bold(), "═".repeat(std::cmp::max(repeat - prefix.len(), 1))), level.clone()),
+            self.get_location(),
             self.colourize(format!(" ═ {} {} ", suffix.bold(), "═".repeat(std::cmp::max(repeat - suffix.len(), 1))), level)
-    )
+        );
+    }
+    // Where the exception happened, with its line underlined. Nothing if it
+    // has no source.
+    fn get_location(&self) -> String {
+        let (column, line) = self.get_position();
+        let text           = self.get_text();
+        if (text.trim().len() <= 0) {
+            return String::new();
+        }
+        let chars  = text.trim_end().chars().collect::<Vec<char>>();
+        let erase  = get_leading_erase_count(text.clone());
+        let min    = (column.max(1) - 1).clamp(erase, chars.len());
+        let max    = (min + self.get_width().max(1)).min(chars.len()).max(min);
+        let left   = chars[erase..min].iter().collect::<String>();
+        let center = chars[min..max].iter().collect::<String>();
+        let right  = chars[max..].iter().collect::<String>();
+        return format!("  {} `{}`, {} `{}`,\n  {}{} {}\n    {}{}{}\n    {}{}\n",
+            "File".blue(), self.get_filename().blue().bold(), "In".blue(), self.get_context().name.blue().bold(),
+            if (line >= 1) {format!("{} {}, ", "Line".cyan(), line.to_string().cyan().bold())} else {String::new()},
+            "Column".cyan(), column.to_string().cyan().bold(),
+            left.green(), center.green().bold(), right.green(),
+            " ".repeat(min - erase), "▔".repeat(std::cmp::max(max - min, 1)).green()
+        );
     }
+    // The `warnings` config can hide warnings or make them errors.
     fn dump_warning(&self) -> () {
-        self.dump(ExceptionLevel::Warning);
+        match (config::get("warnings").as_deref()) {
+            Some("allow") => {},
+            Some("deny")  => self.dump_error(),
+            _             => self.dump(ExceptionLevel::Warning)
+        }
     }
     fn dump_invalid(&self) -> () {
         self.dump(ExceptionLevel::Error);
     }
     fn dump_error(&self) -> ! {
         self.dump(ExceptionLevel::Error);
-        std::process::exit(1);
+        exit(self.get_exit_code());
     }
     fn dump_critical(&self) -> ! {
         self.dump(ExceptionLevel::Critical);
-        std::process::exit(1);
+        exit(EXIT_ERROR);
+    }
+    fn get_exit_code(&self) -> i32 {
+        return EXIT_ERROR;
     }
     fn colourize(&self, text : String, level : ExceptionLevel) -> colored::ColoredString {
         return match (level) {
@@ -80,9 +123,9 @@ pub trait Exception {
     fn get_prefix(&self) -> String;
     fn get_filename(&self) -> String;
     fn get_context(&self) -> data::Context;
-    fn get_position(&self) -> (usize, usize);
-    fn get_text(&self) -> String;
-    fn get_range(&self) -> data::Range;
+    fn get_position(&self) -> (usize, usize); // column, line, from 1
+    fn get_text(&self) -> String; // the line that the exception is on
+    fn get_width(&self) -> usize; // how many characters of the line to underline
     fn get_title(&self) -> String;
     fn get_message(&self) -> String;
 }
@@ -98,7 +141,7 @@ pub struct InternalException {
 impl InternalException {
     pub fn new(message : String) -> InternalException {
         return InternalException {
-            message : message
+            message
         };
     }
 }
@@ -118,11 +161,8 @@ impl Exception for InternalException {
     fn get_text(&self) -> String {
         return String::new();
     }
-    fn get_range(&self) -> data::Range {
-        return data::Range {
-            min : data::Position::new(0, 0, 0, String::from("<Void>"), String::from("<Void>")),
-            max : data::Position::new(0, 0, 0, String::from("<Void>"), String::from("<Void>"))
-        };
+    fn get_width(&self) -> usize {
+        return 0;
     }
     fn get_title(&self) -> String {
         return String::from("Internal");
@@ -143,10 +183,10 @@ pub struct CommandLineException {
 impl CommandLineException {
     pub fn new(exception_type : CommandLineExceptionType, message : String, arguments : Vec<String>, index : usize) -> CommandLineException {
         return CommandLineException {
-            exception_type : exception_type,
-            message        : message,
-            arguments      : arguments,
-            index          : index
+            exception_type,
+            message,
+            arguments,
+            index
         };
     }
 }
@@ -155,39 +195,21 @@ impl Exception for CommandLineException {
         return String::from("CommandLine");
     }
     fn get_filename(&self) -> String {
-        return String::from("<Void>");
+        return String::from("<Command Line>");
     }
     fn get_context(&self) -> data::Context {
         return data::Context::new(String::from("<Command Line>"), None);
     }
+    // The arguments are shown on one line, without a line number.
     fn get_position(&self) -> (usize, usize) {
-        let mut column = 0;
-        for i in 0..(self.arguments.len()) {
-            let argument = self.arguments[i].clone();
-            if (i == self.index) {
-                break;
-            }
-            column += argument.len() + 1;
-        };
-        return (0, column);
+        let column = self.arguments.iter().take(self.index).map(|argument| argument.chars().count() + 1).sum::<usize>();
+        return (column + 1, 0);
     }
     fn get_text(&self) -> String {
         return self.arguments.join(" ");
     }
-    fn get_range(&self) -> data::Range {
-        let mut column = 1;
-        for i in 0..(self.arguments.len()) {
-            let argument = self.arguments[i].clone();
-            if (i == self.index) {
-                break;
-            }
-            column += argument.len() + 1;
-        };
-        let arg_len = self.arguments[self.index].len();
-        return data::Range {
-            min : data::Position::new(column, 0, column, String::from("<Void>"), String::from("<Void>")),
-            max : data::Position::new(column + arg_len - 1, 0, column + arg_len - 1, String::from("<Void>"), String::from("<Void>"))
-        };
+    fn get_width(&self) -> usize {
+        return self.arguments.get(self.index).map(|argument| argument.chars().count()).unwrap_or(0);
     }
     fn get_title(&self) -> String {
         return self.exception_type.get_name();
@@ -200,9 +222,7 @@ impl Exception for CommandLineException {
 pub enum CommandLineExceptionType {
     
     FileFailedToRead,
-    Argument,
-
-    FutureFeature
+    Argument
 
 }
 impl ExceptionType for CommandLineExceptionType {
@@ -210,9 +230,68 @@ impl ExceptionType for CommandLineExceptionType {
         return String::from(match (self) {
 
             CommandLineExceptionType::FileFailedToRead => "FileFailedToRead",
-            CommandLineExceptionType::Argument         => "Argument",
-            
-            CommandLineExceptionType::FutureFeature    => "FutureFeature"
+            CommandLineExceptionType::Argument         => "Argument"
+
+        });
+    }
+}
+
+
+
+pub struct ConfigException {
+    exception_type : ConfigExceptionType,
+    message        : String,
+    range          : data::Range
+}
+impl ConfigException {
+    pub fn new(exception_type : ConfigExceptionType, message : String, range : data::Range) -> ConfigException {
+        return ConfigException {
+            exception_type,
+            message,
+            range
+        };
+    }
+}
+impl Exception for ConfigException {
+    fn get_prefix(&self) -> String {
+        return String::from("Config");
+    }
+    fn get_filename(&self) -> String {
+        return self.range.min.filename.clone();
+    }
+    fn get_context(&self) -> data::Context {
+        return data::Context::new(String::from("<Config>"), None);
+    }
+    fn get_position(&self) -> (usize, usize) {
+        let (line, column, _, _) = locate(&self.range);
+        return (column, line);
+    }
+    fn get_text(&self) -> String {
+        return locate(&self.range).2;
+    }
+    fn get_width(&self) -> usize {
+        return locate(&self.range).3;
+    }
+    fn get_title(&self) -> String {
+        return self.exception_type.get_name();
+    }
+    fn get_message(&self) -> String {
+        return self.message.clone();
+    }
+}
+
+pub enum ConfigExceptionType {
+
+    MissingEquals,
+    InvalidConfig
+
+}
+impl ExceptionType for ConfigExceptionType {
+    fn get_name(&self) -> String {
+        return String::from(match (self) {
+
+            ConfigExceptionType::MissingEquals => "MissingEquals",
+            ConfigExceptionType::InvalidConfig => "InvalidConfig"
 
         });
     }
@@ -228,9 +307,9 @@ pub struct LexerException {
 impl LexerException {
     pub fn new(exception_type : LexerExceptionType, message : String, range : data::Range) -> LexerException {
         return LexerException {
-            exception_type : exception_type,
-            message        : message,
-            range          : range
+            exception_type,
+            message,
+            range
         };
     }
 }
@@ -239,22 +318,20 @@ impl Exception for LexerException {
         return String::from("Lexer");
     }
     fn get_filename(&self) -> String {
-        return String::from(self.range.min.filename.clone());
+        return self.range.min.filename.clone();
     }
     fn get_context(&self) -> data::Context {
         return data::Context::new(String::from("<Lexer>"), None);
     }
     fn get_position(&self) -> (usize, usize) {
-        return (self.range.min.column, self.range.min.line);
+        let (line, column, _, _) = locate(&self.range);
+        return (column, line);
     }
     fn get_text(&self) -> String {
-        return String::from(self.range.min.script.split("\n").collect::<Vec<&str>>()[self.range.min.line]);
+        return locate(&self.range).2;
     }
-    fn get_range(&self) -> data::Range {
-        return data::Range {
-            min : data::Position::new(self.range.min.index, 0, self.range.min.column, self.range.min.filename.clone(), String::from("<Void>")),
-            max : data::Position::new(self.range.max.index, 0, self.range.max.column, self.range.min.filename.clone(), String::from("<Void>"))
-        };
+    fn get_width(&self) -> usize {
+        return locate(&self.range).3;
     }
     fn get_title(&self) -> String {
         return self.exception_type.get_name();
@@ -293,9 +370,9 @@ pub struct ParserException {
 impl ParserException {
     pub fn new(exception_type : ParserExceptionType, message : String, range : data::Range) -> ParserException {
         return ParserException {
-            exception_type : exception_type,
-            message        : message,
-            range          : range
+            exception_type,
+            message,
+            range
         };
     }
 }
@@ -304,22 +381,20 @@ impl Exception for ParserException {
         return String::from("Parser");
     }
     fn get_filename(&self) -> String {
-        return String::from(self.range.min.filename.clone());
+        return self.range.min.filename.clone();
     }
     fn get_context(&self) -> data::Context {
         return data::Context::new(String::from("<Parser>"), None);
     }
     fn get_position(&self) -> (usize, usize) {
-        return (self.range.min.column, self.range.min.line);
+        let (line, column, _, _) = locate(&self.range);
+        return (column, line);
     }
     fn get_text(&self) -> String {
-        return String::from(self.range.min.script.split("\n").collect::<Vec<&str>>()[self.range.min.line]);
+        return locate(&self.range).2;
     }
-    fn get_range(&self) -> data::Range {
-        return data::Range {
-            min : data::Position::new(self.range.min.index, 0, self.range.min.column, self.range.min.filename.clone(), String::from("<Void>")),
-            max : data::Position::new(self.range.max.index, 0, self.range.max.column, self.range.min.filename.clone(), String::from("<Void>"))
-        };
+    fn get_width(&self) -> usize {
+        return locate(&self.range).3;
     }
     fn get_title(&self) -> String {
         return self.exception_type.get_name();
@@ -333,7 +408,11 @@ pub enum ParserExceptionType {
 
     MissingToken,
     InvalidHeader,
-    InvalidMutability
+    InvalidMutability,
+    InvalidAssignment,
+    MissingModule,
+    AmbiguousModule,
+    CyclicImport
 
 }
 impl ExceptionType for ParserExceptionType {
@@ -342,7 +421,11 @@ impl ExceptionType for ParserExceptionType {
 
             ParserExceptionType::MissingToken      => "MissingToken",
             ParserExceptionType::InvalidHeader     => "InvalidHeader",
-            ParserExceptionType::InvalidMutability => "InvalidMutability"
+            ParserExceptionType::InvalidMutability => "InvalidMutability",
+            ParserExceptionType::InvalidAssignment => "InvalidAssignment",
+            ParserExceptionType::MissingModule     => "MissingModule",
+            ParserExceptionType::AmbiguousModule   => "AmbiguousModule",
+            ParserExceptionType::CyclicImport      => "CyclicImport"
 
         });
     }
@@ -359,10 +442,10 @@ pub struct ValidatorException {
 impl ValidatorException {
     pub fn new(exception_type : ValidatorExceptionType, message : String, range : data::Range, context : data::Context) -> ValidatorException {
         return ValidatorException {
-            exception_type : exception_type,
-            message        : message,
-            range          : range,
-            context        : context
+            exception_type,
+            message,
+            range,
+            context
         };
     }
 }
@@ -371,22 +454,20 @@ impl Exception for ValidatorException {
         return String::from("Validator");
     }
     fn get_filename(&self) -> String {
-        return String::from(self.range.min.filename.clone());
+        return self.range.min.filename.clone();
     }
     fn get_context(&self) -> data::Context {
         return self.context.clone();
     }
     fn get_position(&self) -> (usize, usize) {
-        return (self.range.min.column, self.range.min.line);
+        let (line, column, _, _) = locate(&self.range);
+        return (column, line);
     }
     fn get_text(&self) -> String {
-        return String::from(self.range.min.script.split("\n").collect::<Vec<&str>>()[self.range.min.line]);
+        return locate(&self.range).2;
     }
-    fn get_range(&self) -> data::Range {
-        return data::Range {
-            min : data::Position::new(self.range.min.index, 0, self.range.min.column, self.range.min.filename.clone(), String::from("<Void>")),
-            max : data::Position::new(self.range.max.index, 0, self.range.max.column, self.range.min.filename.clone(), String::from("<Void>"))
-        };
+    fn get_width(&self) -> usize {
+        return locate(&self.range).3;
     }
     fn get_title(&self) -> String {
         return self.exception_type.get_name();
@@ -398,14 +479,24 @@ impl Exception for ValidatorException {
 
 pub enum ValidatorExceptionType {
 
-    Name
+    Name,
+    Mutability,
+    Type,
+    ControlFlow,
+    Pattern,
+    Deprecated
 
 }
 impl ExceptionType for ValidatorExceptionType {
     fn get_name(&self) -> String {
         return String::from(match (self) {
 
-            ValidatorExceptionType::Name  => "Name"
+            ValidatorExceptionType::Name        => "Name",
+            ValidatorExceptionType::Mutability  => "Mutability",
+            ValidatorExceptionType::Type        => "Type",
+            ValidatorExceptionType::ControlFlow => "ControlFlow",
+            ValidatorExceptionType::Pattern     => "Pattern",
+            ValidatorExceptionType::Deprecated  => "Deprecated"
 
         });
     }
@@ -413,7 +504,90 @@ impl ExceptionType for ValidatorExceptionType {
 
 
 
-pub fn get_leading_erase_count(text : String) -> usize {
+pub struct RuntimeException {
+    exception_type : RuntimeExceptionType,
+    message        : String,
+    range          : data::Range
+}
+impl RuntimeException {
+    pub fn new(exception_type : RuntimeExceptionType, message : String, range : data::Range) -> RuntimeException {
+        return RuntimeException {
+            exception_type,
+            message,
+            range
+        };
+    }
+}
+impl Exception for RuntimeException {
+    fn get_prefix(&self) -> String {
+        return String::from("Runtime");
+    }
+    // An uncaught `Crash`.
+    fn get_exit_code(&self) -> i32 {
+        return EXIT_CRASH;
+    }
+    fn get_filename(&self) -> String {
+        return self.range.min.filename.clone();
+    }
+    fn get_context(&self) -> data::Context {
+        return data::Context::new(String::from("<Runtime>"), None);
+    }
+    fn get_position(&self) -> (usize, usize) {
+        let (line, column, _, _) = locate(&self.range);
+        return (column, line);
+    }
+    fn get_text(&self) -> String {
+        return locate(&self.range).2;
+    }
+    fn get_width(&self) -> usize {
+        return locate(&self.range).3;
+    }
+    fn get_title(&self) -> String {
+        return self.exception_type.get_name();
+    }
+    fn get_message(&self) -> String {
+        return self.message.clone();
+    }
+}
+
+pub enum RuntimeExceptionType {
+
+    Name,
+    Type,
+    Arithmetic,
+    Index,
+    Bounds
+
+}
+impl ExceptionType for RuntimeExceptionType {
+    fn get_name(&self) -> String {
+        return String::from(match (self) {
+
+            RuntimeExceptionType::Name       => "Name",
+            RuntimeExceptionType::Type       => "Type",
+            RuntimeExceptionType::Arithmetic => "Arithmetic",
+            RuntimeExceptionType::Index      => "Index",
+            RuntimeExceptionType::Bounds     => "Bounds"
+
+        });
+    }
+}
+
+
+
+// The line and column of the start of a range, from 1, the line that it is
+// on and how many characters of that line it covers.
+fn locate(range : &data::Range) -> (usize, usize, String, usize) {
+    let chars = range.min.script.chars().collect::<Vec<char>>();
+    let min   = range.min.index.min(chars.len());
+    let start = chars[..min].iter().rposition(|ch| *ch == '\n').map(|index| index + 1).unwrap_or(0);
+    let end   = chars[min..].iter().position(|ch| *ch == '\n').map(|index| min + index).unwrap_or(chars.len());
+    let line  = chars[..start].iter().filter(|ch| **ch == '\n').count() + 1;
+    let width = if (range.max.index < end) {range.max.index.saturating_sub(min) + 1} else {end - min};
+    return (line, min - start + 1, chars[start..end].iter().collect::<String>().trim_end_matches('\r').to_string(), width);
+}
+
+fn get_leading_erase_count(text : String) -> usize {
     let     chars = text.chars().collect::<Vec<char>>();
     let mut count = 0;
     while ((count < chars.len()) && ([' ','\t'].contains(&chars[count]))) {
@@ -0,0 +1,172 @@
+use std::rc::Rc;
+
+use crate::data;
+use crate::lexer;
+use crate::parser;
+
+
+
+// The most blank lines kept between two lines, like between groups of functions.
+const BLANK_LINES : usize = 3;
+
+// The syntax that can continue on the lines after its first one.
+const STATEMENTS : [data::SyntaxType; 8] = [
+    data::SyntaxType::ExternalImport,
+    data::SyntaxType::LocalImport,
+    data::SyntaxType::ClassField,
+    data::SyntaxType::ExpressionStatement,
+    data::SyntaxType::InitializeVariable,
+    data::SyntaxType::Jump,
+    data::SyntaxType::Return,
+    data::SyntaxType::MatchArm
+];
+
+
+
+// Rewrites the layout of a script from its lossless syntax tree. Lines are
+// indented by 4 spaces for each open bracket, and once more where they
+// continue a statement. Line breaks are `\n`, spaces at the end of lines are
+// removed and the script ends with one line break. Everything else, comments
+// and the spaces inside of a line included, is kept as it is.
+pub struct Formatter {
+    output     : String,
+    depth      : usize, // brackets that are open
+    breaks     : usize, // line breaks that are not written yet
+    space      : String, // spaces that are not written yet, dropped at the end of a line
+    statements : Vec<(usize, bool)> // the statements being written : brackets open at their start, whether any of them is written
+}
+impl Formatter {
+
+    pub fn calculate(filename : String, script : String) -> String {
+        let tokens    = lexer::Lexer::calculate_lossless(filename, script);
+        let (_, tree) = parser::Parser::calculate_lossless(tokens);
+        let mut formatter = Formatter {
+            output     : String::new(),
+            depth      : 0,
+            breaks     : 0,
+            space      : String::new(),
+            statements : Vec::new()
+        };
+        formatter.start_node(&tree);
+        if (formatter.output.len() >= 1) {
+            formatter.output.push('\n');
+        }
+        return formatter.output;
+    }
+
+    fn start_node(&mut self, node : &data::SyntaxNode) -> () {
+        let statement = STATEMENTS.contains(&node.syntax);
+        if (statement) {
+            self.statements.push((self.depth, false));
+        }
+        for child in &node.children {
+            match (child) {
+                data::SyntaxElement::Node(node)   => self.start_node(node),
+                data::SyntaxElement::Token(token) => self.start_token(token)
+            }
+        }
+        if (statement) {
+            self.statements.pop();
+        }
+    }
+
+    fn start_token(&mut self, token : &Rc<data::Token>) -> () {
+        for trivia in &token.leading {
+            self.start_trivia(trivia);
+        }
+        match (token.token) {
+            data::TokenType::RParenthesis | data::TokenType::RBracket | data::TokenType::RBrace => {
+                self.depth = self.depth.saturating_sub(1);
+                self.write(&token.text, true);
+            },
+            data::TokenType::LParenthesis | data::TokenType::LBracket | data::TokenType::LBrace => {
+                self.write(&token.text, false);
+                self.depth += 1;
+            },
+            _ => self.write(&token.text, false)
+        }
+        if (let Some((_, written)) = self.statements.last_mut()) {
+            *written = true;
+        }
+        for trivia in &token.trailing {
+            self.start_trivia(trivia);
+        }
+    }
+
+    fn start_trivia(&mut self, trivia : &data::Trivia) -> () {
+        match (trivia.trivia) {
+            data::TriviaType::Newline    => self.breaks += trivia.text.matches('\n').count(),
+            // The indentation of a line is worked out again.
+            data::TriviaType::Whitespace => if (self.breaks <= 0 && self.output.len() >= 1) {
+                self.space += trivia.text.as_str();
+            },
+            data::TriviaType::Comment    => self.write(trivia.text.trim_end(), false)
+        }
+    }
+
+    // A line that continues a statement is indented once more, unless it is
+    // inside of brackets that the statement opened or closes one of them.
+    fn write(&mut self, text : &str, closing : bool) -> () {
+        if (text.len() <= 0) {
+            return;
+        }
+        if (self.breaks >= 1 && self.output.len() >= 1) {
+            let continued = matches!(self.statements.last(), Some((depth, true)) if *depth == self.depth && ! closing);
+            self.output += "\n".repeat(self.breaks.min(BLANK_LINES + 1)).as_str();
+            self.output += "    ".repeat(self.depth + if (continued) {1} else {0}).as_str();
+        } else {
+            self.output += self.space.replace('\r', "").as_str();
+        }
+        self.output += text;
+        self.breaks  = 0;
+        self.space   = String::new();
+    }
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(script : &str) -> String {
+        let tokens    = lexer::Lexer::calculate_lossless(String::from("<test>"), script.to_string());
+        let (_, tree) = parser::Parser::calculate_lossless(tokens);
+        return tree.to_string();
+    }
+
+    #[test]
+    fn lossless_tree_reproduces_the_examples() {
+        for example in ["hello_world", "input", "returns"] {
+            let path   = format!("{}/examples/{}.vsv", env!("CARGO_MANIFEST_DIR"), example);
+            let script = std::fs::read_to_string(&path).unwrap();
+            assert!(round_trip(&script) == script, "`{}` was not reproduced", path);
+        }
+    }
+
+    #[test]
+    fn lossless_tree_reproduces_the_exact_input() {
+        let script = "// header\r\n\r\nextern std;  \n\nfunc main() : std::types::Void {\n\tlet x = [1,   2]; // two\n    \"a  \n b\";\n}";
+        assert!(round_trip(script) == script);
+    }
+
+    #[test]
+    fn formatter_rewrites_only_the_layout() {
+        let script   = "\n\n// header  \r\nextern std;\n\n\n\n\n\nfunc main() : std::types::Void {\n\tlet x  = [\n1,\n        2\n  ]; // two \n    \"a  \n b\";\n   }";
+        let expected = "// header\nextern std;\n\n\n\nfunc main() : std::types::Void {\n    let x  = [\n        1,\n        2\n    ]; // two\n    \"a  \n b\";\n}\n";
+        let output   = Formatter::calculate(String::from("<test>"), script.to_string());
+        assert!(output == expected, "{:?}", output);
+        assert!(Formatter::calculate(String::from("<test>"), output.clone()) == output);
+    }
+
+    #[test]
+    fn formatter_indents_lines_that_continue_a_statement() {
+        let script   = "func main() : Void {\nlet x = 1\n+ 2;\nlet y = f(\nx\n)\n.g();\nif (x) {\n1\n}\n}";
+        let expected = "func main() : Void {\n    let x = 1\n        + 2;\n    let y = f(\n        x\n    )\n        .g();\n    if (x) {\n        1\n    }\n}\n";
+        let output   = Formatter::calculate(String::from("<test>"), script.to_string());
+        assert!(output == expected, "{:?}", output);
+        assert!(Formatter::calculate(String::from("<test>"), output.clone()) == output);
+    }
+
+}
@@ -0,0 +1,1328 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use colored::Colorize;
+
+use crate::data;
+use crate::library;
+use crate::exception;
+use crate::exception::Exception;
+
+
+
+// How a statement was left.
+pub enum Flow {
+    Normal(data::Value), // result
+    Break(Option<String>), // label
+    Continue(Option<String>), // label
+    Return(data::Value) // value
+}
+
+// Where an assignment writes to, with its parts already evaluated.
+enum Place {
+    Name(String), // name
+    Field(data::Value, String), // parent, member
+    Slice(Box<Place>, data::Value), // parent, index
+    Checked(Box<Place>, data::ValueType) // place, bounds
+}
+
+// The value of an expression, or the flow that leaves it early, like a
+// `scope::return(value)` inside of it.
+pub type Evaluation = Result<data::Value, Flow>;
+
+
+
+pub struct Interpreter {
+    modules     : HashMap<String, data::Scope>, // key, globals
+    pending     : HashMap<(String, String), data::Node>, // globals(module, name) that are not evaluated yet
+    loading     : Vec<(String, String)>, // module, name
+    session     : Option<data::Scope>, // for the REPL, the names declared by its statements
+    environment : library::Environment
+}
+impl Interpreter {
+
+    // The modules come each after the modules that it uses, the script that
+    // was run is the last one. Returns the exit code of the entry function.
+    pub fn calculate(modules : Vec<data::Module>, environment : library::Environment) -> i32 {
+        let mut interpreter = Interpreter::new(environment);
+        let mut entry       = None;
+        for module in modules {
+            let globals = data::Scope::new(None);
+            interpreter.modules.insert(module.key.clone(), globals.clone());
+            if (let Some(function) = interpreter.start_module(globals, &module)) {
+                entry = Some(function);
+            }
+        }
+        let (name, range) = if (let Some(entry) = entry) {
+            entry
+        } else {
+            exception::InternalException::new(
+                String::from("Script has no entry function.")
+            ).dump_critical();
+        };
+        let function = interpreter.get_name(interpreter.modules[""].clone(), &name, range.clone());
+        let args     = match (&function) {
+            data::Value::Function(function) if function.args.len() == 1 => vec![data::Value::List(interpreter.environment.arguments.iter().map(|argument| data::Value::String(argument.clone())).collect())],
+            _                                                            => Vec::new()
+        };
+        return match (interpreter.call(function, args, range.clone())) {
+            Ok(data::Value::Int(code)) if (0..=255).contains(&code) => code as i32,
+            Ok(data::Value::Int(code))                              => {
+                exception::RuntimeException::new(
+                    exception::RuntimeExceptionType::Bounds,
+                    format!("Exit code `{}` is not between 0 and 255.", code),
+                    range
+                ).dump_error();
+            },
+            _                                                       => 0
+        };
+    }
+
+    // For `vesuviuspl test`. Calls each `#[test]` function of the script
+    // whose name contains `filter`. A test fails if it crashes. Returns the
+    // exit code, which is that of a crash if any test failed.
+    pub fn calculate_tests(modules : Vec<data::Module>, environment : library::Environment, filter : String) -> i32 {
+        let mut interpreter = Interpreter::new(environment);
+        let mut tests       = Vec::new();
+        for module in modules {
+            let globals = data::Scope::new(None);
+            interpreter.modules.insert(module.key.clone(), globals.clone());
+            interpreter.start_module(globals, &module);
+            if (module.key.len() <= 0) {
+                tests = module.nodes.iter().filter_map(|node| match (&node.node) {
+                    data::NodeType::DefineFunction(name, ..) if (node.headers.has("test") && name.contains(&filter)) => Some((name.clone(), node.range.clone())),
+                    _                                                                                             => None
+                }).collect::<Vec<(String, data::Range)>>();
+            }
+        }
+        exception::set_recover(true);
+        let mut failed = 0;
+        for (name, range) in &tests {
+            let passed = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                let function = interpreter.get_name(interpreter.modules[""].clone(), name, range.clone());
+                interpreter.call(function, Vec::new(), range.clone())
+            })).is_ok();
+            if (passed) {
+                println!("test {} ... {}", name, "ok".green());
+            } else {
+                println!("test {} ... {}", name, "FAILED".red());
+                failed += 1;
+            }
+        }
+        exception::set_recover(false);
+        println!("\n{} passed, {} failed.", tests.len() - failed, failed);
+        return if (failed >= 1) {exception::EXIT_CRASH} else {0};
+    }
+
+    pub fn new(environment : library::Environment) -> Interpreter {
+        return Interpreter {
+            modules     : HashMap::new(),
+            pending     : HashMap::new(),
+            loading     : Vec::new(),
+            session     : None,
+            environment
+        };
+    }
+
+    // For the REPL, which gives the whole script each time. Starts the
+    // modules that are not started yet, and the script again without its
+    // globals that already have a value. The new globals are evaluated now.
+    pub fn start_session(&mut self, modules : Vec<data::Module>) -> () {
+        self.loading.clear();
+        for mut module in modules {
+            if (module.key.len() >= 1 && self.modules.contains_key(&module.key)) {
+                continue;
+            }
+            let globals = self.modules.entry(module.key.clone()).or_insert(data::Scope::new(None)).clone();
+            module.nodes.retain(|node| ! matches!(&node.node, data::NodeType::InitializeVariable(_, name, _, _) if globals.get_name(name).is_some()));
+            self.start_module(globals.clone(), &module);
+            for node in &module.nodes {
+                if (let data::NodeType::InitializeVariable(_, name, _, _) = &node.node) {
+                    self.get_name(globals.clone(), name, node.range.clone());
+                }
+            }
+        }
+    }
+
+    // For the REPL. Runs statements inside of the globals of the script. The
+    // names they declare stay for the rest of the session.
+    pub fn evaluate(&mut self, statements : Vec<data::Node>) -> data::Value {
+        self.loading.clear();
+        let globals = self.modules.entry(String::new()).or_insert(data::Scope::new(None)).clone();
+        let session = self.session.get_or_insert_with(|| data::Scope::new(Some(globals))).clone();
+        return match (self.start_body(session, statements)) {
+            Flow::Normal(value) | Flow::Return(value) => value,
+            _                                         => data::Value::Void
+        };
+    }
+
+    // Declares the globals of a module. Returns the entry function of the
+    // script that was run.
+    fn start_module(&mut self, globals : data::Scope, module : &data::Module) -> Option<(String, data::Range)> {
+        let mut entry   = None;
+        let mut classes = Vec::new();
+        let mut impls   = Vec::new();
+        for node in module.nodes.clone() {
+            match (node.node.clone()) {
+                data::NodeType::ExternalImport(name)                   => globals.declare_name(name.clone(), library::module_value(&name).unwrap_or(data::Value::Module(Rc::new(HashMap::new())))),
+                data::NodeType::LocalImport(name)                      => globals.declare_name(name.clone(), data::Value::LocalModule(module.imports[&name].clone())),
+                data::NodeType::DefineFunction(name, _, args, _, body) => {
+                    if (node.headers.has("entry") && module.key.len() <= 0) {
+                        entry = Some((name.clone(), node.range.clone()));
+                    }
+                    globals.declare_name(name.clone(), data::Value::Function(Rc::new(data::Function {
+                        name,
+                        args  : args.iter().map(|(arg, _)| arg.clone()).collect(),
+                        body  : *body,
+                        scope : globals.clone()
+                    })));
+                },
+                data::NodeType::DefineClass(..)                        => classes.push(node),
+                data::NodeType::DefineTrait(..)                        => {},
+                data::NodeType::ImplementTrait(_, class, methods)      => impls.push((class, *methods)),
+                data::NodeType::DefineEnum(name, variants)             => globals.declare_name(name.clone(), data::Value::Enum(Rc::new(data::Enum {
+                    name,
+                    variants : variants.iter().map(|(variant, payload)| (variant.clone(), payload.iter().map(|(field, _)| field.clone()).collect())).collect()
+                }))),
+                // Global variables are evaluated the first time they are used.
+                data::NodeType::InitializeVariable(_, name, _, value)  => {
+                    if (let Some(value) = *value) {
+                        self.pending.insert((module.key.clone(), name), value);
+                    } else {
+                        globals.declare_name(name, data::Value::Void);
+                    }
+                },
+                _                                                      => {
+                    exception::InternalException::new(
+                        String::from("Invalid global node.")
+                    ).dump_critical();
+                }
+            }
+        }
+        // The methods of a trait implementation belong to its class.
+        let classes = classes.into_iter().map(|mut node| {
+            if (let data::NodeType::DefineClass(name, _, _, _, methods) = &mut node.node) {
+                for (class, impl_methods) in &impls {
+                    if (class == name) {
+                        methods.extend(impl_methods.clone());
+                    }
+                }
+            }
+            node
+        }).collect();
+        self.start_classes(globals, classes);
+        return entry;
+    }
+
+    fn fail(&self, exception_type : exception::RuntimeExceptionType, message : String, range : data::Range) -> ! {
+        exception::RuntimeException::new(
+            exception_type,
+            message,
+            range
+        ).dump_error();
+    }
+
+
+
+    // Builds every class, with its fields and methods laid out by the
+    // method resolution order. In its methods, `Self` is the class that
+    // defines them.
+    fn start_classes(&mut self, globals : data::Scope, nodes : Vec<data::Node>) -> () {
+        let mut bases       = HashMap::new();
+        let mut members     = HashMap::new();
+        let mut self_scopes = HashMap::new();
+        for node in nodes.clone() {
+            if (let data::NodeType::DefineClass(name, _, class_bases, fields, methods) = node.node) {
+                bases.insert(name.clone(), class_bases);
+                members.insert(name, (*fields, *methods));
+            }
+        }
+        for node in nodes {
+            if (let data::NodeType::DefineClass(name, _, _, _, _) = node.node) {
+                let order = match (data::method_resolution_order(&name, &bases)) {
+                    Ok(order)    => order,
+                    Err(message) => {
+                        exception::InternalException::new(message).dump_critical();
+                    }
+                };
+                let mut fields  : Vec<(String, Option<data::Node>)> = Vec::new();
+                let mut methods = HashMap::new();
+                for class in order.iter().rev() {
+                    for (field, _, default) in members[class].0.clone() {
+                        if (! fields.iter().any(|(other, _)| *other == field)) {
+                            fields.push((field, default));
+                        }
+                    }
+                }
+                for class in order.iter() {
+                    for method in members[class].1.clone() {
+                        if (let data::NodeType::DefineFunction(method_name, _, args, _, body) = method.node
+                            && ! methods.contains_key(&method_name)
+                        ) {
+                            let scope = self_scopes.entry(class.clone()).or_insert_with(|| data::Scope::new(Some(globals.clone()))).clone();
+                            methods.insert(method_name.clone(), (method.headers.has("static"), Rc::new(data::Function {
+                                name  : format!("{}.{}", class, method_name),
+                                args  : args.iter().map(|(arg, _)| arg.clone()).collect(),
+                                body  : *body,
+                                scope
+                            })));
+                        }
+                    }
+                }
+                globals.declare_name(name.clone(), data::Value::Class(Rc::new(data::Class {
+                    name,
+                    fields,
+                    methods,
+                    scope   : globals.clone()
+                })));
+            }
+        }
+        for (class, scope) in self_scopes {
+            if (let Some(value) = globals.get_name(&class)) {
+                scope.declare_name(String::from("Self"), value);
+            }
+        }
+    }
+
+
+
+    pub fn get_name(&mut self, scope : data::Scope, name : &String, range : data::Range) -> data::Value {
+        if (let Some(value) = scope.get_name(name)) {
+            return value;
+        }
+        let module = self.module_of(&scope);
+        let global = (module.clone(), name.clone());
+        if (let Some(node) = self.pending.remove(&global)) {
+            let globals = self.modules[&module].clone();
+            self.loading.push(global);
+            let value = match (self.start_expression(globals.clone(), node)) {
+                Ok(value) => value,
+                Err(_)    => {
+                    exception::InternalException::new(
+                        String::from("Jump out of a global.")
+                    ).dump_critical();
+                }
+            };
+            self.loading.pop();
+            globals.declare_name(name.clone(), value.clone());
+            return value;
+        }
+        if (self.loading.contains(&global)) {
+            self.fail(
+                exception::RuntimeExceptionType::Name,
+                format!("Global `{}` is used in its own definition.", name),
+                range
+            );
+        }
+        self.fail(
+            exception::RuntimeExceptionType::Name,
+            format!("Name `{}` is not defined.", name),
+            range
+        );
+    }
+
+
+    // The key of the module whose globals the scope is inside of.
+    fn module_of(&self, scope : &data::Scope) -> String {
+        let mut root = scope;
+        while (let Some(parent) = &*root.parent) {
+            root = parent;
+        }
+        return self.modules.iter()
+            .find(|(_, globals)| Rc::ptr_eq(&globals.symbols, &root.symbols))
+            .map(|(key, _)| key.clone())
+            .unwrap_or_default();
+    }
+
+
+
+    // `scope::return(value)` can be inside of any expression, so it leaves
+    // with the flow that returns from the function it is in.
+    pub fn call(&mut self, callee : data::Value, args : Vec<data::Value>, range : data::Range) -> Evaluation {
+        return match (callee) {
+            data::Value::Function(function)         => Ok(self.call_function(function, None, args)),
+            data::Value::Method(receiver, function) => Ok(self.call_function(function, Some(*receiver), args)),
+            data::Value::Class(class)               => self.construct(class, args, range),
+            data::Value::VariantConstructor(enumeration, variant) => Ok(data::Value::Variant(enumeration, variant, args)),
+            data::Value::Native(name, _) if (name == "std::scope::return") => Err(Flow::Return(args.into_iter().next().unwrap_or(data::Value::Void))),
+            data::Value::Native(name, bound)        => Ok(library::call(&name, [bound, args].concat(), &self.environment, range)),
+            _                                       => {
+                self.fail(
+                    exception::RuntimeExceptionType::Type,
+                    format!("Can not call `{}`.", callee.type_name()),
+                    range
+                );
+            }
+        };
+    }
+
+
+    pub fn call_function(&mut self, function : Rc<data::Function>, receiver : Option<data::Value>, args : Vec<data::Value>) -> data::Value {
+        let scope = data::Scope::new(Some(function.scope.clone()));
+        if (let Some(receiver) = receiver) {
+            scope.declare_name(String::from("self"), receiver);
+        }
+        for (name, value) in function.args.iter().zip(args) {
+            scope.declare_name(name.clone(), value);
+        }
+        return match (self.start_body(scope, function.body.clone())) {
+            Flow::Normal(value) | Flow::Return(value) => value,
+            _                                         => {
+                exception::InternalException::new(
+                    String::from("Jump out of a function.")
+                ).dump_critical();
+            }
+        };
+    }
+
+
+    // Fields with a default are set first. Then `init` gets the arguments if
+    // the class has one, otherwise they go to the fields without a default.
+    pub fn construct(&mut self, class : Rc<data::Class>, args : Vec<data::Value>, _range : data::Range) -> Evaluation {
+        let instance = Rc::new(data::Instance {
+            class  : class.clone(),
+            fields : RefCell::new(HashMap::new())
+        });
+        for (field, default) in class.fields.clone() {
+            if (let Some(default) = default) {
+                let value = self.start_expression(class.scope.clone(), default)?;
+                instance.fields.borrow_mut().insert(field, value);
+            }
+        }
+        if (let Some((false, init)) = class.methods.get("init").cloned()) {
+            self.call_function(init, Some(data::Value::Instance(instance.clone())), args);
+        } else {
+            let fields = class.fields.iter().filter(|(_, default)| default.is_none()).map(|(field, _)| field.clone());
+            for (field, value) in fields.zip(args) {
+                instance.fields.borrow_mut().insert(field, value);
+            }
+        }
+        return Ok(data::Value::Instance(instance));
+    }
+
+
+
+    pub fn start_body(&mut self, scope : data::Scope, body : Vec<data::Node>) -> Flow {
+        let mut result = data::Value::Void;
+        for node in body {
+            match (self.start_statement(scope.clone(), node)) {
+                Flow::Normal(value) => result = value,
+                flow                => return flow
+            }
+        }
+        return Flow::Normal(result);
+    }
+
+
+    pub fn start_block(&mut self, scope : data::Scope, body : Vec<data::Node>) -> Flow {
+        return self.start_body(data::Scope::new(Some(scope)), body);
+    }
+
+
+
+    pub fn start_statement(&mut self, scope : data::Scope, node : data::Node) -> Flow {
+        return match (self.try_statement(scope, node)) {
+            Ok(flow) | Err(flow) => flow
+        };
+    }
+
+
+    // The flow that leaves an expression of the statement is the error.
+    fn try_statement(&mut self, scope : data::Scope, node : data::Node) -> Result<Flow, Flow> {
+        return Ok(match (node.node) {
+
+            data::NodeType::InitializeVariable(_, name, _, value) => {
+                let value = if (let Some(value) = *value) {self.start_expression(scope.clone(), value)?} else {data::Value::Void};
+                scope.declare_name(name, value);
+                Flow::Normal(data::Value::Void)
+            },
+
+            data::NodeType::If(branches, fallback) => {
+                for (condition, body) in *branches {
+                    if (self.start_condition(scope.clone(), condition)?) {
+                        return Ok(self.start_block(scope, body));
+                    }
+                }
+                if (let Some(body) = *fallback) {
+                    self.start_block(scope, body)
+                } else {
+                    Flow::Normal(data::Value::Void)
+                }
+            },
+
+            data::NodeType::While(label, condition, body) => {
+                while (self.start_condition(scope.clone(), (*condition).clone())?) {
+                    if (let Some(flow) = self.start_loop_body(scope.clone(), &label, (*body).clone())) {
+                        return Ok(flow);
+                    }
+                }
+                Flow::Normal(data::Value::Void)
+            },
+
+            data::NodeType::Loop(label, body) => {
+                loop {
+                    if (let Some(flow) = self.start_loop_body(scope.clone(), &label, (*body).clone())) {
+                        return Ok(flow);
+                    }
+                }
+            },
+
+            data::NodeType::For(label, name, iterable, body) => {
+                let iterable_range = iterable.range.clone();
+                let iterable       = self.start_expression(scope.clone(), *iterable)?;
+                for item in self.iterate(iterable, iterable_range) {
+                    let loop_scope = data::Scope::new(Some(scope.clone()));
+                    loop_scope.declare_name(name.clone(), item);
+                    if (let Some(flow) = self.start_loop_body(loop_scope, &label, (*body).clone())) {
+                        return Ok(flow);
+                    }
+                }
+                Flow::Normal(data::Value::Void)
+            },
+
+            data::NodeType::Break(label)    => Flow::Break(label),
+            data::NodeType::Continue(label) => Flow::Continue(label),
+
+            data::NodeType::Return(value) => Flow::Return(
+                if (let Some(value) = *value) {self.start_expression(scope, value)?} else {data::Value::Void}
+            ),
+
+            data::NodeType::BlockResult(value) => Flow::Normal(self.start_expression(scope, *value)?),
+
+            data::NodeType::Match(value, arms) => {
+                let (arm_scope, body) = self.start_match(scope, *value, *arms, node.range)?;
+                self.start_block(arm_scope, body)
+            },
+
+            _ => {
+                self.start_expression(scope, node)?;
+                Flow::Normal(data::Value::Void)
+            }
+
+        });
+    }
+
+
+    // Runs one iteration. Returns the flow to pass on if the loop has to stop.
+    fn start_loop_body(&mut self, scope : data::Scope, label : &Option<String>, body : Vec<data::Node>) -> Option<Flow> {
+        return match (self.start_block(scope, body)) {
+            Flow::Normal(_)                                               => None,
+            Flow::Break(target) if (target.is_none() || target == *label) => Some(Flow::Normal(data::Value::Void)),
+            Flow::Continue(target) if (target.is_none() || target == *label) => None,
+            flow                                                          => Some(flow)
+        };
+    }
+
+
+    // Picks the first arm whose pattern matches, with the names it binds.
+    fn start_match(&mut self, scope : data::Scope, value : data::Node, arms : Vec<(data::Node, Vec<data::Node>)>, range : data::Range) -> Result<(data::Scope, Vec<data::Node>), Flow> {
+        let value = self.start_expression(scope.clone(), value)?;
+        for (pattern, body) in arms {
+            let mut bindings = Vec::new();
+            if (let data::NodeType::Pattern(pattern) = pattern.node
+                && matches_pattern(&pattern, &value, &mut bindings)
+            ) {
+                let arm_scope = data::Scope::new(Some(scope));
+                for (name, value) in bindings {
+                    arm_scope.declare_name(name, value);
+                }
+                return Ok((arm_scope, body));
+            }
+        }
+        self.fail(
+            exception::RuntimeExceptionType::Type,
+            format!("No arm of the match matches `{}`.", value),
+            range
+        );
+    }
+
+
+    fn start_condition(&mut self, scope : data::Scope, condition : data::Node) -> Result<bool, Flow> {
+        let range = condition.range.clone();
+        return match (self.start_expression(scope, condition)?) {
+            data::Value::Bool(value) => Ok(value),
+            value                    => {
+                self.fail(
+                    exception::RuntimeExceptionType::Type,
+                    format!("Expected `Bool`, found `{}`.", value.type_name()),
+                    range
+                );
+            }
+        };
+    }
+
+
+    fn iterate(&self, iterable : data::Value, range : data::Range) -> Box<dyn Iterator<Item = data::Value>> {
+        return match (iterable) {
+            data::Value::IntRange(min, max, step) => {
+                if (step == 0) {
+                    self.fail(exception::RuntimeExceptionType::Arithmetic, String::from("Range step can not be 0."), range);
+                }
+                Box::new(std::iter::successors(Some(min), move |value| value.checked_add(step))
+                    .take_while(move |value| if (step > 0) {*value < max} else {*value > max})
+                    .map(data::Value::Int))
+            },
+            data::Value::FloatRange(min, max, step) => {
+                if (step == 0.0) {
+                    self.fail(exception::RuntimeExceptionType::Arithmetic, String::from("Range step can not be 0."), range);
+                }
+                Box::new(std::iter::successors(Some(min), move |value| Some(value + step))
+                    .take_while(move |value| if (step > 0.0) {*value < max} else {*value > max})
+                    .map(data::Value::Float))
+            },
+            data::Value::List(values) | data::Value::Array(values) => Box::new(values.into_iter()),
+            data::Value::Dict(entries)                            => Box::new(entries.into_iter().map(|(key, _)| key)),
+            value                                                 => {
+                self.fail(
+                    exception::RuntimeExceptionType::Type,
+                    format!("Can not iterate over `{}`.", value.type_name()),
+                    range
+                );
+            }
+        };
+    }
+
+
+
+    pub fn start_expression(&mut self, scope : data::Scope, node : data::Node) -> Evaluation {
+        if (let Some((operator, left, right)) = node.node.binary_operation()) {
+            return self.start_binary_operation(scope, node.range, operator, left, right);
+        }
+        return Ok(match (node.node) {
+
+            data::NodeType::AssignVariable(target, operator, value) => {
+                let range     = target.range.clone();
+                let place     = self.start_place(scope.clone(), *target)?;
+                let mut value = self.start_expression(scope.clone(), *value)?;
+                if (let Some(operator) = operator) {
+                    let current = self.read_place(scope.clone(), &place, range.clone());
+                    value = self.operate(range.clone(), operator, current, value);
+                }
+                self.write_place(scope, place, value, range);
+                data::Value::Void
+            },
+
+            data::NodeType::OppositeOperation(value) => match (self.start_expression(scope, *value)?) {
+                data::Value::Int(value)   => data::Value::Int(self.checked(value.checked_neg(), &node.range)),
+                data::Value::Float(value) => data::Value::Float(- value),
+                value                     => {
+                    self.fail(
+                        exception::RuntimeExceptionType::Type,
+                        format!("Operator `-` can not be applied to `{}`.", value.type_name()),
+                        node.range
+                    );
+                }
+            },
+            data::NodeType::InvertOperation(value) => match (self.start_expression(scope, *value)?) {
+                data::Value::Bool(value) => data::Value::Bool(! value),
+                value                    => {
+                    self.fail(
+                        exception::RuntimeExceptionType::Type,
+                        format!("Operator `!` can not be applied to `{}`.", value.type_name()),
+                        node.range
+                    );
+                }
+            },
+
+            data::NodeType::ModuleMember(parent, member) => {
+                let parent = self.start_expression(scope, *parent)?;
+                self.get_module_member(parent, &member, node.range)
+            },
+
+            data::NodeType::ClassMember(parent, member) => {
+                let parent = self.start_expression(scope, *parent)?;
+                self.get_class_member(parent, &member, node.range)
+            },
+
+            data::NodeType::Slice(parent, index) => {
+                let parent = self.start_expression(scope.clone(), *parent)?;
+                let index  = self.start_expression(scope, *index)?;
+                self.get_slice(parent, index, node.range)
+            },
+
+            data::NodeType::Call(parent, args) => {
+                let callee     = self.start_expression(scope.clone(), *parent)?;
+                let mut values = Vec::new();
+                for arg in *args {
+                    values.push(self.start_expression(scope.clone(), arg)?);
+                }
+                self.call(callee, values, node.range)?
+            },
+
+            // The captured names are copied, the rest are looked up in the
+            // scope the function was created in.
+            data::NodeType::Function(args, _, body, captures) => {
+                let captured = data::Scope::new(Some(scope.clone()));
+                for name in captures {
+                    if (let Some(value) = scope.get_name(&name)) {
+                        captured.declare_name(name, value);
+                    }
+                }
+                data::Value::Function(Rc::new(data::Function {
+                    name  : String::from("(anonymous)"),
+                    args  : args.iter().map(|(arg, _)| arg.clone()).collect(),
+                    body  : *body,
+                    scope : captured
+                }))
+            },
+
+            data::NodeType::BoundsCheck(value, bounds) => {
+                let value = self.start_expression(scope, *value)?;
+                self.check_bounds(&value, &bounds, node.range);
+                value
+            },
+
+            data::NodeType::Match(value, arms) => {
+                let (arm_scope, body) = self.start_match(scope, *value, *arms, node.range)?;
+                match (self.start_block(arm_scope, body)) {
+                    Flow::Normal(value) => value,
+                    flow                => return Err(flow)
+                }
+            },
+
+            data::NodeType::Literal(literal) => match (literal) {
+                data::Literal::Name(name)      => self.get_name(scope, &name, node.range),
+                data::Literal::Bool(value)     => data::Value::Bool(value),
+                data::Literal::Character(ch)   => data::Value::Char(ch),
+                data::Literal::String(text)    => data::Value::String(text),
+                data::Literal::Integer(number) => data::Value::Int(number),
+                data::Literal::Float(number)   => data::Value::Float(number)
+            },
+
+            data::NodeType::Range(min, max, step) => {
+                let min  = self.start_expression(scope.clone(), *min)?;
+                let max  = self.start_expression(scope.clone(), *max)?;
+                let step = if (let Some(step) = *step) {Some(self.start_expression(scope, step)?)} else {None};
+                match (min, max, step) {
+                    (data::Value::Int(min), data::Value::Int(max), None)                         => data::Value::IntRange(min, max, 1),
+                    (data::Value::Int(min), data::Value::Int(max), Some(data::Value::Int(step)))  => data::Value::IntRange(min, max, step),
+                    (min, max, step)                                                              => data::Value::FloatRange(
+                        self.number(min, node.range.clone()),
+                        self.number(max, node.range.clone()),
+                        if (let Some(step) = step) {self.number(step, node.range)} else {1.0}
+                    )
+                }
+            },
+
+            data::NodeType::Tuple(values) => {
+                let mut items = Vec::new();
+                for value in *values {
+                    items.push(self.start_expression(scope.clone(), value)?);
+                }
+                data::Value::Tuple(items)
+            },
+
+            data::NodeType::Array(values) => {
+                let mut items = Vec::new();
+                for value in *values {
+                    items.push(self.start_expression(scope.clone(), value)?);
+                }
+                data::Value::Array(items)
+            },
+
+            data::NodeType::Dict(entries) => {
+                let mut items : Vec<(data::Value, data::Value)> = Vec::new();
+                for (key, value) in *entries {
+                    let key   = self.start_expression(scope.clone(), key)?;
+                    let value = self.start_expression(scope.clone(), value)?;
+                    items.retain(|(other, _)| ! other.equals(&key));
+                    items.push((key, value));
+                }
+                data::Value::Dict(items)
+            },
+
+            _ => {
+                exception::InternalException::new(
+                    String::from("Invalid expression node.")
+                ).dump_critical();
+            }
+
+        });
+    }
+
+
+    fn check_bounds(&self, value : &data::Value, bounds : &data::ValueType, range : data::Range) -> () {
+        let inside = match (value, bounds) {
+            (data::Value::Int(value), data::ValueType::RInt(min, max))     => min <= value && value <= max,
+            (data::Value::Float(value), data::ValueType::RFloat(min, max)) => *min <= *value && *value <= *max,
+            _                                                              => false
+        };
+        if (! inside) {
+            self.fail(
+                exception::RuntimeExceptionType::Bounds,
+                format!("Value `{}` is not inside of `{}`.", value, bounds),
+                range
+            );
+        }
+    }
+
+
+    fn checked(&self, value : Option<i64>, range : &data::Range) -> i64 {
+        return if (let Some(value) = value) {
+            value
+        } else {
+            self.fail(
+                exception::RuntimeExceptionType::Arithmetic,
+                String::from("Integer overflow."),
+                range.clone()
+            );
+        };
+    }
+
+
+    fn number(&self, value : data::Value, range : data::Range) -> f64 {
+        return match (value) {
+            data::Value::Int(value)   => value as f64,
+            data::Value::Float(value) => value,
+            value                     => {
+                self.fail(
+                    exception::RuntimeExceptionType::Type,
+                    format!("Expected `Int`, `Float`, found `{}`.", value.type_name()),
+                    range
+                );
+            }
+        };
+    }
+
+
+
+    pub fn start_binary_operation(&mut self, scope : data::Scope, range : data::Range, operator : &'static str, left : data::Node, right : data::Node) -> Evaluation {
+        let left = self.start_expression(scope.clone(), left)?;
+        // `&&` and `||` only look at the right side when they have to.
+        if (let ("&&", data::Value::Bool(false)) | ("||", data::Value::Bool(true)) = (operator, &left)) {
+            return Ok(left);
+        }
+        let right = self.start_expression(scope, right)?;
+        return Ok(self.operate(range, operator, left, right));
+    }
+
+
+    fn operate(&mut self, range : data::Range, operator : &str, left : data::Value, right : data::Value) -> data::Value {
+        // An instance overloads an operator with the method of its trait.
+        if (let data::Value::Instance(instance) = &left
+            && let Some((_, _, method)) = data::OPERATOR_TRAITS.iter().find(|(symbol, _, _)| *symbol == operator)
+            && let Some((false, function)) = instance.class.methods.get(*method)
+        ) {
+            let function = function.clone();
+            return self.call_function(function, Some(left.clone()), vec![right]);
+        }
+        let result = match (operator, &left, &right) {
+
+            ("==", _, _) => Some(data::Value::Bool(left.equals(&right))),
+            ("!=", _, _) => Some(data::Value::Bool(! left.equals(&right))),
+
+            ("/" | "%", data::Value::Int(_), data::Value::Int(0)) => {
+                self.fail(
+                    exception::RuntimeExceptionType::Arithmetic,
+                    String::from("Division by zero."),
+                    range
+                );
+            },
+            ("**", data::Value::Int(_), data::Value::Int(b)) if (*b < 0) => {
+                self.fail(
+                    exception::RuntimeExceptionType::Arithmetic,
+                    format!("Can not raise an `Int` to the negative power `{}`.", b),
+                    range
+                );
+            },
+            (_, data::Value::Int(a), data::Value::Int(b)) => match (operator) {
+                "+"  => Some(data::Value::Int(self.checked(a.checked_add(*b), &range))),
+                "-"  => Some(data::Value::Int(self.checked(a.checked_sub(*b), &range))),
+                "*"  => Some(data::Value::Int(self.checked(a.checked_mul(*b), &range))),
+                "/"  => Some(data::Value::Int(self.checked(a.checked_div(*b), &range))),
+                "%"  => Some(data::Value::Int(self.checked(a.checked_rem(*b), &range))),
+                "**" => Some(data::Value::Int(self.checked(u32::try_from(*b).ok().and_then(|b| a.checked_pow(b)), &range))),
+                "&"  => Some(data::Value::Int(a & b)),
+                "|"  => Some(data::Value::Int(a | b)),
+                "^"  => Some(data::Value::Int(a ^ b)),
+                "<<" | ">>" => {
+                    if (*b < 0 || *b >= 64) {
+                        self.fail(
+                            exception::RuntimeExceptionType::Arithmetic,
+                            format!("Can not shift by `{}`.", b),
+                            range
+                        );
+                    }
+                    Some(data::Value::Int(if (operator == "<<") {a << b} else {a >> b}))
+                },
+                _    => compare(operator, a.partial_cmp(b))
+            },
+
+            (_, data::Value::Float(a), data::Value::Float(b)) => match (operator) {
+                "+"  => Some(data::Value::Float(a + b)),
+                "-"  => Some(data::Value::Float(a - b)),
+                "*"  => Some(data::Value::Float(a * b)),
+                "/"  => Some(data::Value::Float(a / b)),
+                "%"  => Some(data::Value::Float(a % b)),
+                "**" => Some(data::Value::Float(a.powf(*b))),
+                _    => compare(operator, a.partial_cmp(b))
+            },
+
+            (_, data::Value::Bool(a), data::Value::Bool(b)) => match (operator) {
+                "&&" | "&" => Some(data::Value::Bool(*a && *b)),
+                "||" | "|" => Some(data::Value::Bool(*a || *b)),
+                "^"        => Some(data::Value::Bool(a != b)),
+                _          => None
+            },
+
+            ("+", data::Value::String(a), data::Value::String(b)) => Some(data::Value::String(format!("{}{}", a, b))),
+            (_, data::Value::String(a), data::Value::String(b))   => compare(operator, a.partial_cmp(b)),
+            (_, data::Value::Char(a), data::Value::Char(b))       => compare(operator, a.partial_cmp(b)),
+
+            _ => None
+
+        };
+        return if (let Some(result) = result) {
+            result
+        } else {
+            self.fail(
+                exception::RuntimeExceptionType::Type,
+                format!("Operator `{}` can not be applied to `{}` and `{}`.", operator, left.type_name(), right.type_name()),
+                range
+            );
+        };
+    }
+
+
+
+    fn get_module_member(&mut self, parent : data::Value, member : &String, range : data::Range) -> data::Value {
+        let found = match (&parent) {
+            data::Value::Class(class)      => class.methods.get(member).filter(|(is_static, _)| *is_static).map(|(_, function)| data::Value::Function(function.clone())),
+            data::Value::Module(module)    => module.get(member).cloned(),
+            data::Value::LocalModule(key)  => {
+                let globals = self.modules[key].clone();
+                Some(self.get_name(globals, member, range.clone()))
+            },
+            data::Value::Enum(enumeration) => enumeration.variants.iter().position(|(variant, _)| variant == member).map(|variant| {
+                if (enumeration.variants[variant].1.len() <= 0) {
+                    data::Value::Variant(enumeration.clone(), variant, Vec::new())
+                } else {
+                    data::Value::VariantConstructor(enumeration.clone(), variant)
+                }
+            }),
+            _                              => None
+        };
+        return if (let Some(found) = found) {
+            found
+        } else {
+            self.fail(
+                exception::RuntimeExceptionType::Name,
+                format!("`{}` has no member `{}`.", parent.type_name(), member),
+                range
+            );
+        };
+    }
+
+
+    fn get_class_member(&mut self, parent : data::Value, member : &String, range : data::Range) -> data::Value {
+        if (let data::Value::Instance(instance) = &parent) {
+            if (let Some(value) = instance.fields.borrow().get(member)) {
+                return value.clone();
+            }
+            if (instance.class.fields.iter().any(|(field, _)| field == member)) {
+                self.fail(
+                    exception::RuntimeExceptionType::Name,
+                    format!("Field `{}` of `{}` is not initialized.", member, instance.class.name),
+                    range
+                );
+            }
+            if (let Some((false, function)) = instance.class.methods.get(member)) {
+                return data::Value::Method(Box::new(parent.clone()), function.clone());
+            }
+        }
+        if (let Some(method) = library::method_value(&parent, member)) {
+            return method;
+        }
+        self.fail(
+            exception::RuntimeExceptionType::Name,
+            format!("`{}` has no member `{}`.", parent.type_name(), member),
+            range
+        );
+    }
+
+
+    fn get_slice(&mut self, parent : data::Value, index : data::Value, range : data::Range) -> data::Value {
+        let found = match (&parent, &index) {
+            (data::Value::List(values), data::Value::Int(i))
+            | (data::Value::Array(values), data::Value::Int(i))
+            | (data::Value::Tuple(values), data::Value::Int(i)) => usize::try_from(*i).ok().and_then(|i| values.get(i).cloned()),
+            (data::Value::String(text), data::Value::Int(i))    => usize::try_from(*i).ok().and_then(|i| text.chars().nth(i)).map(data::Value::Char),
+            (data::Value::Dict(entries), _)                     => {
+                if (let Some((_, value)) = entries.iter().find(|(key, _)| key.equals(&index))) {
+                    Some(value.clone())
+                } else {
+                    self.fail(
+                        exception::RuntimeExceptionType::Index,
+                        format!("Key `{}` is not in the dict.", index),
+                        range
+                    );
+                }
+            },
+            _                                                   => {
+                self.fail(
+                    exception::RuntimeExceptionType::Type,
+                    format!("Can not slice `{}` by `{}`.", parent.type_name(), index.type_name()),
+                    range
+                );
+            }
+        };
+        return if (let Some(found) = found) {
+            found
+        } else {
+            self.fail(
+                exception::RuntimeExceptionType::Index,
+                format!("Index `{}` is out of bounds for length {}.", index, length(&parent)),
+                range
+            );
+        };
+    }
+
+
+
+    // Evaluates the parts of an assignment target once, so `a[f()] += 1`
+    // calls `f` once.
+    fn start_place(&mut self, scope : data::Scope, target : data::Node) -> Result<Place, Flow> {
+        return Ok(match (target.node) {
+            data::NodeType::Literal(data::Literal::Name(name)) => Place::Name(name),
+            data::NodeType::ClassMember(parent, member)        => Place::Field(self.start_expression(scope, *parent)?, member),
+            data::NodeType::Slice(parent, index)               => {
+                let parent = self.start_place(scope.clone(), *parent)?;
+                Place::Slice(Box::new(parent), self.start_expression(scope, *index)?)
+            },
+            data::NodeType::BoundsCheck(target, bounds)        => Place::Checked(Box::new(self.start_place(scope, *target)?), bounds),
+            _                                                  => {
+                exception::InternalException::new(
+                    String::from("Invalid assignment target.")
+                ).dump_critical();
+            }
+        });
+    }
+
+
+    fn read_place(&mut self, scope : data::Scope, place : &Place, range : data::Range) -> data::Value {
+        return match (place) {
+            Place::Name(name)            => self.get_name(scope, name, range),
+            Place::Field(parent, member) => self.get_class_member(parent.clone(), member, range),
+            Place::Slice(parent, index)  => {
+                let parent = self.read_place(scope, parent, range.clone());
+                self.get_slice(parent, index.clone(), range)
+            },
+            Place::Checked(place, _)     => self.read_place(scope, place, range)
+        };
+    }
+
+
+    // Collections are values, so assigning into one writes the changed
+    // collection back to where it came from.
+    fn write_place(&mut self, scope : data::Scope, place : Place, value : data::Value, range : data::Range) -> () {
+        match (place) {
+
+            Place::Name(name) => {
+                // Makes sure a global that was never read exists.
+                self.get_name(scope.clone(), &name, range);
+                scope.set_name(&name, value);
+            },
+
+            Place::Field(parent, member) => {
+                if (let data::Value::Instance(instance) = parent) {
+                    instance.fields.borrow_mut().insert(member, value);
+                } else {
+                    self.fail(
+                        exception::RuntimeExceptionType::Name,
+                        format!("`{}` has no field `{}`.", parent.type_name(), member),
+                        range
+                    );
+                }
+            },
+
+            Place::Slice(parent, index) => {
+                let mut container = self.read_place(scope.clone(), &parent, range.clone());
+                let     size      = length(&container);
+                let     changed   = match (&mut container, &index) {
+                    (data::Value::List(values), data::Value::Int(i))
+                    | (data::Value::Array(values), data::Value::Int(i))
+                    | (data::Value::Tuple(values), data::Value::Int(i)) => {
+                        if (let Some(slot) = usize::try_from(*i).ok().and_then(|i| values.get_mut(i))) {
+                            *slot = value;
+                            true
+                        } else {false}
+                    },
+                    (data::Value::String(text), data::Value::Int(i)) => {
+                        let mut chars = text.chars().collect::<Vec<char>>();
+                        if (let (Some(slot), data::Value::Char(ch)) = (usize::try_from(*i).ok().and_then(|i| chars.get_mut(i)), &value)) {
+                            *slot = *ch;
+                            *text = chars.into_iter().collect();
+                            true
+                        } else {false}
+                    },
+                    (data::Value::Dict(entries), _) => {
+                        entries.retain(|(key, _)| ! key.equals(&index));
+                        entries.push((index.clone(), value));
+                        true
+                    },
+                    _ => {
+                        self.fail(
+                            exception::RuntimeExceptionType::Type,
+                            format!("Can not slice `{}` by `{}`.", container.type_name(), index.type_name()),
+                            range
+                        );
+                    }
+                };
+                if (! changed) {
+                    self.fail(
+                        exception::RuntimeExceptionType::Index,
+                        format!("Index `{}` is out of bounds for length {}.", index, size),
+                        range
+                    );
+                }
+                self.write_place(scope, *parent, container, range);
+            },
+
+            Place::Checked(place, bounds) => {
+                self.check_bounds(&value, &bounds, range.clone());
+                self.write_place(scope, *place, value, range);
+            }
+
+        }
+    }
+
+}
+
+
+
+fn compare(operator : &str, ordering : Option<std::cmp::Ordering>) -> Option<data::Value> {
+    let ordering = ordering?;
+    return Some(data::Value::Bool(match (operator) {
+        "<"  => ordering.is_lt(),
+        "<=" => ordering.is_le(),
+        ">"  => ordering.is_gt(),
+        ">=" => ordering.is_ge(),
+        _    => return None
+    }));
+}
+
+
+fn length(value : &data::Value) -> usize {
+    return match (value) {
+        data::Value::List(values) | data::Value::Array(values) | data::Value::Tuple(values) => values.len(),
+        data::Value::String(text)                                                       => text.chars().count(),
+        data::Value::Dict(entries)                                                      => entries.len(),
+        _                                                                               => 0
+    };
+}
+
+
+
+// Whether `value` matches `pattern`. The names it binds are added to `bindings`.
+fn matches_pattern(pattern : &data::Pattern, value : &data::Value, bindings : &mut Vec<(String, data::Value)>) -> bool {
+    return match (pattern, value) {
+        (data::Pattern::Wildcard, _)                                                  => true,
+        (data::Pattern::Binding(name), _)                                             => {
+            bindings.push((name.clone(), value.clone()));
+            true
+        },
+        (data::Pattern::Literal(literal), _)                                          => literal_value(literal).equals(value),
+        (data::Pattern::Range(min, max), _)                                           => {
+            let (min, max) = (literal_value(min), literal_value(max));
+            match (&min, &max, value) {
+                (data::Value::Int(min), data::Value::Int(max), data::Value::Int(value))       => min <= value && value < max,
+                (data::Value::Float(min), data::Value::Float(max), data::Value::Float(value)) => min <= value && value < max,
+                (data::Value::Char(min), data::Value::Char(max), data::Value::Char(value))    => min <= value && value < max,
+                _                                                                             => false
+            }
+        },
+        (data::Pattern::Variant(name, variant, payload, _), data::Value::Variant(enumeration, index, values)) => {
+            let (variant_name, fields) = &enumeration.variants[*index];
+            // `util::Color` was checked to be the same enum already.
+            if (name.rsplit("::").next() != Some(enumeration.name.as_str()) || variant_name != variant) {
+                return false;
+            }
+            payload.iter().enumerate().all(|(i, (field, sub_pattern))| {
+                let position = if (field.is_some()) {fields.iter().position(|other| other == field)} else {Some(i)};
+                position.and_then(|position| values.get(position)).is_some_and(|value| matches_pattern(sub_pattern, value, bindings))
+            })
+        },
+        _                                                                             => false
+    };
+}
+
+
+
+fn literal_value(literal : &data::Literal) -> data::Value {
+    return match (literal) {
+        data::Literal::Name(_)         => data::Value::Void,
+        data::Literal::Bool(value)     => data::Value::Bool(*value),
+        data::Literal::Character(ch)   => data::Value::Char(*ch),
+        data::Literal::String(text)    => data::Value::String(text.clone()),
+        data::Literal::Integer(number) => data::Value::Int(*number),
+        data::Literal::Float(number)   => data::Value::Float(*number)
+    };
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::exception;
+    use crate::run::exit_code;
+
+    #[test]
+    fn entry_function_gives_the_exit_code() {
+        let script = "
+            #[entry]
+            func main() : Int {
+                40 + 2
+            }
+        ";
+        assert!(exit_code(script) == 42);
+    }
+
+    #[test]
+    fn scope_return_leaves_the_function_from_inside_a_loop() {
+        let script = "
+            extern std;
+            func find(x : Int) : Int {
+                for i in 0..10 {
+                    if (i == x) {
+                        let found = std::scope::return(i * 10);
+                    }
+                }
+                0
+            }
+            #[entry]
+            func main() : Int {
+                find(3) + 1
+            }
+        ";
+        assert!(exit_code(script) == 31);
+    }
+
+    #[test]
+    fn compound_assignment_evaluates_its_target_once() {
+        let script = "
+            class Counter {
+                calls : Int = 0;
+                func next() : Int {
+                    self.calls += 1;
+                    0
+                }
+            }
+            #[entry]
+            func main() : Int {
+                let c      = Counter();
+                let mut xs = [10, 20];
+                xs[c.next()] += 5;
+                xs[0] + c.calls
+            }
+        ";
+        assert!(exit_code(script) == 16);
+    }
+
+    #[test]
+    fn loops_break_out_of_the_labelled_loop() {
+        let script = "
+            #[entry]
+            func main() : Int {
+                let mut total = 0;
+                outer: for i in 0..10 {
+                    for j in 0..10 {
+                        if (i == 3) {
+                            break outer;
+                        }
+                        if (j == 2) {
+                            continue outer;
+                        }
+                        total += 1;
+                    }
+                }
+                total
+            }
+        ";
+        assert!(exit_code(script) == 6);
+    }
+
+    #[test]
+    fn crash_exits_with_the_crash_code() {
+        let script = "
+            func divide(a : Int, b : Int) : Int {
+                a / b
+            }
+            #[entry]
+            func main() : Int {
+                divide(1, 0)
+            }
+        ";
+        assert!(exit_code(script) == exception::EXIT_CRASH);
+    }
+
+    #[test]
+    fn refined_value_inside_of_its_bounds_runs() {
+        let script = "
+            func clamp(value : Int) : RInt<0, 10> {
+                if (value < 0) {
+                    return 0;
+                }
+                if (value > 10) {
+                    return 10;
+                }
+                value
+            }
+            #[entry]
+            func main() : Int {
+                let low  : RInt<0, 10> = clamp(-4);
+                let high : RInt<0, 10> = clamp(7);
+                let sum  : RInt<0, 20> = low + high;
+                sum
+            }
+        ";
+        assert!(exit_code(script) == 7);
+    }
+
+    #[test]
+    fn refined_value_that_leaves_its_bounds_crashes() {
+        let script = "
+            func percent(value : Int) : RInt<0, 100> {
+                value * 10
+            }
+            #[entry]
+            func main() : Int {
+                percent(11)
+            }
+        ";
+        assert!(exit_code(script) == exception::EXIT_CRASH);
+    }
+
+    #[test]
+    fn refined_value_that_is_never_inside_of_its_bounds_is_rejected() {
+        let script = "
+            #[entry]
+            func main() : Int {
+                let small : RInt<0, 10> = 5;
+                let large : RInt<20, 30> = small + 100;
+                0
+            }
+        ";
+        assert!(exit_code(script) == exception::EXIT_ERROR);
+    }
+
+    #[test]
+    fn test_command_fails_if_a_test_crashes() {
+        let script = "
+            #[test]
+            func adds() : Void {
+                let sum : RInt<2, 2> = 1 + 1;
+            }
+            #[test]
+            func divides() : Void {
+                let values = [1, 0];
+                let quotient = 1 / values[1];
+            }
+        ";
+        let test = |filter : &str| exception::catch_exit(|| crate::run::test(String::from("<test>"), script.to_string(), filter.to_string()));
+        assert!(test("") == Ok(exception::EXIT_CRASH));
+        assert!(test("add") == Ok(0));
+        assert!(test("nothing") == Ok(0));
+    }
+
+}
+
@@ -7,26 +7,47 @@ use crate::exception::Exception;
 #[derive(Clone)]
 pub struct Lexer {
         script   : String,
+        offsets  : Vec<usize>, // the byte offset of each character in the script, then of its end
     pub position : data::Position,
         ch       : char,
         tokens   : Vec<data::Token>,
-        end      : bool
+        end      : bool,
+        lossless : bool,
+        trivia   : Vec<data::Trivia>,
+        newline  : bool
 }
 impl Lexer {
 
     pub fn calculate(filename : String, script : String) -> Vec<data::Token> {
+        return Lexer::calculate_mode(filename, script, false);
+    }
+
+    // Keeps whitespace and comments attached to the tokens, so that the
+    // original script can be reproduced from them byte for byte.
+    pub fn calculate_lossless(filename : String, script : String) -> Vec<data::Token> {
+        return Lexer::calculate_mode(filename, script, true);
+    }
+
+    fn calculate_mode(filename : String, script : String, lossless : bool) -> Vec<data::Token> {
+        let     source  = if (lossless) {script.clone()} else {script.replace("\r\n", "\n").replace("\r", "\n")};
+        let mut offsets = source.char_indices().map(|(offset, _)| offset).collect::<Vec<usize>>();
+        offsets.push(source.len());
         let mut lexer = Lexer {
-            script   : script.replace("\r\n", "\n").replace("\r", "\n"),
+            script   : source.clone(),
+            offsets,
             position : data::Position::new(
                 0,
                 0,
                 0,
                 filename,
-                script
+                source
             ),
-            ch     : ' ',
-            tokens : Vec::new(),
-            end    : false
+            ch       : ' ',
+            tokens   : Vec::new(),
+            end      : false,
+            lossless,
+            trivia   : Vec::new(),
+            newline  : false
         };
         lexer.update();
         lexer.start();
@@ -44,9 +65,17 @@ impl Lexer {
         }
     }
 
+    fn peek(&self) -> char {
+        return self.char_at(self.position.index + 1).unwrap_or(' ');
+    }
+
+    fn char_at(&self, index : usize) -> Option<char> {
+        return self.offsets.get(index).and_then(|offset| self.script[*offset..].chars().next());
+    }
+
     fn update(&mut self) -> () {
-        if (self.position.index < self.script.len()) {
-            self.ch  = self.script.chars().nth(self.position.index).unwrap();
+        if (let Some(ch) = self.char_at(self.position.index)) {
+            self.ch  = ch;
             self.end = false;
         } else {
             self.ch  = ' ';
@@ -59,6 +88,10 @@ impl Lexer {
         while (! self.end) {
             
             if ([' ', '\t', '\n', '\r'].contains(&self.ch)) {
+                self.push_trivia(
+                    if (self.ch == '\n') {data::TriviaType::Newline} else {data::TriviaType::Whitespace},
+                    self.ch.to_string()
+                );
                 self.advance();
             }
 
@@ -91,17 +124,56 @@ impl Lexer {
                 self.advance();
             }
             else if (self.ch == '<') {
-                self.push_token(data::TokenType::LCarat);
+                let start = self.position.clone();
                 self.advance();
+                if (self.ch == '<') {
+                    let second = self.position.clone();
+                    self.advance();
+                    if (self.ch == '=') {
+                        self.push_token_start(data::TokenType::DoubleLCaratEquals, start);
+                        self.advance();
+                    } else {
+                        self.push_token_start_end(data::TokenType::DoubleLCarat, start, second);
+                    }
+                } else if (self.ch == '=') {
+                    self.push_token_start(data::TokenType::LCaratEquals, start);
+                    self.advance();
+                } else {
+                    self.push_token_end(data::TokenType::LCarat, start);
+                }
             }
             else if (self.ch == '>') {
-                self.push_token(data::TokenType::RCarat);
+                let start = self.position.clone();
                 self.advance();
+                if (self.ch == '>') {
+                    let second = self.position.clone();
+                    self.advance();
+                    if (self.ch == '=') {
+                        self.push_token_start(data::TokenType::DoubleRCaratEquals, start);
+                        self.advance();
+                    } else {
+                        self.push_token_start_end(data::TokenType::DoubleRCarat, start, second);
+                    }
+                } else if (self.ch == '=') {
+                    self.push_token_start(data::TokenType::RCaratEquals, start);
+                    self.advance();
+                } else {
+                    self.push_token_end(data::TokenType::RCarat, start);
+                }
             }
 
             else if (self.ch == '=') {
-                self.push_token(data::TokenType::Equals);
+                let start = self.position.clone();
                 self.advance();
+                if (self.ch == '=') {
+                    self.push_token_start(data::TokenType::DoubleEquals, start);
+                    self.advance();
+                } else if (self.ch == '>') {
+                    self.push_token_start(data::TokenType::EqualsRCarat, start);
+                    self.advance();
+                } else {
+                    self.push_token_end(data::TokenType::Equals, start);
+                }
             }
             else if (self.ch == ':') {
                 let start = self.position.clone();
@@ -114,8 +186,14 @@ impl Lexer {
                 }
             }
             else if (self.ch == '.') {
-                self.push_token(data::TokenType::Period);
+                let start = self.position.clone();
                 self.advance();
+                if (self.ch == '.') {
+                    self.push_token_start(data::TokenType::DoublePeriod, start);
+                    self.advance();
+                } else {
+                    self.push_token_end(data::TokenType::Period, start);
+                }
             }
             else if (self.ch == ',') {
                 self.push_token(data::TokenType::Comma);
@@ -123,38 +201,82 @@ impl Lexer {
             }
 
             else if (self.ch == '+') {
-                self.push_token(data::TokenType::Plus);
+                let start = self.position.clone();
                 self.advance();
+                self.start_operator_equals(data::TokenType::Plus, data::TokenType::PlusEquals, start);
             }
             else if (self.ch == '-') {
-                self.push_token(data::TokenType::Minus);
+                let start = self.position.clone();
                 self.advance();
+                self.start_operator_equals(data::TokenType::Minus, data::TokenType::MinusEquals, start);
             }
             else if (self.ch == '*') {
                 let start = self.position.clone();
                 self.advance();
                 if (self.ch == '*') {
-                    self.push_token_start(data::TokenType::DoubleAstrisk, start);
+                    let second = self.position.clone();
                     self.advance();
+                    if (self.ch == '=') {
+                        self.push_token_start(data::TokenType::DoubleAstriskEquals, start);
+                        self.advance();
+                    } else {
+                        self.push_token_start_end(data::TokenType::DoubleAstrisk, start, second);
+                    }
                 } else {
-                    self.push_token_end(data::TokenType::Astrisk, start);
+                    self.start_operator_equals(data::TokenType::Astrisk, data::TokenType::AstriskEquals, start);
                 }
             }
             else if (self.ch == '/') {
                 let start = self.position.clone();
                 self.advance();
                 if (self.ch == '/') {
-                    self.start_eol_comment();
+                    self.start_eol_comment(start);
+                } else {
+                    self.start_operator_equals(data::TokenType::Slash, data::TokenType::SlashEquals, start);
+                }
+            }
+            else if (self.ch == '%') {
+                let start = self.position.clone();
+                self.advance();
+                self.start_operator_equals(data::TokenType::Percent, data::TokenType::PercentEquals, start);
+            }
+            else if (self.ch == '&') {
+                let start = self.position.clone();
+                self.advance();
+                if (self.ch == '&') {
+                    self.push_token_start(data::TokenType::DoubleAmpersand, start);
+                    self.advance();
+                } else {
+                    self.start_operator_equals(data::TokenType::Ampersand, data::TokenType::AmpersandEquals, start);
+                }
+            }
+            else if (self.ch == '|') {
+                let start = self.position.clone();
+                self.advance();
+                if (self.ch == '|') {
+                    self.push_token_start(data::TokenType::DoublePipe, start);
+                    self.advance();
                 } else {
-                    self.push_token_end(data::TokenType::Slash, start);
+                    self.start_operator_equals(data::TokenType::Pipe, data::TokenType::PipeEquals, start);
                 }
             }
+            else if (self.ch == '^') {
+                let start = self.position.clone();
+                self.advance();
+                self.start_operator_equals(data::TokenType::Circumflex, data::TokenType::CircumflexEquals, start);
+            }
             else if (self.ch == '!') {
-                self.push_token(data::TokenType::Bang);
+                let start = self.position.clone();
                 self.advance();
+                if (self.ch == '=') {
+                    self.push_token_start(data::TokenType::BangEquals, start);
+                    self.advance();
+                } else {
+                    self.push_token_end(data::TokenType::Bang, start);
+                }
             }
 
-            else if (data::ALPHABETIC.contains(self.ch)) {
+            else if (data::ALPHABETIC.contains(self.ch) || self.ch == '_') {
                 self.start_identifier();
             }
 
@@ -188,6 +310,16 @@ impl Lexer {
         self.push_token(data::TokenType::Eof);
     }
 
+    // Called on the character after a single character operator.
+    fn start_operator_equals(&mut self, token : data::TokenType, equals_token : data::TokenType, start : data::Position) -> () {
+        if (self.ch == '=') {
+            self.push_token_start(equals_token, start);
+            self.advance();
+        } else {
+            self.push_token_end(token, start);
+        }
+    }
+
     fn start_identifier(&mut self) -> () {
         let     start      = self.position.clone();
         let mut end        = self.position.clone();
@@ -206,23 +338,20 @@ impl Lexer {
     }
 
     fn start_character(&mut self) -> () {
-        let     start = self.position.clone();
-        let mut ch    = ' ';
+        let start = self.position.clone();
         if (self.ch != '\'') {
             exception::LexerException::new(
                 exception::LexerExceptionType::MissingCharacter,
-                format!("Expected character `'` not found."),
+                String::from("Expected character `'` not found."),
                 data::Range::new(start, self.position.clone())
             ).dump_error();
         };
         self.advance();
-        if (self.ch == '\\') {
+        let ch = if (self.ch == '\\') {
             let ch_start = self.position.clone();
             self.advance();
-            match (self.calculate_escape()) {
-                Ok(new_ch) => {
-                    ch = new_ch;
-                },
+            let ch = match (self.calculate_escape()) {
+                Ok(new_ch) => new_ch,
                 Err(new_ch) => {
                     exception::LexerException::new(
                         exception::LexerExceptionType::InvalidEscape,
@@ -232,11 +361,16 @@ impl Lexer {
                 }
             };
             self.advance();
-        }
+            ch
+        } else {
+            let ch = self.ch;
+            self.advance();
+            ch
+        };
         if (self.ch != '\'') {
             exception::LexerException::new(
                 exception::LexerExceptionType::MissingCharacter,
-                format!("Expected character `'` not found."),
+                String::from("Expected character `'` not found."),
                 data::Range::new(start, self.position.clone())
             ).dump_error();
         };
@@ -254,7 +388,7 @@ impl Lexer {
         if (self.ch != '"') {
             exception::LexerException::new(
                 exception::LexerExceptionType::MissingCharacter,
-                format!("Expected character `\"` not found."),
+                String::from("Expected character `\"` not found."),
                 data::Range::new(start, self.position.clone())
             ).dump_error();
         };
@@ -293,7 +427,7 @@ impl Lexer {
         if (self.end) {
             exception::LexerException::new(
                 exception::LexerExceptionType::MissingCharacter,
-                format!("Expected character `\"` not found."),
+                String::from("Expected character `\"` not found."),
                 data::Range::new(start, self.position.clone())
             ).dump_error();
         };
@@ -341,7 +475,7 @@ impl Lexer {
             (String::from(data::NUMERIC) + "_.").contains(self.ch)
         )) {
             if (self.ch == '.') {
-                if (dots >= 1) {
+                if (dots >= 1 || self.peek() == '.') {
                     break;
                 }
                 dots += 1;
@@ -368,40 +502,70 @@ impl Lexer {
         }
     }
 
-    fn start_eol_comment(&mut self) -> () {
+    fn start_eol_comment(&mut self, start : data::Position) -> () {
         while ((! self.end) && (
             self.ch != '\n'
         )) {
             self.advance();
         }
+        let comment = self.slice(start.index, self.position.index);
+        self.push_trivia(data::TriviaType::Comment, comment);
+    }
+
+    // The text between two character indices.
+    fn slice(&self, min : usize, max : usize) -> String {
+        let last  = self.offsets.len() - 1;
+        let start = self.offsets[min.min(last)];
+        let end   = self.offsets[max.min(last)];
+        return self.script[start..(end.max(start))].to_string();
+    }
+
+    // Trivia on the same line as the previous token trails it, everything
+    // else leads the next token.
+    fn push_trivia(&mut self, trivia : data::TriviaType, text : String) -> () {
+        if (! self.lossless) {
+            return;
+        }
+        let is_newline = trivia == data::TriviaType::Newline;
+        let target     = if ((! self.newline) && (! is_newline) && (self.tokens.len() >= 1)) {
+            &mut self.tokens.last_mut().unwrap().trailing
+        } else {
+            &mut self.trivia
+        };
+        if (let Some(last) = target.last_mut() && last.trivia == trivia && trivia != data::TriviaType::Comment) {
+            last.text += text.as_str();
+        } else {
+            target.push(data::Trivia::new(trivia, text));
+        }
+        if (is_newline) {
+            self.newline = true;
+        }
     }
 
     fn push_token(&mut self, token : data::TokenType) -> () {
-        self.tokens.push(data::Token::new(
-            token,
-            data::Range::new(self.position.clone(), self.position.clone())
-        ));
+        self.push_token_range(token, data::Range::new(self.position.clone(), self.position.clone()));
     }
 
     fn push_token_start(&mut self, token : data::TokenType, start : data::Position) -> () {
-        self.tokens.push(data::Token::new(
-            token,
-            data::Range::new(start, self.position.clone())
-        ));
+        self.push_token_range(token, data::Range::new(start, self.position.clone()));
     }
 
     fn push_token_start_end(&mut self, token : data::TokenType, start : data::Position, end : data::Position) -> () {
-        self.tokens.push(data::Token::new(
-            token,
-            data::Range::new(start, end)
-        ));
+        self.push_token_range(token, data::Range::new(start, end));
     }
 
     fn push_token_end(&mut self, token : data::TokenType, end : data::Position) -> () {
-        self.tokens.push(data::Token::new(
-            token,
-            data::Range::new(end.clone(), end)
-        ));
+        self.push_token_range(token, data::Range::new(end.clone(), end));
+    }
+
+    fn push_token_range(&mut self, token : data::TokenType, range : data::Range) -> () {
+        let mut token = data::Token::new(token, range);
+        if (self.lossless) {
+            token.text    = self.slice(token.range.min.index, token.range.max.index + 1);
+            token.leading = std::mem::take(&mut self.trivia);
+            self.newline  = false;
+        }
+        self.tokens.push(token);
     }
 
 }
@@ -0,0 +1,210 @@
+use std::io::Write;
+use std::rc::Rc;
+use std::collections::HashMap;
+
+use crate::config;
+use crate::data;
+use crate::exception;
+use crate::exception::Exception;
+
+
+
+// What the script was run with.
+pub struct Environment {
+    pub script    : String, // the path of the script that was run
+    pub arguments : Vec<String> // given after its filename
+}
+impl Environment {
+
+    pub fn new(script : String, arguments : Vec<String>) -> Environment {
+        return Environment {
+            script,
+            arguments
+        };
+    }
+
+}
+
+
+
+// The native functions of `extern std`, with their types.
+fn functions() -> Vec<(&'static str, &'static str, Vec<data::ValueType>, data::ValueType)> { // module, name, args, return_type
+    return vec![
+        ("std::stdout", "write",        vec![data::ValueType::String], data::ValueType::Void),
+        ("std::stdout", "writeln",      vec![data::ValueType::String], data::ValueType::Void),
+        ("std::stdout", "flush",        vec![],                        data::ValueType::Void),
+        ("std::stdin",  "input",        vec![],                        data::ValueType::String),
+        ("std::env",    "arguments",    vec![],                        data::ValueType::List(Box::new(data::ValueType::String))),
+        ("std::env",    "script",       vec![],                        data::ValueType::String),
+        ("std::env",    "variable",     vec![data::ValueType::String], data::ValueType::String),
+        ("std::env",    "has_variable", vec![data::ValueType::String], data::ValueType::Bool),
+        ("std::env",    "variables",    vec![],                        data::ValueType::Dict(Box::new(data::ValueType::String), Box::new(data::ValueType::String))),
+        ("std::env",    "config",       vec![data::ValueType::String], data::ValueType::String)
+    ];
+}
+
+// The built in types, which `std::types` names.
+const TYPES : [&str; 16] = [
+    "Void", "Crash", "Bool", "Int", "RInt", "Float", "RFloat", "Char", "String",
+    "IntRange", "FloatRange", "Tuple", "List", "Array", "Dict", "Func"
+];
+
+const MODULES : [&str; 6] = ["std", "std::stdout", "std::stdin", "std::env", "std::types", "std::scope"];
+
+// The native functions that act as keywords, which the validator and the
+// interpreter handle themselves.
+const KEYWORDS : [(&str, &str); 1] = [ // module, name
+    ("std::scope", "return")
+];
+
+
+
+// The members of a native module, as the validator sees them.
+pub fn module_type(name : &str) -> Option<data::ObjectType> {
+    if (! MODULES.contains(&name)) {
+        return None;
+    }
+    let mut members = HashMap::new();
+    for module in MODULES {
+        if (let Some((parent, member)) = module.rsplit_once("::") && parent == name) {
+            members.insert(member.to_string(), module_type(module)?);
+        }
+    }
+    for (module, function, args, return_type) in functions() {
+        if (module == name) {
+            members.insert(function.to_string(), data::ObjectType::Variable(false, data::ValueType::Func(args, Box::new(return_type))));
+        }
+    }
+    for (module, keyword) in KEYWORDS {
+        if (module == name) {
+            members.insert(keyword.to_string(), data::ObjectType::Keyword(format!("{}::{}", module, keyword)));
+        }
+    }
+    if (name == "std::types") {
+        for typ in TYPES {
+            members.insert(typ.to_string(), data::ObjectType::BuiltinType(typ.to_string()));
+        }
+    }
+    return Some(data::ObjectType::Module(members));
+}
+
+// The members of a native module at runtime. Types have no value.
+pub fn module_value(name : &str) -> Option<data::Value> {
+    if (! MODULES.contains(&name)) {
+        return None;
+    }
+    let mut members = HashMap::new();
+    for module in MODULES {
+        if (let Some((parent, member)) = module.rsplit_once("::") && parent == name) {
+            members.insert(member.to_string(), module_value(module)?);
+        }
+    }
+    for (module, function, _, _) in functions() {
+        if (module == name) {
+            members.insert(function.to_string(), data::Value::Native(format!("{}::{}", module, function), Vec::new()));
+        }
+    }
+    for (module, keyword) in KEYWORDS {
+        if (module == name) {
+            members.insert(keyword.to_string(), data::Value::Native(format!("{}::{}", module, keyword), Vec::new()));
+        }
+    }
+    return Some(data::Value::Module(Rc::new(members)));
+}
+
+
+
+// The methods that every value of a built in type has.
+pub fn method_type(typ : &data::ValueType, member : &str) -> Option<data::ValueType> {
+    return match (typ.widened(), member) {
+        (data::ValueType::Bool
+            | data::ValueType::Int
+            | data::ValueType::Float
+            | data::ValueType::Char
+            | data::ValueType::String, "to_string")  => Some(data::ValueType::Func(Vec::new(), Box::new(data::ValueType::String))),
+        (data::ValueType::String, "parse_int")       => Some(data::ValueType::Func(Vec::new(), Box::new(data::ValueType::Int))),
+        (data::ValueType::String, "parse_float")     => Some(data::ValueType::Func(Vec::new(), Box::new(data::ValueType::Float))),
+        (data::ValueType::Array(item, _), "to_list") => Some(data::ValueType::Func(Vec::new(), Box::new(data::ValueType::List(item)))),
+        _                                             => None
+    };
+}
+
+pub fn method_value(value : &data::Value, member : &str) -> Option<data::Value> {
+    let name = match (value, member) {
+        (data::Value::Bool(_)
+            | data::Value::Int(_)
+            | data::Value::Float(_)
+            | data::Value::Char(_)
+            | data::Value::String(_), "to_string") => "to_string",
+        (data::Value::String(_), "parse_int")      => "parse_int",
+        (data::Value::String(_), "parse_float")    => "parse_float",
+        (data::Value::Array(_), "to_list")         => "to_list",
+        _                                           => return None
+    };
+    return Some(data::Value::Native(name.to_string(), vec![value.clone()]));
+}
+
+
+
+// Runs a native function. A method gets its value as the first argument.
+pub fn call(name : &String, args : Vec<data::Value>, environment : &Environment, range : data::Range) -> data::Value {
+    return match (name.as_str(), args.as_slice()) {
+        ("std::stdout::write", [value])   => {
+            print!("{}", value);
+            data::Value::Void
+        },
+        ("std::stdout::writeln", [value]) => {
+            println!("{}", value);
+            data::Value::Void
+        },
+        ("std::stdout::flush", [])        => {
+            let _ = std::io::stdout().flush();
+            data::Value::Void
+        },
+        // The line break that ends the input is not part of it.
+        ("std::stdin::input", [])         => {
+            let mut line = String::new();
+            let _ = std::io::stdin().read_line(&mut line);
+            data::Value::String(line.trim_end_matches(['\n', '\r']).to_string())
+        },
+        ("std::env::arguments", [])       => data::Value::List(environment.arguments.iter().map(|argument| data::Value::String(argument.clone())).collect()),
+        ("std::env::script", [])          => data::Value::String(environment.script.clone()),
+        // Values that are not valid unicode have their invalid parts replaced.
+        ("std::env::variable", [data::Value::String(variable)]) => match (std::env::var_os(variable)) {
+            Some(value) => data::Value::String(value.to_string_lossy().to_string()),
+            None        => fail(exception::RuntimeExceptionType::Name, format!("Environment variable `{}` is not set. Check it with `has_variable` first.", variable), range)
+        },
+        ("std::env::has_variable", [data::Value::String(variable)]) => data::Value::Bool(std::env::var_os(variable).is_some()),
+        ("std::env::config", [data::Value::String(config_name)]) => match (config::get(config_name)) {
+            Some(value) => data::Value::String(value),
+            None        => fail(exception::RuntimeExceptionType::Name, format!("Config `{}` is not set. Set it in `{}` or with `--cfg.{} VALUE`.", config_name, config::FILENAME, config_name), range)
+        },
+        ("std::env::variables", [])       => data::Value::Dict(std::env::vars_os().map(|(variable, value)| (
+            data::Value::String(variable.to_string_lossy().to_string()),
+            data::Value::String(value.to_string_lossy().to_string())
+        )).collect()),
+        ("to_string", [value])            => data::Value::String(value.to_string()),
+        ("to_list", [data::Value::Array(items)]) => data::Value::List(items.clone()),
+        ("parse_int", [data::Value::String(text)]) => match (text.trim().parse::<i64>()) {
+            Ok(number) => data::Value::Int(number),
+            Err(_)     => fail(exception::RuntimeExceptionType::Type, format!("`{}` is not an `Int`.", text), range)
+        },
+        ("parse_float", [data::Value::String(text)]) => match (text.trim().parse::<f64>()) {
+            Ok(number) => data::Value::Float(number),
+            Err(_)     => fail(exception::RuntimeExceptionType::Type, format!("`{}` is not a `Float`.", text), range)
+        },
+        _                                 => {
+            exception::InternalException::new(
+                format!("Invalid call of native function `{}`.", name)
+            ).dump_critical();
+        }
+    };
+}
+
+fn fail(exception_type : exception::RuntimeExceptionType, message : String, range : data::Range) -> ! {
+    exception::RuntimeException::new(
+        exception_type,
+        message,
+        range
+    ).dump_error();
+}
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use crate::data;
+use crate::exception;
+use crate::exception::Exception;
+use crate::lexer;
+use crate::parser;
+
+
+
+pub struct Loader {
+    root    : std::path::PathBuf, // the directory of the entry script
+    modules : Vec<data::Module>,
+    chain   : Vec<std::path::PathBuf> // the modules that are being loaded, each used by the one before
+}
+impl Loader {
+
+    // Returns the script and every module that it uses, each after the
+    // modules that it uses itself.
+    pub fn calculate(filename : String, script : String) -> Vec<data::Module> {
+        let path = std::path::PathBuf::from(&filename);
+        let mut loader = Loader {
+            root    : canonical(&path).parent().map(|parent| parent.to_path_buf()).unwrap_or_default(),
+            modules : Vec::new(),
+            chain   : Vec::new()
+        };
+        loader.load(String::new(), path, script);
+        return loader.modules;
+    }
+
+
+
+    fn load(&mut self, key : String, path : std::path::PathBuf, script : String) -> () {
+        let tokens = lexer::Lexer::calculate(path.display().to_string(), script);
+        let nodes  = parser::Parser::calculate(tokens);
+
+        self.chain.push(canonical(&path));
+        let mut imports = HashMap::new();
+        for node in &nodes {
+            if (let data::NodeType::LocalImport(name) = &node.node) {
+                let import_key = self.start_import(&path, name, node.range.clone());
+                imports.insert(name.clone(), import_key);
+            }
+        }
+        self.chain.pop();
+
+        self.modules.push(data::Module::new(key, path.display().to_string(), nodes, imports));
+    }
+
+
+
+    // `use util;` loads `util.vsv` or `util/mod.vsv` next to the script
+    // that uses it.
+    fn start_import(&mut self, path : &std::path::Path, name : &String, range : data::Range) -> String {
+        let directory = path.parent().map(|parent| parent.to_path_buf()).unwrap_or_default();
+        let file      = directory.join(format!("{}.vsv", name));
+        let folder    = directory.join(name).join("mod.vsv");
+        let found     = match (file.is_file(), folder.is_file()) {
+            (true, false) => file,
+            (false, true) => folder,
+            (true, true)  => {
+                exception::ParserException::new(
+                    exception::ParserExceptionType::AmbiguousModule,
+                    format!("Module `{}` is ambiguous. Found both `{}` and `{}`.", name, file.display(), folder.display()),
+                    range
+                ).dump_error();
+            },
+            (false, false) => {
+                exception::ParserException::new(
+                    exception::ParserExceptionType::MissingModule,
+                    format!("Module `{}` not found. Expected `{}` or `{}`.", name, file.display(), folder.display()),
+                    range
+                ).dump_error();
+            }
+        };
+
+        let canonical_path = canonical(&found);
+        // The chain starts at the script that was run.
+        if (self.chain.contains(&canonical_path)) {
+            let mut cycle = self.chain.iter().map(|other| format!("`{}`", relative(&self.root, other))).collect::<Vec<String>>();
+            cycle.push(format!("`{}`", relative(&self.root, &canonical_path)));
+            exception::ParserException::new(
+                exception::ParserExceptionType::CyclicImport,
+                format!("Modules use each other in a cycle : {}.", cycle.join(" -> ")),
+                range
+            ).dump_error();
+        }
+
+        let key = module_key(&self.root, &canonical_path);
+        if (self.modules.iter().any(|module| module.key == key)) {
+            return key;
+        }
+        let script = match (std::fs::read_to_string(&found)) {
+            Ok(script) => script,
+            Err(_)     => {
+                exception::ParserException::new(
+                    exception::ParserExceptionType::MissingModule,
+                    format!("Module `{}` could not be read from `{}`.", name, found.display()),
+                    range
+                ).dump_error();
+            }
+        };
+        self.load(key.clone(), found, script);
+        return key;
+    }
+
+}
+
+
+
+fn canonical(path : &std::path::PathBuf) -> std::path::PathBuf {
+    return std::fs::canonicalize(path).unwrap_or(path.clone());
+}
+
+fn relative(root : &std::path::Path, path : &std::path::Path) -> String {
+    return path.strip_prefix(root).map(|path| path.display().to_string()).unwrap_or(path.display().to_string());
+}
+
+// `util/helpers.vsv` and `util/helpers/mod.vsv` are both `util::helpers`.
+fn module_key(root : &std::path::Path, path : &std::path::Path) -> String {
+    let relative  = path.strip_prefix(root).unwrap_or(path).with_extension("");
+    let mut parts = relative.components().map(|part| part.as_os_str().to_string_lossy().to_string()).collect::<Vec<String>>();
+    if (parts.len() >= 2 && parts[parts.len() - 1] == "mod") {
+        parts.pop();
+    }
+    return parts.join("::");
+}
@@ -1,32 +1,116 @@
 #![allow(unused_parens)]
+// The style of the crate: explicit returns and unit types, `len() <= 0`, and
+// types and errors spelled out in full.
+#![allow(
+    clippy::needless_return,
+    clippy::unused_unit,
+    clippy::absurd_extreme_comparisons,
+    clippy::len_zero,
+    clippy::cmp_owned,
+    clippy::box_collection,
+    clippy::type_complexity,
+    clippy::too_many_arguments,
+    clippy::large_enum_variant,
+    clippy::result_large_err
+)]
 #![feature(let_chains)]
 
-use std;
 use colored::Colorize;
 
 mod argument;
+mod config;
 mod data;
 mod exception;
 use exception::Exception;
 mod run;
 mod lexer;
 mod parser;
+mod loader;
 mod validator;
+mod interpreter;
+mod library;
+mod repl;
+mod formatter;
 
 
 
 fn main() -> () {
-    let arguments = std::env::args().collect::<Vec<String>>();
-    argument::parse(arguments[0].clone(), arguments[1..(arguments.len())].to_vec());
+    let arguments  = std::env::args().collect::<Vec<String>>();
+    let invocation = argument::parse(arguments[0].clone(), arguments[1..(arguments.len())].to_vec());
+    match (invocation.command.as_str()) {
+        "run"   => run(invocation),
+        "check" => check(invocation),
+        "fmt"   => fmt(invocation),
+        "repl"  => repl(invocation),
+        "test"  => test(invocation),
+        _       => {
+            exception::InternalException::new(
+                format!("Command `{}` has no handler.", invocation.command)
+            ).dump_critical();
+        }
+    }
+}
+
+
+fn run(invocation : argument::Invocation) -> ! {
+    let script = read(&invocation);
+    config::load(&invocation);
+    let code = run::run(invocation.get_filename(), script, invocation.arguments);
+    std::process::exit(code);
 }
 
 
-fn run(full_arguments : Vec<String>, index : usize) -> ! {
-    run::run(full_arguments[index].clone(), read(full_arguments, index));
+fn check(invocation : argument::Invocation) -> ! {
+    let script = read(&invocation);
+    config::load(&invocation);
+    run::check(invocation.get_filename(), script);
+    println!("{}", format!("`{}` has no errors.", invocation.get_filename()).green());
     std::process::exit(0);
 }
 
 
+// With `--check` the script is only compared with its formatted version.
+fn fmt(invocation : argument::Invocation) -> ! {
+    let script    = read(&invocation);
+    let filename  = invocation.get_filename();
+    let formatted = formatter::Formatter::calculate(filename.clone(), script.clone());
+    if (invocation.options.contains_key("--check")) {
+        if (formatted != script) {
+            println!("{}", format!("`{}` is not formatted.", filename).red());
+            std::process::exit(exception::EXIT_ERROR);
+        }
+        println!("{}", format!("`{}` is formatted.", filename).green());
+        std::process::exit(0);
+    }
+    if (formatted != script && std::fs::write(&filename, formatted).is_err()) {
+        exception::CommandLineException::new(
+            exception::CommandLineExceptionType::FileFailedToRead,
+            format!("File `{}` could not be written", filename),
+            invocation.full_arguments.clone(),
+            invocation.filename.unwrap_or(0)
+        ).dump_error();
+    }
+    println!("{}", format!("Formatted `{}`.", filename).green());
+    std::process::exit(0);
+}
+
+
+// With `--filter NAME` only the tests whose name contains NAME are run.
+fn test(invocation : argument::Invocation) -> ! {
+    let script = read(&invocation);
+    config::load(&invocation);
+    let filter = invocation.options.get("--filter").cloned().unwrap_or_default();
+    let code   = run::test(invocation.get_filename(), script, filter);
+    std::process::exit(code);
+}
+
+
+fn repl(invocation : argument::Invocation) -> ! {
+    config::load(&invocation);
+    repl::Repl::calculate();
+}
+
+
 fn version() -> () {
     println!("\n{a}\n {} {}\n {}\n{a}\n",
         "Vesuvius Programming Language".red().bold(),
@@ -37,38 +121,20 @@ fn version() -> () {
 }
 
 
-fn help(call_argument : String) -> ! {
-    version();
-    println!("{}:\n  {}\n  {}\n\n{}:\n  {}\n  {}\n  {}\n\n{}:\n  {}\n  {}\n",
-        "USAGE".blue().bold(),
-        format!("{} {}", call_argument, "[FLAG]").cyan(),
-        format!("{} {} {}", call_argument, "[FILENAME]", "[OPTION]*").cyan(),
-        "FLAGS".blue().bold(),
-        format!("{} {}           : {}", "-h".bold(), "--help".bold(), "Display this help message.").cyan(),
-        format!("{} {}        : {}", "-v".bold(), "--version".bold(), "Display the version number.").cyan(),
-        format!("{} : {}", "-cfg.[NAME] [VALUE]".bold(), "Set a global config value.").cyan(),
-        "OPTIONS".blue().bold(),
-        format!("{} {} : {}", "-V".bold(), "--validate".bold(), "Check script for errors without running.").cyan(),
-        format!("{} {}  : {}", "-C".bold(), "--compile".bold(), "Compile script.").cyan()
-    );
-    std::process::exit(0);
-}
-
-
-
 fn get_version_number() -> String {
     return String::from(env!("CARGO_PKG_VERSION"));
 }
 
-fn read(arguments : Vec<String>, filename_index : usize) -> String {
-    let contents = match std::fs::read_to_string(arguments[filename_index].clone()) {
+fn read(invocation : &argument::Invocation) -> String {
+    let filename = invocation.get_filename();
+    let contents = match std::fs::read_to_string(&filename) {
         Ok(contents) => contents,
         Err(_e)      => {
             exception::CommandLineException::new(
                 exception::CommandLineExceptionType::FileFailedToRead,
-                format!("File `{}` was not found", arguments[filename_index]),
-                arguments,
-                filename_index
+                format!("File `{}` was not found", filename),
+                invocation.full_arguments.clone(),
+                invocation.filename.unwrap_or(0)
             ).dump_error();
         }
     };
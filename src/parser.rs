@@ -4,17 +4,74 @@ use crate::exception::Exception;
 
 
 
+#[derive(Clone, Copy)]
+pub enum Associativity {
+    Left,
+    Right
+}
+
+// Binary operators : token, binding power, associativity, node.
+// Higher binding powers bind tighter.
+const BINARY_OPERATORS : [(data::TokenType, usize, Associativity, fn(Box<data::Node>, Box<data::Node>) -> data::NodeType); 19] = [
+    (data::TokenType::DoublePipe,      1,  Associativity::Left,  data::NodeType::OrOperation),
+    (data::TokenType::DoubleAmpersand, 2,  Associativity::Left,  data::NodeType::AndOperation),
+    (data::TokenType::DoubleEquals,    3,  Associativity::Left,  data::NodeType::EqualOperation),
+    (data::TokenType::BangEquals,      3,  Associativity::Left,  data::NodeType::NotEqualOperation),
+    (data::TokenType::LCarat,          3,  Associativity::Left,  data::NodeType::LessOperation),
+    (data::TokenType::LCaratEquals,    3,  Associativity::Left,  data::NodeType::LessEqualOperation),
+    (data::TokenType::RCarat,          3,  Associativity::Left,  data::NodeType::GreaterOperation),
+    (data::TokenType::RCaratEquals,    3,  Associativity::Left,  data::NodeType::GreaterEqualOperation),
+    (data::TokenType::Pipe,            4,  Associativity::Left,  data::NodeType::BitwiseOrOperation),
+    (data::TokenType::Circumflex,      5,  Associativity::Left,  data::NodeType::BitwiseXorOperation),
+    (data::TokenType::Ampersand,       6,  Associativity::Left,  data::NodeType::BitwiseAndOperation),
+    (data::TokenType::DoubleLCarat,    7,  Associativity::Left,  data::NodeType::ShiftLeftOperation),
+    (data::TokenType::DoubleRCarat,    7,  Associativity::Left,  data::NodeType::ShiftRightOperation),
+    (data::TokenType::Plus,            8,  Associativity::Left,  data::NodeType::AdditionOperation),
+    (data::TokenType::Minus,           8,  Associativity::Left,  data::NodeType::SubtractionOperation),
+    (data::TokenType::Astrisk,         9,  Associativity::Left,  data::NodeType::MultiplicationOperation),
+    (data::TokenType::Slash,           9,  Associativity::Left,  data::NodeType::DivisionOperation),
+    (data::TokenType::Percent,         9,  Associativity::Left,  data::NodeType::ModuloOperation),
+    (data::TokenType::DoubleAstrisk,   11, Associativity::Right, data::NodeType::PowerOperation)
+];
+
+// Compound assignment operators : token, node.
+const ASSIGN_OPERATORS : [(data::TokenType, &str); 11] = [
+    (data::TokenType::PlusEquals,          "+"),
+    (data::TokenType::MinusEquals,         "-"),
+    (data::TokenType::AstriskEquals,       "*"),
+    (data::TokenType::SlashEquals,         "/"),
+    (data::TokenType::DoubleAstriskEquals, "**"),
+    (data::TokenType::PercentEquals,       "%"),
+    (data::TokenType::AmpersandEquals,     "&"),
+    (data::TokenType::PipeEquals,          "|"),
+    (data::TokenType::CircumflexEquals,    "^"),
+    (data::TokenType::DoubleLCaratEquals,  "<<"),
+    (data::TokenType::DoubleRCaratEquals,  ">>")
+];
+
+const LOOP_KEYWORDS : [&str; 3] = ["while", "loop", "for"];
+
+// Prefix operators : token, binding power of the operand, node.
+const PREFIX_OPERATORS : [(data::TokenType, usize, fn(Box<data::Node>) -> data::NodeType); 2] = [
+    (data::TokenType::Minus,           10, data::NodeType::OppositeOperation),
+    (data::TokenType::Bang,            10, data::NodeType::InvertOperation)
+];
+
+
+
 #[derive(Clone)]
 pub struct ParserData {
     pub allow_assign  : bool,
-    pub allow_mutable : bool
+    pub allow_mutable : bool,
+    pub allow_greater : bool  // `>` closes a tuple instead.
 }
 impl ParserData {
 
     pub fn new() -> ParserData {
         return ParserData {
             allow_assign  : true,
-            allow_mutable : true
+            allow_mutable : true,
+            allow_greater : true
         }
     }
     
@@ -24,33 +81,108 @@ impl ParserData {
 
 #[derive(Clone)]
 pub struct Parser {
-    tokens : Vec<data::Token>,
-    index  : usize,
-    token  : data::Token,
-    nodes  : Vec<data::Node>,
-    end    : bool
+    tokens  : Vec<data::Token>,
+    index   : usize,
+    token   : data::Token,
+    nodes   : Vec<data::Node>,
+    end     : bool,
+    builder : Option<data::SyntaxBuilder>
 }
 impl Parser {
 
     pub fn calculate(tokens : Vec<data::Token>) -> Vec<data::Node> {
-        let mut parser = Parser {
-            tokens : tokens,
-            index  : 0,
-            token  : data::Token::new_void(),
-            nodes  : Vec::new(),
-            end    : false
-        };
+        let mut parser = Parser::new(tokens, None);
         parser.update();
         parser.start();
         return parser.nodes;
         
     }
 
+    // Builds the concrete syntax tree alongside the nodes. The tokens should
+    // come from `Lexer::calculate_lossless` for the tree to keep all trivia.
+    pub fn calculate_lossless(tokens : Vec<data::Token>) -> (Vec<data::Node>, std::rc::Rc<data::SyntaxNode>) {
+        let mut parser = Parser::new(tokens, Some(data::SyntaxBuilder::new()));
+        parser.update();
+        parser.start();
+        let tree = parser.builder.take().unwrap().finish();
+        return (parser.nodes, tree);
+    }
+
+    // The statements of a block without its braces, like the input of the
+    // REPL. The final expression, without `;`, is its result.
+    pub fn calculate_statements(tokens : Vec<data::Token>) -> Vec<data::Node> {
+        let mut parser = Parser::new(tokens, None);
+        parser.update();
+        let mut nodes = Vec::new();
+        while ((! parser.end) && (! matches!(parser.token.token, data::TokenType::Eof))) {
+            if (matches!(parser.token.token, data::TokenType::Eol)) {
+                parser.advance();
+                continue;
+            }
+            nodes.push(parser.start_expression_base(ParserData::new()));
+        }
+        return nodes;
+    }
+
+    fn new(tokens : Vec<data::Token>, builder : Option<data::SyntaxBuilder>) -> Parser {
+        return Parser {
+            tokens,
+            index   : 0,
+            token   : data::Token::new_void(),
+            nodes   : Vec::new(),
+            end     : false,
+            builder
+        };
+    }
+
     fn advance(&mut self) -> () {
+        if (let Some(builder) = &mut self.builder && ! self.end) {
+            builder.token(self.token.clone());
+        }
         self.index += 1;
         self.update();
     }
 
+    // Splits the current token after its first character, so that `>>` can
+    // close two type argument lists and `<<` can open two.
+    fn split_token(&mut self, first : data::TokenType, rest : data::TokenType) -> () {
+        let     token      = self.token.clone();
+        let mut rest_min   = token.range.min.clone();
+        rest_min.index    += 1;
+        rest_min.column   += 1;
+        let mut head       = data::Token::new(first, data::Range::new(token.range.min.clone(), token.range.min.clone()));
+        let mut tail       = data::Token::new(rest, data::Range::new(rest_min, token.range.max.clone()));
+        head.text          = token.text.chars().take(1).collect::<String>();
+        head.leading       = token.leading;
+        tail.text          = token.text.chars().skip(1).collect::<String>();
+        tail.trailing      = token.trailing;
+        self.tokens[self.index] = head;
+        self.tokens.insert(self.index + 1, tail);
+        self.update();
+    }
+
+    fn start_node(&mut self, syntax : data::SyntaxType) -> () {
+        if (let Some(builder) = &mut self.builder) {
+            builder.start_node(syntax);
+        }
+    }
+
+    fn start_node_at(&mut self, checkpoint : usize, syntax : data::SyntaxType) -> () {
+        if (let Some(builder) = &mut self.builder) {
+            builder.start_node_at(checkpoint, syntax);
+        }
+    }
+
+    fn checkpoint(&self) -> usize {
+        return if (let Some(builder) = &self.builder) {builder.checkpoint()} else {0};
+    }
+
+    fn finish_node(&mut self) -> () {
+        if (let Some(builder) = &mut self.builder) {
+            builder.finish_node();
+        }
+    }
+
     fn update(&mut self) -> () {
         if (self.index < self.tokens.len()) {
             self.token = self.tokens[self.index].clone();
@@ -63,6 +195,7 @@ impl Parser {
 
 
     fn start(&mut self) -> () {
+        self.start_node(data::SyntaxType::Root);
         while ((! self.end) && (
             ! matches!(self.token.token, data::TokenType::Eof)
         )) {
@@ -73,8 +206,15 @@ impl Parser {
                 break;
             }
             let node = self.start_statement_global(ParserData::new());
-            self.nodes.push(node);
+            // Nodes whose `#[cfg(...)]` does not hold are left out.
+            if (node.headers.is_enabled()) {
+                self.nodes.push(node);
+            }
+        }
+        if (matches!(self.token.token, data::TokenType::Eof)) {
+            self.advance();
         }
+        self.finish_node();
     }
 
 
@@ -83,9 +223,16 @@ impl Parser {
 
     fn start_statement_global(&mut self, data : ParserData) -> data::Node {
 
+        let     checkpoint  = self.checkpoint();
+        let mut header_list = Vec::new();
+        while (matches!(self.token.token, data::TokenType::Hash)) {
+            header_list.push(self.start_statement_header());
+        }
+
         if (let data::TokenType::Identifier(keyword) = self.token.token.clone()) {
 
             if (keyword.as_str() == "extern") {
+                self.start_node_at(checkpoint, data::SyntaxType::ExternalImport);
                 let start = self.token.range.min.clone();
                 self.advance();
                 let name = if (let data::TokenType::Identifier(name) = self.token.token.clone()) {
@@ -99,13 +246,17 @@ impl Parser {
                 };
                 let end = self.token.range.max.clone();
                 self.advance();
-                return data::Node::new(
+                self.finish_node();
+                let mut import = data::Node::new(
                     data::NodeType::ExternalImport(name),
                     data::Range::new(start, end)
                 );
+                import.headers = data::NodeHeaders::from(header_list, data::AttributeTarget::Import);
+                return import;
             }
 
             else if (keyword.as_str() == "use") {
+                self.start_node_at(checkpoint, data::SyntaxType::LocalImport);
                 let start = self.token.range.min.clone();
                 self.advance();
                 let name = if (let data::TokenType::Identifier(name) = self.token.token.clone()) {
@@ -119,45 +270,72 @@ impl Parser {
                 };
                 let end = self.token.range.max.clone();
                 self.advance();
-                return data::Node::new(
+                self.finish_node();
+                let mut import = data::Node::new(
                     data::NodeType::LocalImport(name),
                     data::Range::new(start, end)
                 );
+                import.headers = data::NodeHeaders::from(header_list, data::AttributeTarget::Import);
+                return import;
             }
         }
         
-        return self.start_statement(data);
+        return self.start_statement(data, checkpoint, header_list);
 
     }
 
 
 
-    fn start_statement(&mut self, data : ParserData) -> data::Node {
+    fn start_statement(&mut self, data : ParserData, checkpoint : usize, header_list : Vec<data::Attribute>) -> data::Node {
 
         if (let data::TokenType::Identifier(keyword) = self.token.token.clone()) {
             if (keyword == String::from("let")) {
                 let mut new_data = data.clone();
                 new_data.allow_mutable = false;
-                return self.start_initialize_variable(new_data);
+                let mut variable = self.start_initialize_variable(new_data);
+                variable.headers = data::NodeHeaders::from(header_list, data::AttributeTarget::Variable);
+                return variable;
             }
-        }
-
-        let mut header_list = Vec::new();
-        while (matches!(self.token.token, data::TokenType::Hash)) {
-            header_list.push((self.start_statement_header(), self.token.range.clone()));
-        }
-
-        if (let data::TokenType::Identifier(keyword) = self.token.token.clone()) {
-            if (keyword == String::from("func")) {
-                let mut function = self.start_statement_function(data);
-                function.headers = data::NodeHeaders::from(header_list);
+            else if (keyword == String::from("func")) {
+                self.start_node_at(checkpoint, data::SyntaxType::DefineFunction);
+                let mut function = self.start_statement_function(data, true);
+                self.finish_node();
+                function.headers = data::NodeHeaders::from(header_list, data::AttributeTarget::Function);
                 return function;
             }
+            else if (keyword == String::from("class")) {
+                self.start_node_at(checkpoint, data::SyntaxType::DefineClass);
+                let mut class = self.start_statement_class(data);
+                self.finish_node();
+                class.headers = data::NodeHeaders::from(header_list, data::AttributeTarget::Class);
+                return class;
+            }
+            else if (keyword == String::from("enum")) {
+                self.start_node_at(checkpoint, data::SyntaxType::DefineEnum);
+                let mut enumeration = self.start_statement_enum(data);
+                self.finish_node();
+                enumeration.headers = data::NodeHeaders::from(header_list, data::AttributeTarget::Enum);
+                return enumeration;
+            }
+            else if (keyword == String::from("trait")) {
+                self.start_node_at(checkpoint, data::SyntaxType::DefineTrait);
+                let mut definition = self.start_statement_trait(data);
+                self.finish_node();
+                definition.headers = data::NodeHeaders::from(header_list, data::AttributeTarget::Trait);
+                return definition;
+            }
+            else if (keyword == String::from("impl")) {
+                self.start_node_at(checkpoint, data::SyntaxType::ImplementTrait);
+                let mut implementation = self.start_statement_impl(data);
+                self.finish_node();
+                implementation.headers = data::NodeHeaders::from(header_list, data::AttributeTarget::Impl);
+                return implementation;
+            }
         }
 
         exception::ParserException::new(
             exception::ParserExceptionType::MissingToken,
-            format!("Expected {}`#`, `func` not found.", if (header_list.len() >= 1) {""} else {"`let`, "}),
+            String::from("Expected `#`, `extern`, `use`, `let`, `func`, `class`, `enum`, `trait`, `impl` not found."),
             self.token.range.clone()
         ).dump_error();
 
@@ -165,245 +343,1350 @@ impl Parser {
 
 
 
-    fn start_statement_header(&mut self) -> String {
+    fn start_statement_class(&mut self, data : ParserData) -> data::Node {
 
-        if (! matches!(self.token.token, data::TokenType::Hash)) {
+        let start = self.token.range.min.clone();
+        // On `class`.
+        self.advance();
+
+        let name = if (let data::TokenType::Identifier(name) = self.token.token.clone()) {
+            name
+        } else {
             exception::ParserException::new(
                 exception::ParserExceptionType::MissingToken,
-                String::from("Expected `#` not found."),
+                String::from("Expected Identifier not found."),
+                self.token.range.clone()
+            ).dump_error();
+        };
+        self.advance();
+
+        let generics = self.start_statement_generics();
+
+        let mut bases = Vec::new();
+        if (matches!(self.token.token.clone(), data::TokenType::Identifier(keyword) if keyword == "extends")) {
+            loop {
+                self.advance();
+                if (let data::TokenType::Identifier(base) = self.token.token.clone()) {
+                    bases.push(base);
+                } else {
+                    exception::ParserException::new(
+                        exception::ParserExceptionType::MissingToken,
+                        String::from("Expected Identifier not found."),
+                        self.token.range.clone()
+                    ).dump_error();
+                }
+                self.advance();
+                if (! matches!(self.token.token, data::TokenType::Comma)) {
+                    break;
+                }
+            }
+        }
+
+        if (! matches!(self.token.token, data::TokenType::LBrace)) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                format!("Expected {}`{{` not found.", if (bases.len() >= 1) {"`,`, "} else if (generics.len() >= 1) {"`extends`, "} else {"`<`, `extends`, "}),
                 self.token.range.clone()
             ).dump_error();
         }
         self.advance();
 
-        if (! matches!(self.token.token, data::TokenType::LBracket)) {
+        let mut fields  = Vec::new();
+        let mut methods = Vec::new();
+        while (! matches!(self.token.token, data::TokenType::RBrace | data::TokenType::Eof)) {
+            if (matches!(self.token.token, data::TokenType::Eol)) {
+                self.advance();
+                continue;
+            }
+
+            let     checkpoint  = self.checkpoint();
+            let mut header_list = Vec::new();
+            while (matches!(self.token.token, data::TokenType::Hash)) {
+                header_list.push(self.start_statement_header());
+            }
+            if (header_list.len() >= 1 || matches!(self.token.token.clone(), data::TokenType::Identifier(keyword) if keyword == "func")) {
+                self.start_node_at(checkpoint, data::SyntaxType::DefineFunction);
+                let mut method = self.start_statement_function(data.clone(), true);
+                self.finish_node();
+                method.headers = data::NodeHeaders::from(header_list, data::AttributeTarget::Method);
+                if (method.headers.is_enabled()) {
+                    methods.push(method);
+                }
+            } else {
+                fields.push(self.start_statement_class_field(data.clone()));
+            }
+        }
+
+        let end = self.token.range.max.clone();
+        if (! matches!(self.token.token, data::TokenType::RBrace)) {
             exception::ParserException::new(
                 exception::ParserExceptionType::MissingToken,
-                String::from("Expected `[` not found."),
+                String::from("Expected `}` not found."),
                 self.token.range.clone()
             ).dump_error();
         }
         self.advance();
 
+        return data::Node::new(
+            data::NodeType::DefineClass(name, generics, bases, Box::new(fields), Box::new(methods)),
+            data::Range::new(start, end)
+        );
+
+    }
+
+
+    fn start_statement_class_field(&mut self, data : ParserData) -> (String, data::Node, Option<data::Node>) {
+
+        self.start_node(data::SyntaxType::ClassField);
         let name = if (let data::TokenType::Identifier(name) = self.token.token.clone()) {
             name
         } else {
             exception::ParserException::new(
                 exception::ParserExceptionType::MissingToken,
-                String::from("Expected Identifier not found."),
+                String::from("Expected Identifier, `#`, `func`, `}` not found."),
                 self.token.range.clone()
             ).dump_error();
         };
         self.advance();
 
-        if (! matches!(self.token.token, data::TokenType::RBracket)) {
+        if (! matches!(self.token.token, data::TokenType::Colon)) {
             exception::ParserException::new(
                 exception::ParserExceptionType::MissingToken,
-                String::from("Expected `]` not found."),
+                String::from("Expected `:` not found."),
                 self.token.range.clone()
             ).dump_error();
         }
         self.advance();
+        let typ = self.start_type(data.clone());
 
-        return name;
-
-    }
+        let mut default = None;
+        if (matches!(self.token.token, data::TokenType::Equals)) {
+            self.advance();
+            let mut new_data = data.clone();
+            new_data.allow_assign  = false;
+            new_data.allow_greater = true;
+            default = Some(self.start_expression(new_data));
+        }
 
+        if (! matches!(self.token.token, data::TokenType::Eol)) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                format!("Expected {}`;` not found.", if (default.is_none()) {"`=`, "} else {""}),
+                self.token.range.clone()
+            ).dump_error();
+        }
+        self.advance();
+        self.finish_node();
 
+        return (name, typ, default);
 
-    fn start_statement_function(&mut self, data : ParserData) -> data::Node {
+    }
 
-        if (let data::TokenType::Identifier(keyword) = self.token.token.clone()) {
-            let start = self.token.range.min.clone();
 
-            if (keyword == String::from("func")) {
 
-                self.advance();
+    fn start_statement_trait(&mut self, data : ParserData) -> data::Node {
 
-                let name = if (let data::TokenType::Identifier(name) = self.token.token.clone()) {
-                    name
-                } else {
-                    exception::ParserException::new(
-                        exception::ParserExceptionType::MissingToken,
-                        String::from("Expected Identifier not found."),
-                        self.token.range.clone()
-                    ).dump_error();
-                };
-                self.advance();
-                if (! matches!(self.token.token, data::TokenType::LParenthesis)) {
-                    exception::ParserException::new(
-                        exception::ParserExceptionType::MissingToken,
-                        String::from("Expected `(` not found."),
-                        self.token.range.clone()
-                    ).dump_error();
-                }
-                self.advance();
+        let start = self.token.range.min.clone();
+        // On `trait`.
+        self.advance();
 
-                let mut args = Vec::new();
-                if (! matches!(self.token.token, data::TokenType::RParenthesis)) {
-                    args.push(self.start_statement_function_argument(data.clone()));
-                    while (matches!(self.token.token, data::TokenType::Comma)) {
-                        self.advance();
-                        args.push(self.start_statement_function_argument(data.clone()));
-                    }
-                }
+        let name = if (let data::TokenType::Identifier(name) = self.token.token.clone()) {
+            name
+        } else {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected Identifier not found."),
+                self.token.range.clone()
+            ).dump_error();
+        };
+        self.advance();
 
-                if (! matches!(self.token.token, data::TokenType::RParenthesis)) {
-                    exception::ParserException::new(
-                        exception::ParserExceptionType::MissingToken,
-                        String::from("Expected `,`, `)` not found."),
-                        self.token.range.clone()
-                    ).dump_error();
-                }
-                self.advance();
+        if (! matches!(self.token.token, data::TokenType::LBrace)) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected `{` not found."),
+                self.token.range.clone()
+            ).dump_error();
+        }
+        self.advance();
 
-                if (! matches!(self.token.token, data::TokenType::Colon)) {
-                    exception::ParserException::new(
-                        exception::ParserExceptionType::MissingToken,
-                        String::from("Expected `:` not found."),
-                        self.token.range.clone()
-                    ).dump_error();
-                }
+        let mut methods = Vec::new();
+        while (! matches!(self.token.token, data::TokenType::RBrace | data::TokenType::Eof)) {
+            if (matches!(self.token.token, data::TokenType::Eol)) {
                 self.advance();
+                continue;
+            }
+            if (! matches!(self.token.token.clone(), data::TokenType::Identifier(keyword) if keyword == "func")) {
+                exception::ParserException::new(
+                    exception::ParserExceptionType::MissingToken,
+                    String::from("Expected `func`, `}` not found."),
+                    self.token.range.clone()
+                ).dump_error();
+            }
+            self.start_node(data::SyntaxType::DefineFunction);
+            methods.push(self.start_statement_function(data.clone(), false));
+            self.finish_node();
+        }
 
-                let return_type = self.start_type(data.clone());
+        let end = self.token.range.max.clone();
+        if (! matches!(self.token.token, data::TokenType::RBrace)) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected `}` not found."),
+                self.token.range.clone()
+            ).dump_error();
+        }
+        self.advance();
 
-                if (! matches!(self.token.token, data::TokenType::LBrace)) {
-                    exception::ParserException::new(
-                        exception::ParserExceptionType::MissingToken,
-                        String::from("Expected `{` not found."),
-                        self.token.range.clone()
-                    ).dump_error();
-                }
-                self.advance();
+        return data::Node::new(
+            data::NodeType::DefineTrait(name, Box::new(methods)),
+            data::Range::new(start, end)
+        );
 
-                let mut content = Vec::new();
+    }
 
-                while (! matches!(self.token.token, data::TokenType::RBrace)) {
-                    content.push(self.start_expression_base(data.clone()));
-                }
 
-                let end = self.token.range.max.clone();
-                if (! matches!(self.token.token, data::TokenType::RBrace)) {
-                    exception::ParserException::new(
-                        exception::ParserExceptionType::MissingToken,
-                        String::from("Expected `}` not found."),
-                        self.token.range.clone()
-                    ).dump_error();
-                }
-                self.advance();
+    fn start_statement_impl(&mut self, data : ParserData) -> data::Node {
 
-                return data::Node::new(
-                    data::NodeType::DefineFunction(
-                        name, Box::new(args), Box::new(return_type), Box::new(content)
-                    ),
-                    data::Range::new(start, end)
-                );
+        let start = self.token.range.min.clone();
+        // On `impl`.
+        self.advance();
 
-            }
+        let name = if (let data::TokenType::Identifier(name) = self.token.token.clone()) {
+            name
+        } else {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected Identifier not found."),
+                self.token.range.clone()
+            ).dump_error();
+        };
+        self.advance();
 
+        if (! matches!(self.token.token.clone(), data::TokenType::Identifier(keyword) if keyword == "for")) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected `for` not found."),
+                self.token.range.clone()
+            ).dump_error();
         }
+        self.advance();
 
-        exception::ParserException::new(
-            exception::ParserExceptionType::MissingToken,
-            String::from("Expected `func` not found."),
-            self.token.range.clone()
-        ).dump_error();
+        let class = if (let data::TokenType::Identifier(class) = self.token.token.clone()) {
+            class
+        } else {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected Identifier not found."),
+                self.token.range.clone()
+            ).dump_error();
+        };
+        self.advance();
 
-    }
+        if (! matches!(self.token.token, data::TokenType::LBrace)) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected `{` not found."),
+                self.token.range.clone()
+            ).dump_error();
+        }
+        self.advance();
 
+        let mut methods = Vec::new();
+        while (! matches!(self.token.token, data::TokenType::RBrace | data::TokenType::Eof)) {
+            if (matches!(self.token.token, data::TokenType::Eol)) {
+                self.advance();
+                continue;
+            }
+            let     checkpoint  = self.checkpoint();
+            let mut header_list = Vec::new();
+            while (matches!(self.token.token, data::TokenType::Hash)) {
+                header_list.push(self.start_statement_header());
+            }
+            self.start_node_at(checkpoint, data::SyntaxType::DefineFunction);
+            let mut method = self.start_statement_function(data.clone(), true);
+            self.finish_node();
+            method.headers = data::NodeHeaders::from(header_list, data::AttributeTarget::Method);
+            if (method.headers.is_enabled()) {
+                methods.push(method);
+            }
+        }
 
-    fn start_statement_function_argument(&mut self, data : ParserData) -> (String, data::Node) {
+        let end = self.token.range.max.clone();
+        if (! matches!(self.token.token, data::TokenType::RBrace)) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected `}` not found."),
+                self.token.range.clone()
+            ).dump_error();
+        }
+        self.advance();
+
+        return data::Node::new(
+            data::NodeType::ImplementTrait(name, class, Box::new(methods)),
+            data::Range::new(start, end)
+        );
+
+    }
+
+
+
+    fn start_statement_enum(&mut self, data : ParserData) -> data::Node {
+
+        let start = self.token.range.min.clone();
+        // On `enum`.
+        self.advance();
+
+        let name = if (let data::TokenType::Identifier(name) = self.token.token.clone()) {
+            name
+        } else {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected Identifier not found."),
+                self.token.range.clone()
+            ).dump_error();
+        };
+        self.advance();
+
+        if (! matches!(self.token.token, data::TokenType::LBrace)) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected `{` not found."),
+                self.token.range.clone()
+            ).dump_error();
+        }
+        self.advance();
+
+        let mut variants = Vec::new();
+        if (! matches!(self.token.token, data::TokenType::RBrace)) {
+            variants.push(self.start_statement_enum_variant(data.clone()));
+            while (matches!(self.token.token, data::TokenType::Comma)) {
+                self.advance();
+                if (matches!(self.token.token, data::TokenType::RBrace)) {
+                    break;
+                }
+                variants.push(self.start_statement_enum_variant(data.clone()));
+            }
+        }
+
+        let end = self.token.range.max.clone();
+        if (! matches!(self.token.token, data::TokenType::RBrace)) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected `,`, `}` not found."),
+                self.token.range.clone()
+            ).dump_error();
+        }
+        self.advance();
+
+        return data::Node::new(
+            data::NodeType::DefineEnum(name, Box::new(variants)),
+            data::Range::new(start, end)
+        );
+
+    }
+
+
+    fn start_statement_enum_variant(&mut self, data : ParserData) -> (String, Vec<(Option<String>, data::Node)>) {
+
+        self.start_node(data::SyntaxType::EnumVariant);
+        let name = if (let data::TokenType::Identifier(name) = self.token.token.clone()) {
+            name
+        } else {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected Identifier not found."),
+                self.token.range.clone()
+            ).dump_error();
+        };
+        self.advance();
+
+        let mut payload = Vec::new();
+        if (matches!(self.token.token, data::TokenType::LParenthesis)) {
+            self.advance();
+            if (! matches!(self.token.token, data::TokenType::RParenthesis)) {
+                payload.push((None, self.start_type(data.clone())));
+                while (matches!(self.token.token, data::TokenType::Comma)) {
+                    self.advance();
+                    payload.push((None, self.start_type(data.clone())));
+                }
+            }
+            if (! matches!(self.token.token, data::TokenType::RParenthesis)) {
+                exception::ParserException::new(
+                    exception::ParserExceptionType::MissingToken,
+                    String::from("Expected `,`, `)` not found."),
+                    self.token.range.clone()
+                ).dump_error();
+            }
+            self.advance();
+        }
+
+        else if (matches!(self.token.token, data::TokenType::LBrace)) {
+            loop {
+                self.advance();
+                let field = if (let data::TokenType::Identifier(field) = self.token.token.clone()) {
+                    field
+                } else {
+                    exception::ParserException::new(
+                        exception::ParserExceptionType::MissingToken,
+                        String::from("Expected Identifier not found."),
+                        self.token.range.clone()
+                    ).dump_error();
+                };
+                self.advance();
+                if (! matches!(self.token.token, data::TokenType::Colon)) {
+                    exception::ParserException::new(
+                        exception::ParserExceptionType::MissingToken,
+                        String::from("Expected `:` not found."),
+                        self.token.range.clone()
+                    ).dump_error();
+                }
+                self.advance();
+                payload.push((Some(field), self.start_type(data.clone())));
+                if (! matches!(self.token.token, data::TokenType::Comma)) {
+                    break;
+                }
+            }
+            if (! matches!(self.token.token, data::TokenType::RBrace)) {
+                exception::ParserException::new(
+                    exception::ParserExceptionType::MissingToken,
+                    String::from("Expected `,`, `}` not found."),
+                    self.token.range.clone()
+                ).dump_error();
+            }
+            self.advance();
+        }
+        self.finish_node();
+
+        return (name, payload);
+
+    }
+
+
+
+    fn start_statement_header(&mut self) -> data::Attribute {
+
+        self.start_node(data::SyntaxType::Header);
+        let start = self.token.range.min.clone();
+        if (! matches!(self.token.token, data::TokenType::Hash)) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected `#` not found."),
+                self.token.range.clone()
+            ).dump_error();
+        }
+        self.advance();
+
+        if (! matches!(self.token.token, data::TokenType::LBracket)) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected `[` not found."),
+                self.token.range.clone()
+            ).dump_error();
+        }
+        self.advance();
+
+        let name = if (let data::TokenType::Identifier(name) = self.token.token.clone()) {
+            name
+        } else {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected Identifier not found."),
+                self.token.range.clone()
+            ).dump_error();
+        };
+        self.advance();
+
+        let arguments = if (matches!(self.token.token, data::TokenType::LParenthesis)) {
+            data::AttributeArguments::List(self.start_header_arguments().0)
+        } else if (matches!(self.token.token, data::TokenType::Equals)) {
+            self.advance();
+            let range = self.token.range.clone();
+            data::AttributeArguments::Value(self.start_header_literal(), range)
+        } else {
+            data::AttributeArguments::None
+        };
+
+        let end = self.token.range.max.clone();
+        if (! matches!(self.token.token, data::TokenType::RBracket)) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected `(`, `=`, `]` not found."),
+                self.token.range.clone()
+            ).dump_error();
+        }
+        self.advance();
+        self.finish_node();
+
+        return data::Attribute {
+            name,
+            arguments,
+            range     : data::Range::new(start, end)
+        };
+
+    }
+
+
+
+    fn start_header_arguments(&mut self) -> (Vec<data::AttributeArgument>, data::Position) {
+
+        if (! matches!(self.token.token, data::TokenType::LParenthesis)) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected `(` not found."),
+                self.token.range.clone()
+            ).dump_error();
+        }
+        self.advance();
+
+        let mut arguments = Vec::new();
+        if (! matches!(self.token.token, data::TokenType::RParenthesis)) {
+            arguments.push(self.start_header_argument());
+            while (matches!(self.token.token, data::TokenType::Comma)) {
+                self.advance();
+                arguments.push(self.start_header_argument());
+            }
+        }
+
+        let end = self.token.range.max.clone();
+        if (! matches!(self.token.token, data::TokenType::RParenthesis)) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected `,`, `)` not found."),
+                self.token.range.clone()
+            ).dump_error();
+        }
+        self.advance();
+
+        return (arguments, end);
+
+    }
+
+
+
+    // `literal`, `name = literal` or `name(args)`.
+    fn start_header_argument(&mut self) -> data::AttributeArgument {
+
+        let start = self.token.range.min.clone();
+        if (let data::TokenType::Identifier(name) = self.token.token.clone()
+            && ! ["true", "false"].contains(&name.as_str())
+        ) {
+            let range = self.token.range.clone();
+            self.advance();
+            if (matches!(self.token.token, data::TokenType::LParenthesis)) {
+                let (arguments, end) = self.start_header_arguments();
+                return data::AttributeArgument::List(name, arguments, data::Range::new(start, end));
+            }
+            if (matches!(self.token.token, data::TokenType::Equals)) {
+                self.advance();
+                let end   = self.token.range.max.clone();
+                let value = self.start_header_literal();
+                return data::AttributeArgument::NameValue(name, value, data::Range::new(start, end));
+            }
+            return data::AttributeArgument::Literal(data::Literal::Name(name), range);
+        }
+
+        let range = self.token.range.clone();
+        return data::AttributeArgument::Literal(self.start_header_literal(), range);
+
+    }
+
+
+
+    fn start_header_literal(&mut self) -> data::Literal {
+        let literal = match (self.token.token.clone()) {
+            data::TokenType::Identifier(name) if name == "true"  => data::Literal::Bool(true),
+            data::TokenType::Identifier(name) if name == "false" => data::Literal::Bool(false),
+            data::TokenType::Character(ch)                       => data::Literal::Character(ch),
+            data::TokenType::String(text)                        => data::Literal::String(text),
+            data::TokenType::Integer(value)                      => data::Literal::Integer(value),
+            data::TokenType::Float(value)                        => data::Literal::Float(value),
+            _                                                    => {
+                exception::ParserException::new(
+                    exception::ParserExceptionType::MissingToken,
+                    String::from("Expected Bool, Character, String, Integer, Float not found."),
+                    self.token.range.clone()
+                ).dump_error();
+            }
+        };
+        self.advance();
+        return literal;
+    }
+
+
+
+    // A function without a body ends with `;`, like the methods of a trait.
+    fn start_statement_function(&mut self, data : ParserData, has_body : bool) -> data::Node {
+
+        if (let data::TokenType::Identifier(keyword) = self.token.token.clone()) {
+            let start = self.token.range.min.clone();
+
+            if (keyword == String::from("func")) {
+
+                self.advance();
+
+                let name = if (let data::TokenType::Identifier(name) = self.token.token.clone()) {
+                    name
+                } else {
+                    exception::ParserException::new(
+                        exception::ParserExceptionType::MissingToken,
+                        String::from("Expected Identifier not found."),
+                        self.token.range.clone()
+                    ).dump_error();
+                };
+                self.advance();
+                let generics = self.start_statement_generics();
+                if (! matches!(self.token.token, data::TokenType::LParenthesis)) {
+                    exception::ParserException::new(
+                        exception::ParserExceptionType::MissingToken,
+                        format!("Expected {}`(` not found.", if (generics.len() >= 1) {""} else {"`<`, "}),
+                        self.token.range.clone()
+                    ).dump_error();
+                }
+                self.advance();
+
+                let args = self.start_statement_function_arguments(data.clone());
+
+                if (! matches!(self.token.token, data::TokenType::Colon)) {
+                    exception::ParserException::new(
+                        exception::ParserExceptionType::MissingToken,
+                        String::from("Expected `:` not found."),
+                        self.token.range.clone()
+                    ).dump_error();
+                }
+                self.advance();
+
+                let return_type = self.start_type(data.clone());
+
+                let (content, end) = if (has_body) {
+                    self.start_block(data.clone())
+                } else {
+                    if (! matches!(self.token.token, data::TokenType::Eol)) {
+                        exception::ParserException::new(
+                            exception::ParserExceptionType::MissingToken,
+                            String::from("Expected `;` not found."),
+                            self.token.range.clone()
+                        ).dump_error();
+                    }
+                    self.advance();
+                    (Vec::new(), return_type.range.max.clone())
+                };
+
+                return data::Node::new(
+                    data::NodeType::DefineFunction(
+                        name, generics, Box::new(args), Box::new(return_type), Box::new(content)
+                    ),
+                    data::Range::new(start, end)
+                );
+
+            }
+
+        }
+
+        exception::ParserException::new(
+            exception::ParserExceptionType::MissingToken,
+            String::from("Expected `func` not found."),
+            self.token.range.clone()
+        ).dump_error();
+
+    }
+
+
+    // The type parameters of a function or class, like `<T, U : Add + Display>`.
+    fn start_statement_generics(&mut self) -> Vec<(String, Vec<String>)> {
+
+        let mut generics = Vec::new();
+        if (! matches!(self.token.token, data::TokenType::LCarat)) {
+            return generics;
+        }
+        self.start_node(data::SyntaxType::Generics);
+        loop {
+            self.advance();
+            let name = if (let data::TokenType::Identifier(name) = self.token.token.clone()) {
+                name
+            } else {
+                exception::ParserException::new(
+                    exception::ParserExceptionType::MissingToken,
+                    String::from("Expected Identifier not found."),
+                    self.token.range.clone()
+                ).dump_error();
+            };
+            self.advance();
+            let mut bounds = Vec::new();
+            if (matches!(self.token.token, data::TokenType::Colon)) {
+                loop {
+                    self.advance();
+                    if (let data::TokenType::Identifier(bound) = self.token.token.clone()) {
+                        bounds.push(bound);
+                    } else {
+                        exception::ParserException::new(
+                            exception::ParserExceptionType::MissingToken,
+                            String::from("Expected Identifier not found."),
+                            self.token.range.clone()
+                        ).dump_error();
+                    }
+                    self.advance();
+                    if (! matches!(self.token.token, data::TokenType::Plus)) {
+                        break;
+                    }
+                }
+            }
+            generics.push((name, bounds));
+            if (! matches!(self.token.token, data::TokenType::Comma)) {
+                break;
+            }
+        }
+
+        self.split_rcarat();
+        if (! matches!(self.token.token, data::TokenType::RCarat)) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                format!("Expected {}`,`, `>` not found.", if (generics.last().map(|(_, bounds)| bounds.len() >= 1).unwrap_or(false)) {"`+`, "} else {"`:`, "}),
+                self.token.range.clone()
+            ).dump_error();
+        }
+        self.advance();
+        self.finish_node();
+
+        return generics;
+
+    }
+
+
+    // The arguments after the opening `(`, up to and including the `)`.
+    fn start_statement_function_arguments(&mut self, data : ParserData) -> Vec<(String, data::Node)> {
+
+        let mut args = Vec::new();
+        if (! matches!(self.token.token, data::TokenType::RParenthesis)) {
+            args.push(self.start_statement_function_argument(data.clone()));
+            while (matches!(self.token.token, data::TokenType::Comma)) {
+                self.advance();
+                args.push(self.start_statement_function_argument(data.clone()));
+            }
+        }
+
+        if (! matches!(self.token.token, data::TokenType::RParenthesis)) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected `,`, `)` not found."),
+                self.token.range.clone()
+            ).dump_error();
+        }
+        self.advance();
+
+        return args;
+
+    }
+
+
+
+    fn start_statement_function_argument(&mut self, data : ParserData) -> (String, data::Node) {
+
+        if (let data::TokenType::Identifier(name) = self.token.token.clone()) {
+            self.start_node(data::SyntaxType::FunctionArgument);
+            let start = self.token.range.min.clone();
+            
+            self.advance();
+            if (! matches!(self.token.token, data::TokenType::Colon)) {
+                exception::ParserException::new(
+                    exception::ParserExceptionType::MissingToken,
+                    String::from("Expected `:` not found."),
+                    self.token.range.clone()
+                ).dump_error();
+            }
+            self.advance();
+
+            let typ = self.start_type(data);
+            self.finish_node();
+
+            return (name, data::Node::new(
+                typ.node.clone(),
+                data::Range::new(start, typ.range.max)
+            ));
+            
+        }
+        
+        exception::ParserException::new(
+            exception::ParserExceptionType::MissingToken,
+            String::from("Expected Identifier not found."),
+            self.token.range.clone()
+        ).dump_error();
+
+    }
+
+
+
+
+
+    fn start_block(&mut self, data : ParserData) -> (Vec<data::Node>, data::Position) {
+
+        self.start_node(data::SyntaxType::Block);
+        if (! matches!(self.token.token, data::TokenType::LBrace)) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected `{` not found."),
+                self.token.range.clone()
+            ).dump_error();
+        }
+        self.advance();
+
+        let mut content = Vec::new();
+
+        while (! matches!(self.token.token, data::TokenType::RBrace | data::TokenType::Eof)) {
+            if (matches!(self.token.token, data::TokenType::Eol)) {
+                self.advance();
+                continue;
+            }
+            content.push(self.start_expression_base(data.clone()));
+        }
+
+        let end = self.token.range.max.clone();
+        if (! matches!(self.token.token, data::TokenType::RBrace)) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected `}` not found."),
+                self.token.range.clone()
+            ).dump_error();
+        }
+        self.advance();
+        self.finish_node();
+
+        return (content, end);
+
+    }
+
+
+
+    fn start_expression_base(&mut self, data : ParserData) -> data::Node {
+
+        if (let data::TokenType::Identifier(keyword) = self.token.token.clone()) {
+
+            if (keyword == String::from("let")) {
+                let mut new_data = data.clone();
+                new_data.allow_mutable = true;
+                return self.start_initialize_variable(new_data);
+            }
+
+            else if (keyword == String::from("if")) {
+                return self.start_control_if(data);
+            }
+
+            else if (LOOP_KEYWORDS.contains(&keyword.as_str())) {
+                return self.start_control_loop(data, None);
+            }
+
+            else if (self.is_loop_label()) {
+                let start = self.token.range.clone();
+                self.start_node(data::SyntaxType::Label);
+                self.advance();
+                self.advance();
+                self.finish_node();
+                return self.start_control_loop(data, Some((keyword, start)));
+            }
+
+            else if (keyword == String::from("break") || keyword == String::from("continue")) {
+                return self.start_control_jump(data);
+            }
+
+            else if (keyword == String::from("return")) {
+                return self.start_control_return(data);
+            }
+
+            else if (keyword == String::from("match")) {
+                return self.start_control_match(data);
+            }
+
+        }
+
+        self.start_node(data::SyntaxType::ExpressionStatement);
+        let mut new_data = data.clone();
+        new_data.allow_assign = true;
+        let node = self.start_expression(new_data);
+
+        // The final expression of a block, without `;`, is its result. So is
+        // the one at the end of `calculate_statements`.
+        if (matches!(self.token.token, data::TokenType::RBrace | data::TokenType::Eof)) {
+            self.finish_node();
+            let range = node.range.clone();
+            return data::Node::new(
+                data::NodeType::BlockResult(Box::new(node)),
+                range
+            );
+        }
+
+        if (! matches!(self.token.token, data::TokenType::Eol)) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected `;`, `}` not found."),
+                self.token.range.clone()
+            ).dump_error();
+        }
+        self.advance();
+        self.finish_node();
+
+        return node;
+
+    }
+
+
+
+    // `name: while`, `name: loop` or `name: for`.
+    fn is_loop_label(&self) -> bool {
+        return matches!(self.tokens.get(self.index + 1).map(|token| token.token.clone()), Some(data::TokenType::Colon))
+            && matches!(self.tokens.get(self.index + 2).map(|token| token.token.clone()), Some(data::TokenType::Identifier(keyword)) if LOOP_KEYWORDS.contains(&keyword.as_str()));
+    }
+
+
+
+    fn start_control_if(&mut self, data : ParserData) -> data::Node {
+
+        self.start_node(data::SyntaxType::If);
+        let     start    = self.token.range.min.clone();
+        let mut branches = Vec::new();
+        let mut fallback = None;
+        let mut end;
+        let mut new_data = data.clone();
+        new_data.allow_assign  = false;
+        new_data.allow_greater = true;
+
+        loop {
+            // On `if`.
+            self.advance();
+            let condition     = self.start_expression(new_data.clone());
+            let (body, close) = self.start_block(data.clone());
+            branches.push((condition, body));
+            end = close;
+
+            if (! matches!(self.token.token.clone(), data::TokenType::Identifier(keyword) if keyword == "else")) {
+                break;
+            }
+            self.advance();
+
+            if (matches!(self.token.token.clone(), data::TokenType::Identifier(keyword) if keyword == "if")) {
+                continue;
+            }
+            let (body, close) = self.start_block(data.clone());
+            fallback = Some(body);
+            end      = close;
+            break;
+        }
+
+        self.finish_node();
+        return data::Node::new(
+            data::NodeType::If(Box::new(branches), Box::new(fallback)),
+            data::Range::new(start, end)
+        );
+
+    }
+
+
+
+    fn start_control_loop(&mut self, data : ParserData, label : Option<(String, data::Range)>) -> data::Node {
+
+        let checkpoint = self.checkpoint();
+        let start      = if (let Some((_, range)) = &label) {range.min.clone()} else {self.token.range.min.clone()};
+        let label      = label.map(|(name, _)| name);
+        let keyword    = if (let data::TokenType::Identifier(keyword) = self.token.token.clone()) {keyword} else {String::new()};
+        let mut new_data = data.clone();
+        new_data.allow_assign  = false;
+        new_data.allow_greater = true;
+        self.advance();
+
+        let (node, syntax, end) = match (keyword.as_str()) {
+
+            "while" => {
+                let condition   = self.start_expression(new_data);
+                let (body, end) = self.start_block(data);
+                (data::NodeType::While(label, Box::new(condition), Box::new(body)), data::SyntaxType::While, end)
+            },
+
+            "loop" => {
+                let (body, end) = self.start_block(data);
+                (data::NodeType::Loop(label, Box::new(body)), data::SyntaxType::Loop, end)
+            },
+
+            "for" => {
+                let name = if (let data::TokenType::Identifier(name) = self.token.token.clone()) {
+                    name
+                } else {
+                    exception::ParserException::new(
+                        exception::ParserExceptionType::MissingToken,
+                        String::from("Expected Identifier not found."),
+                        self.token.range.clone()
+                    ).dump_error();
+                };
+                self.advance();
+                if (! matches!(self.token.token.clone(), data::TokenType::Identifier(keyword) if keyword == "in")) {
+                    exception::ParserException::new(
+                        exception::ParserExceptionType::MissingToken,
+                        String::from("Expected `in` not found."),
+                        self.token.range.clone()
+                    ).dump_error();
+                }
+                self.advance();
+                let iterable    = self.start_expression(new_data);
+                let (body, end) = self.start_block(data);
+                (data::NodeType::For(label, name, Box::new(iterable), Box::new(body)), data::SyntaxType::For, end)
+            },
+
+            _ => {
+                exception::ParserException::new(
+                    exception::ParserExceptionType::MissingToken,
+                    String::from("Expected `while`, `loop`, `for` not found."),
+                    self.token.range.clone()
+                ).dump_error();
+            }
+
+        };
+
+        self.start_node_at(checkpoint, syntax);
+        self.finish_node();
+        return data::Node::new(
+            node,
+            data::Range::new(start, end)
+        );
+
+    }
+
+
+
+    fn start_control_jump(&mut self, _data : ParserData) -> data::Node {
+
+        self.start_node(data::SyntaxType::Jump);
+        let     start   = self.token.range.min.clone();
+        let mut end     = self.token.range.max.clone();
+        let     is_break = matches!(self.token.token.clone(), data::TokenType::Identifier(keyword) if keyword == "break");
+        self.advance();
+
+        let mut label = None;
+        if (let data::TokenType::Identifier(name) = self.token.token.clone()) {
+            label = Some(name);
+            end   = self.token.range.max.clone();
+            self.advance();
+        }
+
+        if (! matches!(self.token.token, data::TokenType::Eol)) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                format!("Expected {}`;` not found.", if (label.is_none()) {"label, "} else {""}),
+                self.token.range.clone()
+            ).dump_error();
+        }
+        self.advance();
+        self.finish_node();
+
+        return data::Node::new(
+            if (is_break) {data::NodeType::Break(label)} else {data::NodeType::Continue(label)},
+            data::Range::new(start, end)
+        );
+
+    }
+
+
+
+    fn start_control_return(&mut self, data : ParserData) -> data::Node {
+
+        self.start_node(data::SyntaxType::Return);
+        let     start = self.token.range.min.clone();
+        let mut end   = self.token.range.max.clone();
+        self.advance();
+
+        let mut value = None;
+        if (! matches!(self.token.token, data::TokenType::Eol)) {
+            let mut new_data = data.clone();
+            new_data.allow_assign  = false;
+            new_data.allow_greater = true;
+            let node = self.start_expression(new_data);
+            end   = node.range.max.clone();
+            value = Some(node);
+        }
+
+        if (! matches!(self.token.token, data::TokenType::Eol)) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected `;` not found."),
+                self.token.range.clone()
+            ).dump_error();
+        }
+        self.advance();
+        self.finish_node();
+
+        return data::Node::new(
+            data::NodeType::Return(Box::new(value)),
+            data::Range::new(start, end)
+        );
+
+    }
+
+
+
+    fn start_control_match(&mut self, data : ParserData) -> data::Node {
+
+        self.start_node(data::SyntaxType::Match);
+        let start = self.token.range.min.clone();
+        let mut new_data = data.clone();
+        new_data.allow_assign  = false;
+        new_data.allow_greater = true;
+        // On `match`.
+        self.advance();
+
+        let value = self.start_expression(new_data.clone());
+        if (! matches!(self.token.token, data::TokenType::LBrace)) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected `{` not found."),
+                self.token.range.clone()
+            ).dump_error();
+        }
+        self.advance();
+
+        let mut arms = Vec::new();
+        while (! matches!(self.token.token, data::TokenType::RBrace | data::TokenType::Eof)) {
+            self.start_node(data::SyntaxType::MatchArm);
+            let pattern = self.start_pattern();
+            if (! matches!(self.token.token, data::TokenType::EqualsRCarat)) {
+                exception::ParserException::new(
+                    exception::ParserExceptionType::MissingToken,
+                    String::from("Expected `=>` not found."),
+                    self.token.range.clone()
+                ).dump_error();
+            }
+            self.advance();
+
+            // A block arm may leave out the `,`, an expression arm may not.
+            if (matches!(self.token.token, data::TokenType::LBrace)) {
+                let (body, _) = self.start_block(data.clone());
+                arms.push((pattern, body));
+                if (matches!(self.token.token, data::TokenType::Comma)) {
+                    self.advance();
+                }
+            } else {
+                let value = self.start_expression(new_data.clone());
+                let range = value.range.clone();
+                arms.push((pattern, vec![data::Node::new(data::NodeType::BlockResult(Box::new(value)), range)]));
+                if (matches!(self.token.token, data::TokenType::Comma)) {
+                    self.advance();
+                } else if (! matches!(self.token.token, data::TokenType::RBrace)) {
+                    exception::ParserException::new(
+                        exception::ParserExceptionType::MissingToken,
+                        String::from("Expected `,`, `}` not found."),
+                        self.token.range.clone()
+                    ).dump_error();
+                }
+            }
+            self.finish_node();
+        }
+
+        let end = self.token.range.max.clone();
+        if (! matches!(self.token.token, data::TokenType::RBrace)) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected `}` not found."),
+                self.token.range.clone()
+            ).dump_error();
+        }
+        self.advance();
+        self.finish_node();
+
+        return data::Node::new(
+            data::NodeType::Match(Box::new(value), Box::new(arms)),
+            data::Range::new(start, end)
+        );
+
+    }
+
+
+
+    fn start_pattern(&mut self) -> data::Node {
+
+        self.start_node(data::SyntaxType::Pattern);
+        let     start = self.token.range.min.clone();
+        let mut end   = self.token.range.max.clone();
+
+        let pattern = match (self.token.token.clone()) {
+
+            data::TokenType::Identifier(name) if name == "_" => {
+                self.advance();
+                data::Pattern::Wildcard
+            },
+
+            data::TokenType::Identifier(name) if name == "true" || name == "false" => {
+                self.advance();
+                data::Pattern::Literal(data::Literal::Bool(name == "true"))
+            },
+
+            data::TokenType::Identifier(name) => {
+                self.advance();
+                if (! matches!(self.token.token, data::TokenType::DoubleColon)) {
+                    data::Pattern::Binding(name)
+                } else {
+                    self.advance();
+                    let variant = if (let data::TokenType::Identifier(variant) = self.token.token.clone()) {
+                        variant
+                    } else {
+                        exception::ParserException::new(
+                            exception::ParserExceptionType::MissingToken,
+                            String::from("Expected Identifier not found."),
+                            self.token.range.clone()
+                        ).dump_error();
+                    };
+                    end = self.token.range.max.clone();
+                    self.advance();
+                    // `util::Color::Red` names an enum of another module.
+                    let mut name    = name;
+                    let mut variant = variant;
+                    while (matches!(self.token.token, data::TokenType::DoubleColon)) {
+                        self.advance();
+                        if (let data::TokenType::Identifier(next) = self.token.token.clone()) {
+                            name    = format!("{}::{}", name, variant);
+                            variant = next;
+                        } else {
+                            exception::ParserException::new(
+                                exception::ParserExceptionType::MissingToken,
+                                String::from("Expected Identifier not found."),
+                                self.token.range.clone()
+                            ).dump_error();
+                        }
+                        end = self.token.range.max.clone();
+                        self.advance();
+                    }
+                    let mut payload = Vec::new();
+                    let mut rest    = false;
+                    if (matches!(self.token.token, data::TokenType::LParenthesis | data::TokenType::LBrace)) {
+                        let named = matches!(self.token.token, data::TokenType::LBrace);
+                        self.advance();
+                        while (! matches!(self.token.token, data::TokenType::RParenthesis | data::TokenType::RBrace)) {
+                            if (matches!(self.token.token, data::TokenType::DoublePeriod)) {
+                                self.advance();
+                                rest = true;
+                                break;
+                            }
+                            if (named) {
+                                let field = if (let data::TokenType::Identifier(field) = self.token.token.clone()) {
+                                    field
+                                } else {
+                                    exception::ParserException::new(
+                                        exception::ParserExceptionType::MissingToken,
+                                        String::from("Expected Identifier, `..` not found."),
+                                        self.token.range.clone()
+                                    ).dump_error();
+                                };
+                                self.advance();
+                                // `{ field }` binds the field to a name of its own.
+                                if (matches!(self.token.token, data::TokenType::Colon)) {
+                                    self.advance();
+                                    payload.push((Some(field), self.start_pattern_inner()));
+                                } else {
+                                    payload.push((Some(field.clone()), data::Pattern::Binding(field)));
+                                }
+                            } else {
+                                payload.push((None, self.start_pattern_inner()));
+                            }
+                            if (! matches!(self.token.token, data::TokenType::Comma)) {
+                                break;
+                            }
+                            self.advance();
+                        }
+                        let close = if (named) {"}"} else {")"};
+                        if (! (if (named) {matches!(self.token.token, data::TokenType::RBrace)} else {matches!(self.token.token, data::TokenType::RParenthesis)})) {
+                            exception::ParserException::new(
+                                exception::ParserExceptionType::MissingToken,
+                                format!("Expected `,`, `{}` not found.", close),
+                                self.token.range.clone()
+                            ).dump_error();
+                        }
+                        end = self.token.range.max.clone();
+                        self.advance();
+                    }
+                    data::Pattern::Variant(name, variant, payload, rest)
+                }
+            },
 
-        if (let data::TokenType::Identifier(name) = self.token.token.clone()) {
-            let start = self.token.range.min.clone();
-            
-            self.advance();
-            if (! matches!(self.token.token, data::TokenType::Colon)) {
-                exception::ParserException::new(
-                    exception::ParserExceptionType::MissingToken,
-                    String::from("Expected `:` not found."),
-                    self.token.range.clone()
-                ).dump_error();
+            _ => {
+                let min = self.start_pattern_literal();
+                end = self.tokens[self.index - 1].range.max.clone();
+                if (matches!(self.token.token, data::TokenType::DoublePeriod)) {
+                    self.advance();
+                    let max = self.start_pattern_literal();
+                    end = self.tokens[self.index - 1].range.max.clone();
+                    data::Pattern::Range(min, max)
+                } else {
+                    data::Pattern::Literal(min)
+                }
             }
-            self.advance();
 
-            let typ = self.start_type(data);
+        };
 
-            return (name, data::Node::new(
-                typ.node.clone(),
-                data::Range::new(start, typ.range.max)
-            ));
-            
-        }
-        
-        exception::ParserException::new(
-            exception::ParserExceptionType::MissingToken,
-            String::from("Expected Identifier not found."),
-            self.token.range.clone()
-        ).dump_error();
+        self.finish_node();
+        return data::Node::new(
+            data::NodeType::Pattern(pattern),
+            data::Range::new(start, end)
+        );
 
     }
 
 
-
-
-
-    fn start_expression_base(&mut self, data : ParserData) -> data::Node {
-
-        if (let data::TokenType::Identifier(keyword) = self.token.token.clone()) {
-            //let start = self.token.range.min.clone();
-
-            if (keyword == String::from("let")) {
-                let mut new_data = data.clone();
-                new_data.allow_mutable = true;
-                return self.start_initialize_variable(new_data);
-            }
-
+    fn start_pattern_inner(&mut self) -> data::Pattern {
+        if (let data::NodeType::Pattern(pattern) = self.start_pattern().node) {
+            return pattern;
         }
+        return data::Pattern::Wildcard;
+    }
 
-        let mut new_data = data.clone();
-        new_data.allow_assign = true;
-        let node = self.start_expression(new_data);
 
-        if (! matches!(self.token.token, data::TokenType::Eol)) {
-            exception::ParserException::new(
-                exception::ParserExceptionType::MissingToken,
-                String::from("Expected `;` not found."),
-                self.token.range.clone()
-            ).dump_error();
+    fn start_pattern_literal(&mut self) -> data::Literal {
+        let negative = matches!(self.token.token, data::TokenType::Minus);
+        if (negative) {
+            self.advance();
         }
+        let literal = match (self.token.token.clone()) {
+            data::TokenType::Integer(value)                => data::Literal::Integer(if (negative) {-value} else {value}),
+            data::TokenType::Float(value)                  => data::Literal::Float(if (negative) {-value} else {value}),
+            data::TokenType::Character(ch) if ! negative   => data::Literal::Character(ch),
+            data::TokenType::String(text) if ! negative    => data::Literal::String(text),
+            _                                              => {
+                exception::ParserException::new(
+                    exception::ParserExceptionType::MissingToken,
+                    String::from(if (negative) {"Expected Integer, Float not found."} else {"Expected pattern not found."}),
+                    self.token.range.clone()
+                ).dump_error();
+            }
+        };
         self.advance();
-
-        return node;
-
+        return literal;
     }
 
 
 
     fn start_expression(&mut self, data : ParserData) -> data::Node {
-        return self.start_expression_addition(data);
+        return self.start_expression_binary(data, 0);
     }
 
 
 
-    fn start_expression_addition(&mut self, data : ParserData) -> data::Node {
+    // Precedence climbing : only operators binding at least as tight as
+    // `min_power` are folded into `left` at this depth.
+    fn start_expression_binary(&mut self, data : ParserData, min_power : usize) -> data::Node {
 
-        let mut left = self.start_expression_multiplication(data.clone());
-        while ([data::TokenType::Plus, data::TokenType::Minus].contains(&self.token.token)) {
-            let operation = self.token.token.clone();
+        let     checkpoint = self.checkpoint();
+        let mut left       = self.start_expression_prefix(data.clone());
+        while (let Some((_, power, associativity, operation)) = BINARY_OPERATORS.iter().find(|(token, _, _, _)| *token == self.token.token).cloned()) {
+            if (power < min_power || (
+                (! data.allow_greater) && [data::TokenType::RCarat, data::TokenType::RCaratEquals, data::TokenType::DoubleRCarat].contains(&self.token.token)
+            )) {
+                break;
+            }
+            self.start_node_at(checkpoint, data::SyntaxType::BinaryOperation);
             self.advance();
-            let right     = self.start_expression_multiplication(data.clone());
+            let mut new_data = data.clone();
+            new_data.allow_assign = false;
+            let right = self.start_expression_binary(new_data, match (associativity) {
+                Associativity::Left  => power + 1,
+                Associativity::Right => power
+            });
+            self.finish_node();
             left = data::Node::new(
-                match (operation) {
-                    data::TokenType::Plus  => data::NodeType::AdditionOperation(Box::new(left.clone()), Box::new(right.clone())),
-                    data::TokenType::Minus => data::NodeType::SubtractionOperation(Box::new(left.clone()), Box::new(right.clone())),
-                    _                      => {
-                        exception::InternalException::new(
-                            String::from("Invalid Addition Operation")
-                        ).dump_critical();
-                    }
-                },
+                operation(Box::new(left.clone()), Box::new(right.clone())),
                 data::Range::new(left.range.min, right.range.max)
             );
         }
@@ -413,52 +1696,24 @@ impl Parser {
 
 
 
-    fn start_expression_multiplication(&mut self, data : ParserData) -> data::Node {
+    fn start_expression_prefix(&mut self, data : ParserData) -> data::Node {
+        let start = self.token.range.min.clone();
 
-        let mut left = self.start_expression_power(data.clone());
-        while ([data::TokenType::Astrisk, data::TokenType::Slash].contains(&self.token.token)) {
-            let operation = self.token.token.clone();
+        if (let Some((_, power, operation)) = PREFIX_OPERATORS.iter().find(|(token, _, _)| *token == self.token.token).cloned()) {
+            self.start_node(data::SyntaxType::UnaryOperation);
             self.advance();
-            let right     = self.start_expression_power(data.clone());
-            left = data::Node::new(
-                match (operation) {
-                    data::TokenType::Astrisk => data::NodeType::MultiplicationOperation(Box::new(left.clone()), Box::new(right.clone())),
-                    data::TokenType::Slash   => data::NodeType::DivisionOperation(Box::new(left.clone()), Box::new(right.clone())),
-                    _                        => {
-                        exception::InternalException::new(
-                            String::from("Invalid Multiplication Operation")
-                        ).dump_critical();
-                    }
-                },
-                data::Range::new(left.range.min, right.range.max)
+            let mut new_data = data.clone();
+            new_data.allow_assign = false;
+            let value = self.start_expression_binary(new_data, power);
+            self.finish_node();
+            return data::Node::new(
+                operation(Box::new(value.clone())),
+                data::Range::new(start, value.range.max)
             );
         }
-        return left;
-
-    }
 
+        return self.start_term(data);
 
-
-    fn start_expression_power(&mut self, data : ParserData) -> data::Node {
-        
-        let mut left = self.start_term(data.clone());
-        while ([data::TokenType::DoubleAstrisk].contains(&self.token.token)) {
-            let operation = self.token.token.clone();
-            self.advance();
-            let right     = self.start_term(data.clone());
-            left = data::Node::new(
-                match (operation) {
-                    data::TokenType::Plus  => data::NodeType::PowerOperation(Box::new(left.clone()), Box::new(right.clone())),
-                    _                      => {
-                        exception::InternalException::new(
-                            String::from("Invalid Power Operation")
-                        ).dump_critical();
-                    }
-                },
-                data::Range::new(left.range.min, right.range.max)
-            );
-        }
-        return left;
     }
 
 
@@ -467,11 +1722,13 @@ impl Parser {
 
     fn start_term(&mut self, data : ParserData) -> data::Node {
     
-        let mut left = self.start_atom(data.clone());
+        let     checkpoint = self.checkpoint();
+        let mut left       = self.start_atom(data.clone());
 
         loop {
 
             if (matches!(self.token.token, data::TokenType::DoubleColon)) {
+                self.start_node_at(checkpoint, data::SyntaxType::ModuleMember);
                 self.advance();
                 if (let data::TokenType::Identifier(name) = self.token.token.clone()) {
                     left = data::Node::new(
@@ -479,6 +1736,7 @@ impl Parser {
                         data::Range::new(left.range.min, self.token.range.max.clone())
                     );
                     self.advance();
+                    self.finish_node();
                 } else {
                     exception::ParserException::new(
                         exception::ParserExceptionType::MissingToken,
@@ -489,6 +1747,7 @@ impl Parser {
             }
 
             else if (matches!(self.token.token, data::TokenType::Period)) {
+                self.start_node_at(checkpoint, data::SyntaxType::ClassMember);
                 self.advance();
                 if (let data::TokenType::Identifier(name) = self.token.token.clone()) {
                     left = data::Node::new(
@@ -496,6 +1755,7 @@ impl Parser {
                         data::Range::new(left.range.min, self.token.range.max.clone())
                     );
                     self.advance();
+                    self.finish_node();
                 } else {
                     exception::ParserException::new(
                         exception::ParserExceptionType::MissingToken,
@@ -506,9 +1766,11 @@ impl Parser {
             }
 
             else if (matches!(self.token.token, data::TokenType::LBracket)) {
+                self.start_node_at(checkpoint, data::SyntaxType::Slice);
                 self.advance();
                 let mut new_data = data.clone();
-                new_data.allow_assign = false;
+                new_data.allow_assign  = false;
+                new_data.allow_greater = true;
                 let right = self.start_expression(new_data);
                 if (! matches!(self.token.token, data::TokenType::RBracket)) {
                     exception::ParserException::new(
@@ -522,12 +1784,15 @@ impl Parser {
                     data::Range::new(left.range.min, self.token.range.max.clone())
                 );
                 self.advance();
+                self.finish_node();
             }
 
             else if (matches!(self.token.token, data::TokenType::LParenthesis)) {
+                self.start_node_at(checkpoint, data::SyntaxType::Call);
                 self.advance();
                 let mut new_data = data.clone();
-                new_data.allow_assign = false;
+                new_data.allow_assign  = false;
+                new_data.allow_greater = true;
                 let mut args = Vec::new();
                 if (! matches!(self.token.token, data::TokenType::RParenthesis)) {
                     args.push(self.start_expression(new_data.clone()));
@@ -545,7 +1810,8 @@ impl Parser {
                 }
                 let end = self.token.range.max.clone();
                 self.advance();
-                return data::Node::new(
+                self.finish_node();
+                left = data::Node::new(
                     data::NodeType::Call(Box::new(left.clone()), Box::new(args)),
                     data::Range::new(left.range.min, end)
                 );
@@ -557,15 +1823,49 @@ impl Parser {
 
         }
 
+        if (data.allow_assign) {
+            return self.start_term_identifier_action(data, checkpoint, left);
+        }
+
         return left;
 
     }
 
 
 
-    /*fn start_term_identifier_action(&mut self, _data : ParserData) -> data::Node {
-        panic!("Term Identifier Action");
-    }*/
+    fn start_term_identifier_action(&mut self, data : ParserData, checkpoint : usize, target : data::Node) -> data::Node {
+
+        let operation = if (matches!(self.token.token, data::TokenType::Equals)) {
+            None
+        } else if (let Some((_, operation)) = ASSIGN_OPERATORS.iter().find(|(token, _)| *token == self.token.token).cloned()) {
+            Some(operation)
+        } else {
+            return target;
+        };
+
+        if (! matches!(target.node, data::NodeType::Literal(data::Literal::Name(_)) | data::NodeType::ClassMember(_, _) | data::NodeType::Slice(_, _))) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::InvalidAssignment,
+                format!("Can not assign to `{}`.", target),
+                target.range.clone()
+            ).dump_error();
+        }
+
+        self.start_node_at(checkpoint, data::SyntaxType::AssignVariable);
+        self.advance();
+        let mut new_data = data.clone();
+        new_data.allow_assign  = false;
+        new_data.allow_greater = true;
+        let value = self.start_expression(new_data);
+        self.finish_node();
+
+        // `a += b` stays its own node, so that `a` is only evaluated once.
+        return data::Node::new(
+            data::NodeType::AssignVariable(Box::new(target.clone()), operation, Box::new(value.clone())),
+            data::Range::new(target.range.min, value.range.max)
+        );
+
+    }
 
 
 
@@ -576,6 +1876,7 @@ impl Parser {
 
             if (keyword == String::from("let")) {
 
+                self.start_node(data::SyntaxType::InitializeVariable);
                 self.advance();
                 let mut mutable = false;
                 let mut name    = None;
@@ -600,7 +1901,7 @@ impl Parser {
 
                 }
 
-                if (matches!(name, None)) {
+                if (name.is_none()) {
 
                     name = if (let data::TokenType::Identifier(name) = self.token.token.clone()) {
                         Some(name)
@@ -651,13 +1952,14 @@ impl Parser {
                     exception::ParserException::new(
                         exception::ParserExceptionType::MissingToken,
                         format!("Expected {}{}`;` not found.",
-                            if (matches!(value, None) && ! type_set) {"`:`, "} else {""},
-                            if (matches!(value, None)) {"`=`, "} else {""}
+                            if (value.is_none() && ! type_set) {"`:`, "} else {""},
+                            if (value.is_none()) {"`=`, "} else {""}
                         ),
                         self.token.range.clone()
                     ).dump_error();
                 }
                 self.advance();
+                self.finish_node();
                 
                 return data::Node::new(
                     data::NodeType::InitializeVariable(mutable, name.unwrap(), Box::new(typ), Box::new(value)),
@@ -685,6 +1987,7 @@ impl Parser {
         let mut end   = self.token.range.max.clone();
 
         if (let data::TokenType::Identifier(name) = self.token.token.clone()) {
+            self.start_node(data::SyntaxType::Type);
             self.advance();
 
             let mut bases = vec![name];
@@ -706,17 +2009,22 @@ impl Parser {
 
             let mut arguments = Vec::new();
 
+            if (matches!(self.token.token, data::TokenType::DoubleLCarat)) {
+                self.split_token(data::TokenType::LCarat, data::TokenType::LCarat);
+            }
             if (matches!(self.token.token, data::TokenType::LCarat)) {
                 self.advance();
 
+                self.split_rcarat();
                 if (! matches!(self.token.token, data::TokenType::RCarat)) {
-                    arguments.push(self.start_type(data.clone()));
+                    arguments.push(self.start_type_argument(data.clone()));
                     while (matches!(self.token.token, data::TokenType::Comma)) {
                         self.advance();
-                        arguments.push(self.start_type(data.clone()));
+                        arguments.push(self.start_type_argument(data.clone()));
                     }
                 }
 
+                self.split_rcarat();
                 if (! matches!(self.token.token, data::TokenType::RCarat)) {
                     exception::ParserException::new(
                         exception::ParserExceptionType::MissingToken,
@@ -727,6 +2035,7 @@ impl Parser {
                 self.advance();
 
             }
+            self.finish_node();
 
             return data::Node::new(
                 data::NodeType::Type(data::Type::Base(bases), arguments),
@@ -745,29 +2054,220 @@ impl Parser {
 
 
 
+    // Bounds and lengths are given as literals, like `RInt<-5, 5>`, and
+    // the arguments of a function type as a list, like `Func<<Int, Int>, Bool>`.
+    fn start_type_argument(&mut self, data : ParserData) -> data::Node {
+        if (matches!(self.token.token, data::TokenType::LCarat | data::TokenType::DoubleLCarat)) {
+            return self.start_type_list(data);
+        }
+        let negative = matches!(self.token.token, data::TokenType::Minus)
+            && matches!(self.tokens.get(self.index + 1).map(|token| token.token.clone()), Some(data::TokenType::Integer(_) | data::TokenType::Float(_)));
+        if (! negative && ! matches!(self.token.token, data::TokenType::Integer(_) | data::TokenType::Float(_))) {
+            return self.start_type(data);
+        }
+        let start = self.token.range.min.clone();
+        self.start_node(data::SyntaxType::Literal);
+        if (negative) {
+            self.advance();
+        }
+        let literal = match (self.token.token.clone()) {
+            data::TokenType::Integer(value) => data::Literal::Integer(if (negative) {- value} else {value}),
+            data::TokenType::Float(value)   => data::Literal::Float(if (negative) {- value} else {value}),
+            _                               => {
+                exception::ParserException::new(
+                    exception::ParserExceptionType::MissingToken,
+                    String::from("Expected Integer, Float not found."),
+                    self.token.range.clone()
+                ).dump_error();
+            }
+        };
+        let end = self.token.range.max.clone();
+        self.advance();
+        self.finish_node();
+        return data::Node::new(
+            data::NodeType::Literal(literal),
+            data::Range::new(start, end)
+        );
+    }
+
+
+
+
+
+    fn start_type_list(&mut self, data : ParserData) -> data::Node {
+        if (matches!(self.token.token, data::TokenType::DoubleLCarat)) {
+            self.split_token(data::TokenType::LCarat, data::TokenType::LCarat);
+        }
+        self.start_node(data::SyntaxType::Type);
+        let start = self.token.range.min.clone();
+        self.advance();
+        let mut types = Vec::new();
+        self.split_rcarat();
+        if (! matches!(self.token.token, data::TokenType::RCarat)) {
+            types.push(self.start_type_argument(data.clone()));
+            while (matches!(self.token.token, data::TokenType::Comma)) {
+                self.advance();
+                types.push(self.start_type_argument(data.clone()));
+            }
+        }
+        self.split_rcarat();
+        if (! matches!(self.token.token, data::TokenType::RCarat)) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                format!("Expected {}, `>` not found.", if (types.len() >= 1) {"`,`"} else {"type"}),
+                self.token.range.clone()
+            ).dump_error();
+        }
+        let end = self.token.range.max.clone();
+        self.advance();
+        self.finish_node();
+        return data::Node::new(
+            data::NodeType::Type(data::Type::Base(Vec::new()), types),
+            data::Range::new(start, end)
+        );
+    }
+
+
+
+    fn split_rcarat(&mut self) -> () {
+        match (self.token.token) {
+            data::TokenType::DoubleRCarat       => self.split_token(data::TokenType::RCarat, data::TokenType::RCarat),
+            data::TokenType::RCaratEquals       => self.split_token(data::TokenType::RCarat, data::TokenType::Equals),
+            data::TokenType::DoubleRCaratEquals => self.split_token(data::TokenType::RCarat, data::TokenType::RCaratEquals),
+            _                                   => {}
+        };
+    }
+
 
 
     fn start_atom(&mut self, data : ParserData) -> data::Node {
         let start = self.token.range.min.clone();
-        
-        if (matches!(self.token.token, data::TokenType::Minus)) {
+
+        let mut new_data = data.clone();
+        new_data.allow_assign  = false;
+        new_data.allow_greater = true;
+
+        if (matches!(self.token.token, data::TokenType::LParenthesis)) {
+            self.start_node(data::SyntaxType::Group);
+            self.advance();
+            let value = self.start_expression(new_data);
+            if (! matches!(self.token.token, data::TokenType::RParenthesis)) {
+                exception::ParserException::new(
+                    exception::ParserExceptionType::MissingToken,
+                    String::from("Expected `)` not found."),
+                    self.token.range.clone()
+                ).dump_error();
+            }
+            let end = self.token.range.max.clone();
             self.advance();
-            let value = self.start_atom(data);
+            self.finish_node();
             return data::Node::new(
-                data::NodeType::OppositeOperation(Box::new(value.clone())),
-                data::Range::new(start, value.range.max)
+                value.node,
+                data::Range::new(start, end)
             );
         }
-        
-        else if (matches!(self.token.token, data::TokenType::Bang)) {
+
+        else if (matches!(self.token.token, data::TokenType::LCarat | data::TokenType::DoubleLCarat)) {
+            if (matches!(self.token.token, data::TokenType::DoubleLCarat)) {
+                self.split_token(data::TokenType::LCarat, data::TokenType::LCarat);
+            }
+            self.start_node(data::SyntaxType::Tuple);
+            self.advance();
+            new_data.allow_greater = false;
+            let mut values = Vec::new();
+            self.split_rcarat();
+            if (! matches!(self.token.token, data::TokenType::RCarat)) {
+                values.push(self.start_expression(new_data.clone()));
+                while (matches!(self.token.token, data::TokenType::Comma)) {
+                    self.advance();
+                    values.push(self.start_expression(new_data.clone()));
+                }
+            }
+            self.split_rcarat();
+            if (! matches!(self.token.token, data::TokenType::RCarat)) {
+                exception::ParserException::new(
+                    exception::ParserExceptionType::MissingToken,
+                    String::from("Expected `,`, `>` not found."),
+                    self.token.range.clone()
+                ).dump_error();
+            }
+            let end = self.token.range.max.clone();
             self.advance();
-            let value = self.start_atom(data);
+            self.finish_node();
             return data::Node::new(
-                data::NodeType::InvertOperation(Box::new(value.clone())),
-                data::Range::new(start, value.range.max)
+                data::NodeType::Tuple(Box::new(values)),
+                data::Range::new(start, end)
+            );
+        }
+
+        else if (matches!(self.token.token, data::TokenType::LBracket)) {
+            self.start_node(data::SyntaxType::Array);
+            self.advance();
+            let mut values = Vec::new();
+            if (! matches!(self.token.token, data::TokenType::RBracket)) {
+                values.push(self.start_expression(new_data.clone()));
+                while (matches!(self.token.token, data::TokenType::Comma)) {
+                    self.advance();
+                    values.push(self.start_expression(new_data.clone()));
+                }
+            }
+            if (! matches!(self.token.token, data::TokenType::RBracket)) {
+                exception::ParserException::new(
+                    exception::ParserExceptionType::MissingToken,
+                    String::from("Expected `,`, `]` not found."),
+                    self.token.range.clone()
+                ).dump_error();
+            }
+            let end = self.token.range.max.clone();
+            self.advance();
+            self.finish_node();
+            return data::Node::new(
+                data::NodeType::Array(Box::new(values)),
+                data::Range::new(start, end)
+            );
+        }
+
+        else if (matches!(self.token.token, data::TokenType::LBrace)) {
+            self.start_node(data::SyntaxType::Dict);
+            self.advance();
+            let mut entries = Vec::new();
+            if (! matches!(self.token.token, data::TokenType::RBrace)) {
+                entries.push(self.start_atom_dict_entry(new_data.clone()));
+                while (matches!(self.token.token, data::TokenType::Comma)) {
+                    self.advance();
+                    entries.push(self.start_atom_dict_entry(new_data.clone()));
+                }
+            }
+            if (! matches!(self.token.token, data::TokenType::RBrace)) {
+                exception::ParserException::new(
+                    exception::ParserExceptionType::MissingToken,
+                    String::from("Expected `,`, `}` not found."),
+                    self.token.range.clone()
+                ).dump_error();
+            }
+            let end = self.token.range.max.clone();
+            self.advance();
+            self.finish_node();
+            return data::Node::new(
+                data::NodeType::Dict(Box::new(entries)),
+                data::Range::new(start, end)
             );
         }
 
+        else if (matches!(self.token.token.clone(), data::TokenType::Identifier(keyword) if keyword == "match")) {
+            return self.start_control_match(data);
+        }
+
+        else if (matches!(self.token.token.clone(), data::TokenType::Identifier(keyword) if keyword == "func")) {
+            return self.start_atom_function(new_data);
+        }
+
+        else if (matches!(self.token.token, data::TokenType::Integer(_) | data::TokenType::Float(_))
+            && matches!(self.tokens.get(self.index + 1).map(|token| token.token.clone()), Some(data::TokenType::DoublePeriod))
+        ) {
+            return self.start_atom_range(new_data);
+        }
+
         let value = self.start_literal(data.clone());
 
         return value;
@@ -776,10 +2276,104 @@ impl Parser {
 
 
 
+    // An anonymous function, like `func (x : Int) : Int {x + 1}`.
+    fn start_atom_function(&mut self, data : ParserData) -> data::Node {
+        self.start_node(data::SyntaxType::Function);
+        let start = self.token.range.min.clone();
+        self.advance();
+        if (! matches!(self.token.token, data::TokenType::LParenthesis)) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected `(` not found."),
+                self.token.range.clone()
+            ).dump_error();
+        }
+        self.advance();
+        let args = self.start_statement_function_arguments(data.clone());
+        if (! matches!(self.token.token, data::TokenType::Colon)) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected `:` not found."),
+                self.token.range.clone()
+            ).dump_error();
+        }
+        self.advance();
+        let return_type    = self.start_type(data.clone());
+        let (content, end) = self.start_block(data);
+        self.finish_node();
+        return data::Node::new(
+            data::NodeType::Function(Box::new(args), Box::new(return_type), Box::new(content), Vec::new()),
+            data::Range::new(start, end)
+        );
+    }
+
+
+
+    fn start_atom_dict_entry(&mut self, data : ParserData) -> (data::Node, data::Node) {
+        self.start_node(data::SyntaxType::DictEntry);
+        let key = self.start_literal(data.clone());
+        if (! matches!(self.token.token, data::TokenType::Colon)) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                String::from("Expected `:` not found."),
+                self.token.range.clone()
+            ).dump_error();
+        }
+        self.advance();
+        let value = self.start_expression(data);
+        self.finish_node();
+        return (key, value);
+    }
+
+
+
+    fn start_atom_range(&mut self, data : ParserData) -> data::Node {
+        self.start_node(data::SyntaxType::Range);
+        let is_integer = matches!(self.token.token, data::TokenType::Integer(_));
+        let min        = self.start_atom_range_bound(data.clone(), is_integer);
+        self.advance();
+        let max        = self.start_atom_range_bound(data.clone(), is_integer);
+        let mut end    = max.range.max.clone();
+        let mut step   = None;
+        if (matches!(self.token.token, data::TokenType::DoublePeriod)) {
+            self.advance();
+            let value = self.start_atom_range_bound(data.clone(), is_integer);
+            end  = value.range.max.clone();
+            step = Some(value);
+        }
+        self.finish_node();
+        return data::Node::new(
+            data::NodeType::Range(Box::new(min.clone()), Box::new(max), Box::new(step)),
+            data::Range::new(min.range.min, end)
+        );
+    }
+
+
+
+    fn start_atom_range_bound(&mut self, data : ParserData, is_integer : bool) -> data::Node {
+        if (is_integer != matches!(self.token.token, data::TokenType::Integer(_))
+            || ! matches!(self.token.token, data::TokenType::Integer(_) | data::TokenType::Float(_))
+        ) {
+            exception::ParserException::new(
+                exception::ParserExceptionType::MissingToken,
+                format!("Expected {} not found.", if (is_integer) {"Integer"} else {"Float"}),
+                self.token.range.clone()
+            ).dump_error();
+        }
+        return self.start_literal(data);
+    }
+
+
+
     fn start_literal(&mut self, _data : ParserData) -> data::Node {
+        self.start_node(data::SyntaxType::Literal);
         let range = self.token.range.clone();
         let node  = match (self.token.token.clone()) {
-            data::TokenType::Identifier(name) => data::NodeType::Literal(data::Literal::Name(name)),
+            data::TokenType::Identifier(name) => data::NodeType::Literal(match (name.as_str()) {
+                "true"  => data::Literal::Bool(true),
+                "false" => data::Literal::Bool(false),
+                _       => data::Literal::Name(name)
+            }),
             data::TokenType::Character(ch)    => data::NodeType::Literal(data::Literal::Character(ch)),
             data::TokenType::String(text)     => data::NodeType::Literal(data::Literal::String(text)),
             data::TokenType::Integer(value)   => data::NodeType::Literal(data::Literal::Integer(value)),
@@ -793,6 +2387,7 @@ impl Parser {
             }
         };
         self.advance();
+        self.finish_node();
         return data::Node::new(
             node,
             range
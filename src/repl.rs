@@ -0,0 +1,297 @@
+use std::io::Write;
+use colored::Colorize;
+
+use crate::data;
+use crate::exception;
+use crate::exception::Exception;
+use crate::interpreter;
+use crate::lexer;
+use crate::library;
+use crate::loader;
+use crate::parser;
+use crate::validator;
+
+
+
+// The globals of the session make up one script, whose `use`s are next to
+// the current directory. Each input has its own filename, so that the ranges
+// of its nodes are kept apart from those of the others.
+const INPUT : &str = "<input>";
+
+// The keywords that start globals, which stay for the rest of the session.
+// `let mut` starts a statement instead, as globals can not be mutable.
+const GLOBAL_KEYWORDS : [&str; 8] = ["extern", "use", "let", "func", "class", "enum", "trait", "impl"];
+
+const COMMANDS : [(&str, &str, &str); 7] = [ // name, argument, summary
+    (":type",   "[EXPRESSION]", "Display the type of an expression."),
+    (":ast",    "[INPUT]",      "Display the syntax tree of an input."),
+    (":tokens", "[INPUT]",      "Display the tokens of an input."),
+    (":load",   "[FILENAME]",   "Add the globals of a script to the session."),
+    (":reset",  "",             "Forget every global of the session."),
+    (":help",   "",             "Display this help message."),
+    (":quit",   "",             "End the session.")
+];
+
+
+
+// The validator and the interpreter are kept for the whole session, and
+// each input is checked and run inside of what they already have.
+pub struct Repl {
+    inputs      : usize,
+    script      : Vec<data::Node>, // the globals of the session that are checked, in order
+    imports     : std::collections::HashMap<String, String>, // name, key of the modules that the script uses
+    loaded      : std::collections::HashSet<String>, // keys of the modules that are checked
+    validator   : validator::Validator,
+    interpreter : interpreter::Interpreter
+}
+impl Repl {
+
+    // Reads and runs inputs until `:quit` or the end of the input. An error
+    // ends only the input that caused it.
+    pub fn calculate() -> ! {
+        exception::set_recover(true);
+        let mut repl = Repl::new();
+        crate::version();
+        println!("{}\n", "Type `:help` for the commands.".cyan());
+        while (let Some(input) = read_input()) {
+            if (input.trim().len() <= 0) {
+                continue;
+            }
+            let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| repl.start(input)));
+        }
+        println!();
+        std::process::exit(0);
+    }
+
+    fn new() -> Repl {
+        return Repl {
+            inputs      : 0,
+            script      : Vec::new(),
+            imports     : std::collections::HashMap::new(),
+            loaded      : std::collections::HashSet::new(),
+            validator   : validator::Validator::new(),
+            interpreter : interpreter::Interpreter::new(library::Environment::new(String::from(INPUT), Vec::new()))
+        };
+    }
+
+    fn filename(&mut self) -> String {
+        self.inputs += 1;
+        return format!("<input {}>", self.inputs);
+    }
+
+
+
+    fn start(&mut self, input : String) -> () {
+        let trimmed = input.trim();
+        if (! trimmed.starts_with(":")) {
+            if (is_global(trimmed)) {
+                self.start_globals(input);
+            } else {
+                self.start_statements(input);
+            }
+            return;
+        }
+        let (command, argument) = trimmed.split_once(char::is_whitespace).map(|(command, argument)| (command, argument.trim())).unwrap_or((trimmed, ""));
+        match (command) {
+            ":type"   => self.start_type(argument.to_string()),
+            ":ast"    => start_ast(argument.to_string()),
+            ":tokens" => start_tokens(argument.to_string()),
+            ":load"   => self.start_load(argument.to_string()),
+            ":reset"  => {
+                *self = Repl::new();
+                println!("{}", "Every global was forgotten.".green());
+            },
+            ":help"   => help(),
+            ":quit"   => std::process::exit(0),
+            _         => {
+                exception::CommandLineException::new(
+                    exception::CommandLineExceptionType::Argument,
+                    format!("Invalid command `{}`. Expected {}.", command, COMMANDS.iter().map(|(name, _, _)| format!("`{}`", name)).collect::<Vec<String>>().join(", ")),
+                    trimmed.split_whitespace().map(|word| word.to_string()).collect(),
+                    0
+                ).dump_error();
+            }
+        }
+    }
+
+
+    // The globals stay only if the session is still valid with them. The
+    // modules and `extern`s that the session already has are not added again,
+    // so that scripts that use the same ones can be loaded together.
+    fn start_globals(&mut self, input : String) -> bool {
+        let filename    = self.filename();
+        let mut modules = Vec::new();
+        for mut module in loader::Loader::calculate(filename.clone(), input) {
+            if (module.key.len() >= 1) {
+                if (! self.loaded.contains(&module.key)) {
+                    modules.push(module);
+                }
+                continue;
+            }
+            module.nodes.retain(|node| match (&node.node) {
+                data::NodeType::ExternalImport(name) => ! self.script.iter().any(|other| matches!(&other.node, data::NodeType::ExternalImport(other) if other == name)),
+                data::NodeType::LocalImport(name)    => self.imports.get(name) != module.imports.get(name),
+                _                                    => true
+            });
+            modules.push(module);
+        }
+        let modules = if (let Some(modules) = self.validator.start_session_globals(modules)) {
+            modules
+        } else {
+            return false;
+        };
+        let mut started = Vec::new();
+        for module in modules {
+            self.loaded.insert(module.key.clone());
+            if (module.key.len() <= 0) {
+                self.script.extend(module.nodes);
+                self.imports.extend(module.imports);
+            } else {
+                started.push(module);
+            }
+        }
+        started.push(data::Module::new(String::new(), filename, self.script.clone(), self.imports.clone()));
+        self.interpreter.start_session(started);
+        return true;
+    }
+
+
+    // Shows the value of the final expression, unless it is `Void`.
+    fn start_statements(&mut self, input : String) -> () {
+        let tokens     = lexer::Lexer::calculate(self.filename(), input);
+        let statements = parser::Parser::calculate_statements(tokens);
+        if (let Some((statements, _)) = self.validator.start_session_statements(statements)) {
+            let value = self.interpreter.evaluate(statements);
+            if (! matches!(value, data::Value::Void)) {
+                println!("{}", value);
+            }
+        }
+    }
+
+
+    // The names that the expression declares are forgotten.
+    fn start_type(&mut self, input : String) -> () {
+        let tokens     = lexer::Lexer::calculate(self.filename(), input);
+        let statements = parser::Parser::calculate_statements(tokens);
+        if (let Some(value_type) = self.validator.type_session_statements(statements)) {
+            println!("{}", value_type.to_string().cyan());
+        }
+    }
+
+
+    // The `use`s of the script are next to the current directory, not to it.
+    fn start_load(&mut self, filename : String) -> () {
+        match (std::fs::read_to_string(&filename)) {
+            Ok(script) => {
+                if (self.start_globals(script)) {
+                    println!("{}", format!("Loaded `{}`.", filename).green());
+                }
+            },
+            Err(_)     => {
+                exception::CommandLineException::new(
+                    exception::CommandLineExceptionType::FileFailedToRead,
+                    format!("File `{}` was not found", filename),
+                    vec![String::from(":load"), filename],
+                    1
+                ).dump_error();
+            }
+        }
+    }
+
+
+}
+
+
+
+fn start_ast(input : String) -> () {
+    let tokens = lexer::Lexer::calculate(String::from(INPUT), input.clone());
+    let nodes  = if (is_global(input.trim())) {parser::Parser::calculate(tokens)} else {parser::Parser::calculate_statements(tokens)};
+    for node in nodes {
+        println!("{}", node.to_string().cyan());
+    }
+}
+
+
+fn start_tokens(input : String) -> () {
+    let tokens = lexer::Lexer::calculate(String::from(INPUT), input);
+    println!("{}", tokens.iter().map(|token| token.to_string()).collect::<Vec<String>>().join(" ").cyan());
+}
+
+
+fn is_global(input : &str) -> bool {
+    let keyword = first_word(input);
+    if (keyword == "let" && first_word(input[keyword.len()..].trim_start()) == "mut") {
+        return false;
+    }
+    return input.starts_with("#") || GLOBAL_KEYWORDS.contains(&keyword);
+}
+
+fn first_word(input : &str) -> &str {
+    return input.split(|ch : char| ! (ch.is_alphanumeric() || ch == '_')).next().unwrap_or("");
+}
+
+
+
+// Reads lines until the brackets that they open are closed, and past lines
+// of only attributes, which belong to the next one. Returns nothing at the
+// end of the input.
+fn read_input() -> Option<String> {
+    let mut input = String::new();
+    loop {
+        print!("{}", if (input.len() <= 0) {">> "} else {".. "}.magenta());
+        let _ = std::io::stdout().flush();
+        let mut line = String::new();
+        if (std::io::stdin().read_line(&mut line).unwrap_or(0) <= 0) {
+            return if (input.len() <= 0) {None} else {Some(input)};
+        }
+        input += line.as_str();
+        if (depth(&input) <= 0 && ! is_attributes(line.trim())) {
+            return Some(input);
+        }
+    }
+}
+
+
+fn is_attributes(line : &str) -> bool {
+    return line.starts_with("#[") && line.ends_with("]");
+}
+
+
+// How many brackets the input leaves open, outside of strings, characters
+// and comments.
+fn depth(input : &str) -> isize {
+    let mut depth = 0;
+    let mut chars = input.chars().peekable();
+    while (let Some(ch) = chars.next()) {
+        match (ch) {
+            '"' | '\'' => {
+                while (let Some(other) = chars.next()) {
+                    if (other == '\\') {
+                        chars.next();
+                    } else if (other == ch) {
+                        break;
+                    }
+                }
+            },
+            '/' if (chars.peek() == Some(&'/')) => {
+                while (let Some(other) = chars.next() && other != '\n') {}
+            },
+            '{' | '(' | '[' => depth += 1,
+            '}' | ')' | ']' => depth -= 1,
+            _               => {}
+        }
+    }
+    return depth;
+}
+
+
+
+fn help() -> () {
+    let width = COMMANDS.iter().map(|(name, argument, _)| name.len() + argument.len() + 1).max().unwrap_or(0);
+    println!("{}:", "COMMANDS".blue().bold());
+    for (name, argument, summary) in COMMANDS {
+        let usage = format!("{} {}", name, argument);
+        println!("  {}", format!("{}{} : {}", usage.bold(), " ".repeat(width - usage.len()), summary).cyan());
+    }
+    println!("\n  {}\n", "Globals, like `let` and `func`, and `let mut` variables stay for the rest of the session. Other input\n  is run once, and the value of its final expression is displayed. Open brackets and attributes continue\n  the input on the next line.".cyan());
+}
@@ -1,15 +1,50 @@
-use crate::lexer;
-use crate::parser;
+use crate::loader;
 use crate::validator;
+use crate::interpreter;
+use crate::library;
 
 
 
-pub fn run(filename : String, script: String) -> () {
+// Returns the exit code of the script.
+pub fn run(filename : String, script: String, arguments : Vec<String>) -> i32 {
 
-    let tokens = lexer::Lexer::calculate(filename, script);
+    let environment = library::Environment::new(filename.clone(), arguments);
 
-    let nodes  = parser::Parser::calculate(tokens);
+    let modules = loader::Loader::calculate(filename, script);
 
-    validator::Validator::calculate(nodes);
-    
+    let modules = validator::Validator::calculate(modules);
+
+    return interpreter::Interpreter::calculate(modules, environment);
+
+}
+
+
+pub fn check(filename : String, script : String) -> () {
+
+    let modules = loader::Loader::calculate(filename, script);
+
+    validator::Validator::calculate(modules);
+
+}
+
+
+// Returns the exit code of the tests.
+pub fn test(filename : String, script : String, filter : String) -> i32 {
+
+    let environment = library::Environment::new(filename.clone(), Vec::new());
+
+    let modules = loader::Loader::calculate(filename, script);
+
+    let modules = validator::Validator::calculate_tests(modules);
+
+    return interpreter::Interpreter::calculate_tests(modules, environment, filter);
+
+}
+
+
+// For the tests. Returns the exit code of a script, also if it is invalid or
+// crashes.
+#[cfg(test)]
+pub fn exit_code(script : &str) -> i32 {
+    return crate::exception::catch_exit(|| run(String::from("<test>"), script.to_string(), Vec::new())).unwrap_or_else(|code| code);
 }
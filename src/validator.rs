@@ -1,28 +1,835 @@
-use std;
-
 use crate::data;
 use crate::exception;
 use crate::exception::Exception;
+use crate::library;
+
+
+
+// The state of a REPL session, to go back to when an input is invalid.
+struct Snapshot {
+    modules    : std::collections::HashMap<String, data::Context>,
+    bases      : std::collections::HashMap<String, Vec<String>>,
+    symbols    : Vec<(data::Context, std::collections::HashMap<String, data::Object>)>, // the names of each context
+    session    : Option<data::Context>,
+    public     : std::collections::HashSet<String>,
+    signatures : std::collections::HashMap<(String, usize, usize), data::ValueType>
+}
 
 
 
-pub struct Validator {}
+pub struct Validator {
+    failed      : bool,
+    loops       : Vec<(Option<String>, bool)>, // label, broken
+    return_type : data::ValueType,
+    barrier     : Option<usize>, // inside of a `match` expression, the loops below this can not be jumped to
+    checks      : std::collections::HashMap<(String, usize, usize), data::ValueType>, // values that are bounds checked at runtime
+    closures    : Vec<(data::Context, Vec<String>)>, // the anonymous functions being checked, names they capture by value
+    captures    : std::collections::HashMap<(String, usize, usize), Vec<String>>, // names captured by value of each anonymous function
+    signatures  : std::collections::HashMap<(String, usize, usize), data::ValueType>, // the type of each function and method, worked out once
+    instances   : std::collections::HashMap<(String, usize, usize), String>, // the generic function that each call calls, like `first<Int>`
+    modules     : std::collections::HashMap<String, data::Context>, // key, globals
+    bases       : std::collections::HashMap<String, Vec<String>>, // the qualified names of each class and of its direct bases
+    session     : Option<data::Context>, // for the REPL, the names declared by its statements
+    module      : data::Module, // the module being checked
+    public      : std::collections::HashSet<String> // the qualified names of the globals marked `#[public]`
+}
 impl Validator {
 
-    pub fn calculate(nodes : Vec<data::Node>) -> () {
-        let mut validator = Validator {};
-        let     context   = data::Context::new(String::from("Global"), None);
-        for node in nodes {
-            validator.start(context.clone(), node);
+    // Returns the modules with the runtime checks that the validator could
+    // not do itself. Each module is checked after the modules it uses.
+    pub fn calculate(modules : Vec<data::Module>) -> Vec<data::Module> {
+        return Validator::calculate_script(modules, true);
+    }
+
+    // For `vesuviuspl test`, which calls the `#[test]` functions of the
+    // script instead of its `#[entry]` function.
+    pub fn calculate_tests(modules : Vec<data::Module>) -> Vec<data::Module> {
+        return Validator::calculate_script(modules, false);
+    }
+
+    fn calculate_script(modules : Vec<data::Module>, entry : bool) -> Vec<data::Module> {
+        let mut validator       = Validator::new();
+        let     modules_checked = validator.start_modules(modules);
+        // The script that was run is the last module.
+        if (let Some(script) = modules_checked.last() && entry) {
+            validator.check_entries(validator.modules[&script.key].clone(), &script.nodes);
         }
+        if (validator.failed) {
+            exception::exit(exception::EXIT_ERROR);
+        }
+        return modules_checked;
     }
 
-    pub fn start(&mut self, context : data::Context, node : data::Node) -> data::Object {
-        return match (node.node) {
+    // For the REPL, which keeps one validator for the whole session. Checks
+    // the new modules and the new globals of the script inside of those that
+    // are already checked. Returns them with their runtime checks, or nothing
+    // if anything is invalid, in which case the session is left as it was.
+    pub fn start_session_globals(&mut self, modules : Vec<data::Module>) -> Option<Vec<data::Module>> {
+        let snapshot        = self.snapshot();
+        let modules_checked = self.start_modules(modules);
+        if (self.failed) {
+            self.restore(snapshot);
+            return None;
+        }
+        return Some(modules_checked);
+    }
+
+    // For the REPL. Checks statements inside of the globals of the script.
+    // The names they declare stay for the rest of the session. Returns the
+    // statements with their runtime checks and the type of their value, or
+    // nothing if anything is invalid.
+    pub fn start_session_statements(&mut self, statements : Vec<data::Node>) -> Option<(Vec<data::Node>, data::ValueType)> {
+        let snapshot   = self.snapshot();
+        let session    = self.session();
+        let value_type = self.start_body_value(session, statements.clone());
+        let statements = self.finish_nodes(statements);
+        if (self.failed) {
+            self.restore(snapshot);
+            return None;
+        }
+        return Some((statements, value_type));
+    }
+
+    // For the REPL. The type of the value of statements, which are then
+    // forgotten.
+    pub fn type_session_statements(&mut self, statements : Vec<data::Node>) -> Option<data::ValueType> {
+        let snapshot = self.snapshot();
+        let result   = self.start_session_statements(statements);
+        self.restore(snapshot);
+        return result.map(|(_, value_type)| value_type);
+    }
+
+    // The context of the statements of the REPL, inside of the globals of
+    // the script.
+    fn session(&mut self) -> data::Context {
+        if (let Some(session) = &self.session) {
+            return session.clone();
+        }
+        let globals = if (let Some(globals) = self.modules.get("")) {
+            globals.clone()
+        } else {
+            let globals = data::Context::new(String::from("Global"), Some((data::Range::new_void(), builtins())));
+            self.modules.insert(String::new(), globals.clone());
+            globals
+        };
+        let session = data::Context::new(String::from("Session"), Some((data::Range::new_void(), globals)));
+        self.session = Some(session.clone());
+        return session;
+    }
+
+    fn snapshot(&mut self) -> Snapshot {
+        self.failed = false;
+        let mut symbols = Vec::new();
+        for context in self.modules.values().chain(self.session.iter()) {
+            symbols.push((context.clone(), context.symbols.borrow().clone()));
+        }
+        return Snapshot {
+            modules    : self.modules.clone(),
+            bases      : self.bases.clone(),
+            symbols,
+            session    : self.session.clone(),
+            public     : self.public.clone(),
+            signatures : self.signatures.clone()
+        };
+    }
+
+    fn restore(&mut self, snapshot : Snapshot) -> () {
+        for (context, symbols) in snapshot.symbols {
+            *context.symbols.borrow_mut() = symbols;
+        }
+        self.modules    = snapshot.modules;
+        self.bases      = snapshot.bases;
+        self.session    = snapshot.session;
+        self.public     = snapshot.public;
+        self.signatures = snapshot.signatures;
+        self.checks.clear();
+        self.captures.clear();
+        self.instances.clear();
+        self.failed     = false;
+    }
+
+    pub fn new() -> Validator {
+        return Validator {
+            failed      : false,
+            loops       : Vec::new(),
+            return_type : data::ValueType::Void,
+            barrier     : None,
+            checks      : std::collections::HashMap::new(),
+            closures    : Vec::new(),
+            captures    : std::collections::HashMap::new(),
+            signatures  : std::collections::HashMap::new(),
+            instances   : std::collections::HashMap::new(),
+            modules     : std::collections::HashMap::new(),
+            bases       : std::collections::HashMap::new(),
+            session     : None,
+            module      : data::Module::new(String::new(), String::new(), Vec::new(), std::collections::HashMap::new()),
+            public      : std::collections::HashSet::new()
+        };
+    }
+
+    // A module that is already checked, like the script in the REPL, gets
+    // its new globals added to those it has.
+    fn start_modules(&mut self, modules : Vec<data::Module>) -> Vec<data::Module> {
+        let builtins = builtins();
+        let mut modules_checked = Vec::new();
+        for mut module in modules {
+            let context = if (let Some(context) = self.modules.get(&module.key)) {
+                context.clone()
+            } else {
+                data::Context::new(if (module.key.len() <= 0) {String::from("Global")} else {module.key.clone()}, Some((data::Range::new_void(), builtins.clone())))
+            };
+            self.modules.insert(module.key.clone(), context.clone());
+            self.module  = module.clone();
+            module.nodes = self.start_module(context, module.nodes);
+            modules_checked.push(module);
+        }
+        return modules_checked;
+    }
+
+
+
+    fn start_module(&mut self, context : data::Context, nodes : Vec<data::Node>) -> Vec<data::Node> {
+        // Globals are declared before any body is checked, so that functions
+        // can be used above their definition.
+        // Classes, enums and traits come first, so that any global can use them as a type.
+        // Imports and the aliases of their modules come before them, so that types can be written
+        // through them.
+        let (imports, others) = nodes.clone().into_iter().partition::<Vec<data::Node>, _>(|node| matches!(node.node, data::NodeType::ExternalImport(_) | data::NodeType::LocalImport(_)));
+        for node in imports {
+            self.declare(context.clone(), node);
+        }
+        let mut rest = Vec::new();
+        for node in others {
+            if (self.alias_of(&context, &node).is_some()) {
+                self.declare(context.clone(), node);
+            } else {
+                rest.push(node);
+            }
+        }
+        let (types, others) = rest.into_iter().partition::<Vec<data::Node>, _>(|node| matches!(node.node, data::NodeType::DefineClass(..) | data::NodeType::DefineEnum(..) | data::NodeType::DefineTrait(..)));
+        let (impls, others) = others.into_iter().partition::<Vec<data::Node>, _>(|node| matches!(node.node, data::NodeType::ImplementTrait(..)));
+        for node in types.clone() {
+            self.declare(context.clone(), node);
+        }
+        for node in impls {
+            self.implement_trait(context.clone(), node);
+        }
+        self.order_classes(context.clone(), types.clone());
+        self.resolve_enums(context.clone(), types);
+        for node in others {
+            self.declare(context.clone(), node);
+        }
+        for node in nodes.clone() {
+            self.start(context.clone(), node);
+        }
+        return self.finish_nodes(nodes);
+    }
+
+    // Adds the names that anonymous functions capture and the runtime
+    // bounds checks to the nodes.
+    fn finish_nodes(&mut self, nodes : Vec<data::Node>) -> Vec<data::Node> {
+        return nodes.into_iter().map(|node| node.map(&mut |mut node| {
+            if (let data::NodeType::Function(_, _, _, captures) = &mut node.node
+                && let Some(names) = self.captures.remove(&check_key(&node.range))
+            ) {
+                *captures = names;
+            }
+            if (let Some(bounds) = self.checks.remove(&check_key(&node.range))) {
+                let range = node.range.clone();
+                return data::Node::new(data::NodeType::BoundsCheck(Box::new(node), bounds), range);
+            }
+            return node;
+        })).collect();
+    }
+
+    // The script that was run must have exactly one `#[entry]` function.
+    // Those of the modules that it uses are never called.
+    fn check_entries(&mut self, context : data::Context, nodes : &[data::Node]) -> () {
+        let entries = nodes.iter().filter_map(|node| match (&node.node) {
+            data::NodeType::DefineFunction(name, ..) if node.headers.has("entry") => Some((name.clone(), node.range.clone())),
+            _                                                                      => None
+        }).collect::<Vec<(String, data::Range)>>();
+        if (entries.len() <= 0) {
+            self.invalid(
+                exception::ValidatorExceptionType::Name,
+                format!("Script `{}` has no `#[entry]` function.", self.module.path),
+                data::Range::new_void(),
+                context
+            );
+            return;
+        }
+        let (first, first_range) = entries[0].clone();
+        for (name, range) in entries.into_iter().skip(1) {
+            self.invalid(
+                exception::ValidatorExceptionType::Name,
+                format!("Function `{}` at line {}, column {} is marked `#[entry]`, but so is `{}` at line {}, column {}. A script has exactly one.", name, range.min.line + 1, range.min.column, first, first_range.min.line + 1, first_range.min.column),
+                range,
+                context.clone()
+            );
+        }
+    }
+
+    // Looks up a class or enum by the name that every module knows it by,
+    // like `util::Point`.
+    fn lookup(&self, context : &data::Context, name : &String) -> Option<data::Object> {
+        if (let Some((key, local)) = name.rsplit_once("::")
+            && let Some(module) = self.modules.get(key)
+        ) {
+            return module.get_name(&local.to_string());
+        }
+        return context.get_name(name);
+    }
+
+    // The context that the types of a class or enum are written in.
+    fn owner(&self, context : &data::Context, name : &str) -> data::Context {
+        if (let Some((key, _)) = name.rsplit_once("::")
+            && let Some(module) = self.modules.get(key)
+        ) {
+            return module.clone();
+        }
+        return context.clone();
+    }
+
+    // The name that every module knows the global `name` by.
+    fn qualify(&self, context : &data::Context, name : &String) -> String {
+        if (let Some(owner) = defining_context(context, name)) {
+            for (key, module) in &self.modules {
+                if (std::rc::Rc::ptr_eq(&module.symbols, &owner.symbols)) {
+                    return data::qualify(key, name);
+                }
+            }
+        }
+        return name.clone();
+    }
+
+
+
+    fn invalid(&mut self, exception_type : exception::ValidatorExceptionType, message : String, range : data::Range, context : data::Context) -> () {
+        exception::ValidatorException::new(
+            exception_type,
+            message,
+            range,
+            context
+        ).dump_invalid();
+        self.failed = true;
+    }
+
+    // A value returned by a generic function names the types it was called
+    // with.
+    fn expect_type(&mut self, context : data::Context, expected : &data::ValueType, found : &data::ValueType, range : data::Range) -> () {
+        if (! expected.accepts(found)) {
+            let instance = self.instances.get(&check_key(&range)).map(|instance| format!(" It is returned by `{}`.", instance)).unwrap_or_default();
+            self.invalid(
+                exception::ValidatorExceptionType::Type,
+                format!("Expected `{}`, found `{}`.{}", expected, found, instance),
+                range,
+                context
+            );
+        }
+    }
+
+
+
+    // Like `expect_type`, but for a value that is stored, passed or
+    // returned. A value that may escape the bounds of a refined type is
+    // checked at runtime, one that always escapes them is rejected.
+    fn expect_value(&mut self, context : data::Context, expected : &data::ValueType, found : &data::ValueType, value : &data::Node) -> () {
+        self.expect_type(context.clone(), expected, found, value.range.clone());
+        if (! expected.is_refined()) {
+            return;
+        }
+        let bounds = if (found.is_refined()) {Some(found.clone())} else {constant_bounds(value)};
+        if (let Some(bounds) = &bounds && bounds.within(expected)) {
+            return;
+        }
+        if (let Some(bounds) = &bounds && bounds.outside(expected)) {
+            self.invalid(
+                exception::ValidatorExceptionType::Type,
+                match (bounds) {
+                    data::ValueType::RInt(min, max) if (min == max)   => format!("Value `{}` is never inside of `{}`.", min, expected),
+                    data::ValueType::RFloat(min, max) if (min == max) => format!("Value `{:?}` is never inside of `{}`.", min, expected),
+                    _                                                 => format!("Value in `{}` is never inside of `{}`.", bounds, expected)
+                },
+                value.range.clone(),
+                context
+            );
+            return;
+        }
+        self.checks.insert(check_key(&value.range), expected.clone());
+    }
+
+
+
+    fn declare(&mut self, context : data::Context, node : data::Node) -> () {
+        let alias = self.alias_of(&context, &node);
+        let (name, object) = match (node.node) {
+            data::NodeType::InitializeVariable(_, name, _, _) if (alias.is_some()) => (name, alias.unwrap_or(data::ObjectType::Variable(false, data::ValueType::Unknown))),
+            data::NodeType::ExternalImport(name)                          => (name.clone(), library::module_type(&name).unwrap_or(data::ObjectType::Module(std::collections::HashMap::new()))),
+            data::NodeType::LocalImport(name)                             => (name.clone(), data::ObjectType::LocalModule(self.module.imports[&name].clone())),
+            data::NodeType::DefineFunction(name, generics, args, return_type, _) => (name, data::ObjectType::Function(generics, *args, *return_type)),
+            data::NodeType::InitializeVariable(mutable, name, typ, _)     => (name, data::ObjectType::Variable(mutable, self.resolve_type(context.clone(), *typ))),
+            data::NodeType::DefineClass(name, generics, bases, fields, methods) => (name.clone(), data::ObjectType::Class(generics, bases, vec![self.module.qualify(&name)], *fields, *methods)),
+            data::NodeType::DefineEnum(name, variants)                    => (name, data::ObjectType::Enum(variants.iter().map(|(variant, payload)| (
+                variant.clone(), payload.iter().map(|(field, _)| (field.clone(), data::ValueType::Unknown)).collect()
+            )).collect())),
+            data::NodeType::DefineTrait(name, methods)                    => (name, data::ObjectType::Trait(*methods, Vec::new())),
+            _                                                             => {
+                exception::InternalException::new(
+                    String::from("Invalid global node.")
+                ).dump_critical();
+            }
+        };
+        if (context.contains_name(&name)) {
+            self.invalid(
+                exception::ValidatorExceptionType::Name,
+                format!("Name `{}` is already defined.", name),
+                node.range.clone(),
+                context.clone()
+            );
+            return;
+        }
+        if (node.headers.has("public")) {
+            self.public.insert(self.module.qualify(&name));
+        }
+        context.set_name(name, data::Object::new(object, node.range));
+    }
+
+
+
+    // Adds the methods of a trait implementation to its class, once all
+    // classes and traits are declared.
+    fn implement_trait(&mut self, context : data::Context, node : data::Node) -> () {
+        let (name, class, methods) = if (let data::NodeType::ImplementTrait(name, class, methods) = node.node) {
+            (name, class, *methods)
+        } else {
+            exception::InternalException::new(
+                String::from("Invalid trait implementation node.")
+            ).dump_critical();
+        };
+        let (trait_object, trait_methods, implementors) = match (context.get_name(&name)) {
+            Some(object) => if (let data::ObjectType::Trait(trait_methods, implementors) = object.value.clone()) {
+                (object, trait_methods, implementors)
+            } else {
+                self.invalid(
+                    exception::ValidatorExceptionType::Name,
+                    format!("`{}` is not a trait.", name),
+                    node.range,
+                    context
+                );
+                return;
+            },
+            None         => {
+                self.invalid(
+                    exception::ValidatorExceptionType::Name,
+                    format!("Trait `{}` is not defined.", name),
+                    node.range,
+                    context
+                );
+                return;
+            }
+        };
+        let class_object = context.get_name(&class);
+        let (class_range, generics, bases, order, fields, class_methods) = if (let Some(object) = class_object
+            && let data::ObjectType::Class(generics, bases, order, fields, class_methods) = object.value
+        ) {
+            (object.range, generics, bases, order, fields, class_methods)
+        } else {
+            self.invalid(
+                exception::ValidatorExceptionType::Name,
+                format!("Class `{}` is not defined.", class),
+                node.range,
+                context
+            );
+            return;
+        };
+        if (implementors.contains(&order[0])) {
+            self.invalid(
+                exception::ValidatorExceptionType::Name,
+                format!("Class `{}` already implements trait `{}`.", class, name),
+                node.range,
+                context
+            );
+            return;
+        }
+        // The built in traits are shared, so their implementations are
+        // recorded where they are declared.
+        let trait_context = defining_context(&context, &name).unwrap_or(context.clone());
+        trait_context.set_name(name, data::Object::new(data::ObjectType::Trait(trait_methods, [implementors, vec![order[0].clone()]].concat()), trait_object.range));
+        context.set_name(class, data::Object::new(data::ObjectType::Class(generics, bases, order, fields, [class_methods, methods].concat()), class_range));
+    }
+
+
+
+    // Works out the method resolution order of every class, once all of them
+    // are declared.
+    fn order_classes(&mut self, context : data::Context, classes : Vec<data::Node>) -> () {
+        for node in classes.clone() {
+            if (let data::NodeType::DefineClass(name, _, class_bases, _, _) = node.node) {
+                let mut valid = true;
+                let mut base_order = Vec::new();
+                for base in &class_bases {
+                    match (context.get_name(base).map(|object| object.value)) {
+                        Some(data::ObjectType::Class(generics, _, order, _, _)) if (generics.len() <= 0) => base_order.push(order[0].clone()),
+                        Some(data::ObjectType::Class(..))                                   => {
+                            self.invalid(
+                                exception::ValidatorExceptionType::Type,
+                                format!("Class `{}` is generic and can not be used as a base.", base),
+                                node.range.clone(),
+                                context.clone()
+                            );
+                            valid = false;
+                        },
+                        _                                                                    => {
+                            self.invalid(
+                                exception::ValidatorExceptionType::Name,
+                                format!("Class `{}` is not defined.", base),
+                                node.range.clone(),
+                                context.clone()
+                            );
+                            valid = false;
+                        }
+                    }
+                }
+                self.bases.insert(self.module.qualify(&name), if (valid) {base_order} else {Vec::new()});
+            }
+        }
+        for node in classes {
+            if (let data::NodeType::DefineClass(name, _, _, _, _) = node.node
+                && let Some(object) = context.get_name(&name)
+                && let data::ObjectType::Class(generics, class_bases, _, fields, methods) = object.value
+            ) {
+                let order = match (data::method_resolution_order(&self.module.qualify(&name), &self.bases)) {
+                    Ok(order)    => order,
+                    Err(message) => {
+                        self.invalid(
+                            exception::ValidatorExceptionType::Type,
+                            message,
+                            node.range.clone(),
+                            context.clone()
+                        );
+                        vec![self.module.qualify(&name)]
+                    }
+                };
+                context.set_name(name, data::Object::new(data::ObjectType::Class(generics, class_bases, order, fields, methods), object.range));
+            }
+        }
+    }
+
+
+
+    // Works out the payload types of every enum, once all classes and enums
+    // are declared.
+    fn resolve_enums(&mut self, context : data::Context, enums : Vec<data::Node>) -> () {
+        for node in enums {
+            if (let data::NodeType::DefineEnum(name, variants) = node.node) {
+                let mut resolved = Vec::new();
+                for (variant, payload) in *variants {
+                    let mut resolved_payload = Vec::new();
+                    for (field, typ) in payload {
+                        resolved_payload.push((field, self.resolve_type(context.clone(), typ)));
+                    }
+                    resolved.push((variant, resolved_payload));
+                }
+                if (let Some(object) = context.get_name(&name)
+                    && matches!(object.value, data::ObjectType::Enum(_))
+                ) {
+                    context.set_name(name, data::Object::new(data::ObjectType::Enum(resolved), object.range));
+                }
+            }
+        }
+    }
+
+
+
+    pub fn resolve_type(&mut self, context : data::Context, node : data::Node) -> data::ValueType {
+        let (base, arguments) = if (let data::NodeType::Type(base, arguments) = node.node.clone()) {
+            (base, arguments)
+        } else {
+            exception::InternalException::new(
+                String::from("Invalid type node.")
+            ).dump_critical();
+        };
+        let parts = match (base) {
+            data::Type::Base(parts) => parts,
+            _                       => return data::ValueType::Unknown
+        };
+        if (parts.len() <= 0) {
+            self.invalid(
+                exception::ValidatorExceptionType::Type,
+                format!("A list of types like `{}` can only be the arguments of `Func`.", node),
+                node.range,
+                context
+            );
+            return data::ValueType::Unknown;
+        }
+        if (parts.len() == 1 && (parts[0] == "RInt" || parts[0] == "RFloat")) {
+            return self.resolve_refined_type(context, &parts[0], arguments, node.range);
+        }
+        if (parts.len() == 1 && parts[0] == "Array") {
+            return self.resolve_array_type(context, arguments, node.range);
+        }
+        if (parts.len() == 1 && parts[0] == "Func") {
+            return self.resolve_function_type(context, arguments, node.range);
+        }
+        // A path like `util::Point` is a type of another module, `type::Int`
+        // is a built in type through an alias of `std::types`.
+        let name         = parts.join("::");
+        let (owner, object) = if (parts.len() >= 2) {
+            if (let Some((owner, object)) = self.resolve_path(context.clone(), &parts, node.range.clone())) {
+                (owner, Some(object))
+            } else {
+                return data::ValueType::Unknown;
+            }
+        } else {
+            (context.clone(), context.get_name(&parts[0]).map(|object| object.value))
+        };
+        if (let Some(data::ObjectType::BuiltinType(builtin)) = object) {
+            let builtin_node = data::Node::new(data::NodeType::Type(data::Type::Base(vec![builtin]), arguments), node.range);
+            return self.resolve_type(context, builtin_node);
+        }
+        let mut resolved_arguments = Vec::new();
+        for argument in arguments.clone() {
+            if (let data::NodeType::Literal(literal) = &argument.node) {
+                self.invalid(
+                    exception::ValidatorExceptionType::Type,
+                    format!("Type `{}` takes types as arguments. Found `{}`.", name, literal),
+                    argument.range.clone(),
+                    context.clone()
+                );
+                resolved_arguments.push(data::ValueType::Unknown);
+                continue;
+            }
+            resolved_arguments.push(self.resolve_type(context.clone(), argument));
+        }
+        match (object) {
+            Some(data::ObjectType::Trait(_, _)) => {
+                self.invalid(
+                    exception::ValidatorExceptionType::Type,
+                    format!("Trait `{}` can not be used as a type. Use a type parameter with a bound, like `<T : {}>`.", name, name),
+                    node.range,
+                    context
+                );
+                return data::ValueType::Unknown;
+            },
+            Some(data::ObjectType::Parameter(_)) => {
+                if (resolved_arguments.len() >= 1) {
+                    self.invalid(
+                        exception::ValidatorExceptionType::Type,
+                        format!("Type `{}` takes 0 arguments. {} given.", name, resolved_arguments.len()),
+                        node.range,
+                        context
+                    );
+                }
+                return data::ValueType::Parameter(name);
+            },
+            Some(data::ObjectType::Class(generics, _, order, _, _)) => {
+                // `Self` alone is the class with its own type parameters.
+                if (name == "Self" && resolved_arguments.len() <= 0) {
+                    return data::ValueType::Class(order, generics.iter().map(|(generic, _)| data::ValueType::Parameter(generic.clone())).collect());
+                }
+                if (resolved_arguments.len() != generics.len()) {
+                    self.invalid(
+                        exception::ValidatorExceptionType::Type,
+                        format!("Type `{}` takes {} arguments. {} given.", name, generics.len(), resolved_arguments.len()),
+                        node.range,
+                        context
+                    );
+                    return data::ValueType::Class(order, vec![data::ValueType::Unknown; generics.len()]);
+                }
+                let arguments = generics.iter().map(|(generic, _)| generic.clone()).zip(resolved_arguments.iter().cloned()).collect();
+                self.expect_bounds(owner, &generics, &arguments, &name, node.range);
+                return data::ValueType::Class(order, resolved_arguments);
+            },
+            Some(data::ObjectType::Enum(_)) => {
+                if (resolved_arguments.len() >= 1) {
+                    self.invalid(
+                        exception::ValidatorExceptionType::Type,
+                        format!("Type `{}` takes 0 arguments. {} given.", name, resolved_arguments.len()),
+                        node.range,
+                        context
+                    );
+                }
+                return data::ValueType::Enum(self.qualify(&owner, &parts[parts.len() - 1]));
+            },
+            // Only names known before running can be types.
+            Some(data::ObjectType::Variable(_, _) | data::ObjectType::Function(..)) => {
+                self.invalid(
+                    exception::ValidatorExceptionType::Type,
+                    format!("`{}` is a value, not a type.", name),
+                    node.range,
+                    context
+                );
+                return data::ValueType::Unknown;
+            },
+            Some(data::ObjectType::Module(_) | data::ObjectType::LocalModule(_)) => {
+                self.invalid(
+                    exception::ValidatorExceptionType::Type,
+                    format!("Module `{}` is not a type. Use one of its members, like `{}::Name`.", name, name),
+                    node.range,
+                    context
+                );
+                return data::ValueType::Unknown;
+            },
+            _ if (parts.len() >= 2) => {
+                self.invalid(
+                    exception::ValidatorExceptionType::Type,
+                    format!("`{}` is not a type.", name),
+                    node.range,
+                    context
+                );
+                return data::ValueType::Unknown;
+            },
+            _ => {}
+        }
+        let expected_arguments = match (parts[0].as_str()) {
+            "Tuple"                                                                                       => resolved_arguments.len(),
+            "List"                                                                                        => 1,
+            "Dict"                                                                                        => 2,
+            "Void" | "Crash" | "Bool" | "Int" | "Float" | "Char" | "String" | "IntRange" | "FloatRange" => 0,
+            _                                                                                             => {
+                self.invalid(
+                    exception::ValidatorExceptionType::Name,
+                    format!("Type `{}` is not defined.", parts[0]),
+                    node.range,
+                    context
+                );
+                return data::ValueType::Unknown;
+            }
+        };
+        if (resolved_arguments.len() != expected_arguments) {
+            self.invalid(
+                exception::ValidatorExceptionType::Type,
+                format!("Type `{}` takes {} arguments. {} given.", parts[0], expected_arguments, resolved_arguments.len()),
+                node.range,
+                context
+            );
+            return data::ValueType::Unknown;
+        }
+        return match (parts[0].as_str()) {
+            "Void"       => data::ValueType::Void,
+            "Crash"      => data::ValueType::Crash,
+            "Bool"       => data::ValueType::Bool,
+            "Int"        => data::ValueType::Int,
+            "Float"      => data::ValueType::Float,
+            "Char"       => data::ValueType::Char,
+            "String"     => data::ValueType::String,
+            "IntRange"   => data::ValueType::IntRange,
+            "FloatRange" => data::ValueType::FloatRange,
+            "Tuple"      => data::ValueType::Tuple(resolved_arguments),
+            "List"       => data::ValueType::List(Box::new(resolved_arguments[0].clone())),
+            "Dict"       => data::ValueType::Dict(Box::new(resolved_arguments[0].clone()), Box::new(resolved_arguments[1].clone())),
+            _            => data::ValueType::Unknown
+        };
+    }
+
+
+
+    // `RInt<I, A>` and `RFloat<I, A>` hold the values from `I` to `A`, both included.
+    fn resolve_refined_type(&mut self, context : data::Context, name : &String, arguments : Vec<data::Node>, range : data::Range) -> data::ValueType {
+        let mut bounds = Vec::new();
+        for argument in &arguments {
+            match (name.as_str(), &argument.node) {
+                (_, data::NodeType::Literal(data::Literal::Integer(value)))      => bounds.push(*value as f64),
+                ("RFloat", data::NodeType::Literal(data::Literal::Float(value))) => bounds.push(*value),
+                _                                                                => {}
+            }
+        }
+        if (arguments.len() != 2 || bounds.len() != 2) {
+            self.invalid(
+                exception::ValidatorExceptionType::Type,
+                format!("Type `{}` takes 2 {} literal arguments.", name, if (name == "RInt") {"Integer"} else {"Integer or Float"}),
+                range,
+                context
+            );
+            return if (name == "RInt") {data::ValueType::Int} else {data::ValueType::Float};
+        }
+        let refined = if (let [data::NodeType::Literal(data::Literal::Integer(min)), data::NodeType::Literal(data::Literal::Integer(max))] = [&arguments[0].node, &arguments[1].node]
+            && name == "RInt"
+        ) {
+            data::ValueType::RInt(*min, *max)
+        } else {
+            data::ValueType::RFloat(bounds[0], bounds[1])
+        };
+        if (bounds[0] > bounds[1]) {
+            self.invalid(
+                exception::ValidatorExceptionType::Type,
+                format!("Type `{}` holds no values. The lower bound must not be above the upper bound.", refined),
+                range,
+                context
+            );
+        }
+        return refined;
+    }
+
+
+
+    // The length of `Array<T, L>` is part of its type.
+    fn resolve_array_type(&mut self, context : data::Context, arguments : Vec<data::Node>, range : data::Range) -> data::ValueType {
+        if (let [item, length] = &arguments[..]
+            && let data::NodeType::Type(_, _) = item.node
+            && let data::NodeType::Literal(data::Literal::Integer(length)) = length.node
+            && length >= 0
+        ) {
+            let item = self.resolve_type(context, item.clone());
+            return data::ValueType::Array(Box::new(item), length as usize);
+        }
+        self.invalid(
+            exception::ValidatorExceptionType::Type,
+            String::from("Type `Array` takes a type and a length, like `Array<Int, 3>`."),
+            range,
+            context
+        );
+        return data::ValueType::Unknown;
+    }
+
+
+
+    // `Func<<A, B>, R>` takes the argument types as a list.
+    fn resolve_function_type(&mut self, context : data::Context, arguments : Vec<data::Node>, range : data::Range) -> data::ValueType {
+        if (let [args, return_type] = &arguments[..]
+            && let data::NodeType::Type(data::Type::Base(parts), args) = &args.node
+            && parts.len() <= 0
+            && args.iter().chain([return_type]).all(|typ| matches!(typ.node, data::NodeType::Type(_, _)))
+        ) {
+            let args        = args.iter().map(|arg| self.resolve_type(context.clone(), arg.clone())).collect();
+            let return_type = self.resolve_type(context, return_type.clone());
+            return data::ValueType::Func(args, Box::new(return_type));
+        }
+        self.invalid(
+            exception::ValidatorExceptionType::Type,
+            String::from("Type `Func` takes a list of argument types and a return type, like `Func<<Int, Int>, Bool>`."),
+            range,
+            context
+        );
+        return data::ValueType::Unknown;
+    }
+
+
+
+    pub fn start(&mut self, context : data::Context, node : data::Node) -> () {
+        match (node.node) {
             data::NodeType::ExternalImport(name)                          => self.start_external_import(context, node.range, name),
             data::NodeType::LocalImport(name)                             => self.start_local_import(context, node.range, name),
-            data::NodeType::DefineFunction(name, args, return_type, body) => self.start_define_function(context, node.range, name, *args, *return_type, *body),
-            data::NodeType::InitializeVariable(mutable, name, typ, value) => self.start_initialize_variable(context, node.range, mutable, name, *typ, *value),
+            data::NodeType::DefineFunction(name, generics, args, return_type, body) => {
+                let is_generic = generics.len() >= 1;
+                let function   = self.start_define_function(context.clone(), node.range.clone(), name.clone(), generics, *args, *return_type, *body, None);
+                if (node.headers.has("entry") && self.module.key.len() <= 0) {
+                    self.start_entry(context.clone(), node.range.clone(), name.clone(), is_generic, function.clone());
+                }
+                if (node.headers.has("test")) {
+                    self.start_test(context, node.range, name, is_generic, function);
+                }
+            },
+            data::NodeType::DefineClass(name, generics, _, fields, _)               => self.start_define_class(context, node.range, name, generics, *fields),
+            data::NodeType::DefineEnum(name, variants)                    => self.start_define_enum(context, node.range, name, *variants),
+            data::NodeType::DefineTrait(name, methods)                    => self.start_define_trait(context, node.range, name, *methods),
+            data::NodeType::ImplementTrait(name, class, methods)          => self.start_implement_trait(context, node.range, name, class, *methods),
+            data::NodeType::InitializeVariable(mutable, name, typ, value) => {
+                // Aliases have no value to check.
+                if (matches!(context.get_name(&name).map(|object| object.value), Some(data::ObjectType::Variable(_, _)))) {
+                    self.start_initialize_variable(context, node.range, mutable, name, *typ, *value);
+                }
+            },
             _                                                             => {
                 exception::InternalException::new(
                     String::from("Invalid global node.")
@@ -30,45 +837,2291 @@ impl Validator {
             }
         };
     }
- 
 
 
-    pub fn start_external_import(&mut self, context : data::Context, range : data::Range, name : String) -> data::Object {
-        /*if (self.contains_name(name.clone())) {
-            exception::ValidatorException::new(
+
+    pub fn start_external_import(&mut self, context : data::Context, range : data::Range, name : String) -> () {
+        if (library::module_type(&name).is_none()) {
+            self.invalid(
                 exception::ValidatorExceptionType::Name,
-                format!("Name `{}` is already defined.", name),
-                range.clone()
-            ).dump_invalid();
-        }*/
+                format!("Native module `{}` does not exist. Scripts are loaded with `use {};`.", name, name),
+                range,
+                context
+            );
+        }
+    }
+
+
+
+    pub fn start_local_import(&mut self, _context : data::Context, _range : data::Range, _name : String) -> () {
+    }
+
+
+
+    // Methods get their instance as `receiver`, which is bound to `self`.
+    // Returns the type of the function.
+    pub fn start_define_function(&mut self, context : data::Context, range : data::Range, name : String, generics : Vec<(String, Vec<String>)>, args : Vec<(String, data::Node)>, return_type : data::Node, body : Vec<data::Node>, receiver : Option<data::ValueType>) -> data::ValueType {
+        let signature        = self.function_type(context.clone(), &range, &generics, &args, &return_type);
+        let function_context = data::Context::new(name.clone(), Some((range.clone(), context)));
+        self.start_generics(function_context.clone(), range.clone(), &generics);
+        if (let Some(receiver) = receiver) {
+            function_context.set_name(String::from("self"), data::Object::new(data::ObjectType::Variable(true, receiver), range.clone()));
+        }
+        let (arg_types, return_type) = match (generic_body(signature)) {
+            data::ValueType::Func(arg_types, return_type) => (arg_types, *return_type),
+            _                                             => (Vec::new(), data::ValueType::Unknown)
+        };
+        for ((arg, typ), resolved) in args.into_iter().zip(arg_types.iter()) {
+            function_context.set_name(arg, data::Object::new(data::ObjectType::Variable(false, resolved.clone()), typ.range));
+        }
+        let loops       = std::mem::take(&mut self.loops);
+        let outer       = std::mem::replace(&mut self.return_type, return_type.clone());
+        let barrier     = self.barrier.take();
+        let returns     = self.start_body(function_context.clone(), body, true);
+        self.loops       = loops;
+        self.return_type = outer;
+        self.barrier     = barrier;
+        if (! returns && ! [data::ValueType::Void, data::ValueType::Unknown].contains(&return_type)) {
+            self.invalid(
+                exception::ValidatorExceptionType::ControlFlow,
+                format!("Not every path of function `{}` returns a value of type `{}`.", name, return_type),
+                range,
+                function_context
+            );
+        }
+        return data::ValueType::Func(arg_types, Box::new(return_type));
+    }
+
+
+
+    // The entry function takes nothing or the arguments of the script, and
+    // returns nothing or the exit code.
+    pub fn start_entry(&mut self, context : data::Context, range : data::Range, name : String, is_generic : bool, function : data::ValueType) -> () {
+        if (is_generic) {
+            self.invalid(
+                exception::ValidatorExceptionType::Type,
+                format!("Entry function `{}` can not be generic.", name),
+                range,
+                context
+            );
+            return;
+        }
+        let arguments = data::ValueType::List(Box::new(data::ValueType::String));
+        let valid     = match (&function) {
+            data::ValueType::Func(args, return_type) => (args.len() <= 0 || (args.len() == 1 && args[0] == arguments))
+                && [data::ValueType::Void, data::ValueType::Int].contains(return_type),
+            _                                        => false
+        };
+        if (! valid) {
+            self.invalid(
+                exception::ValidatorExceptionType::Type,
+                format!("Entry function `{}` has type `{}`. It takes nothing or `{}`, and returns `Void` or an `Int` exit code.", name, function, arguments),
+                range,
+                context
+            );
+        }
+    }
+
+
+
+    pub fn start_test(&mut self, context : data::Context, range : data::Range, name : String, is_generic : bool, function : data::ValueType) -> () {
+        if (is_generic) {
+            self.invalid(
+                exception::ValidatorExceptionType::Type,
+                format!("Test function `{}` can not be generic.", name),
+                range,
+                context
+            );
+            return;
+        }
+        if (! matches!(&function, data::ValueType::Func(args, return_type) if args.len() <= 0 && **return_type == data::ValueType::Void)) {
+            self.invalid(
+                exception::ValidatorExceptionType::Type,
+                format!("Test function `{}` has type `{}`. It takes nothing and returns `Void`.", name, function),
+                range,
+                context
+            );
+        }
+    }
+
+
+
+    // The methods of the class include those of its trait implementations.
+    pub fn start_define_class(&mut self, context : data::Context, range : data::Range, name : String, generics : Vec<(String, Vec<String>)>, fields : Vec<(String, data::Node, Option<data::Node>)>) -> () {
+        let object = context.get_name(&name).map(|object| object.value);
+        let (order, methods) = if (let Some(data::ObjectType::Class(_, _, order, _, methods)) = object.clone()) {
+            (order, methods)
+        } else {
+            exception::InternalException::new(
+                String::from("Class is not declared.")
+            ).dump_critical();
+        };
+        let class_context = data::Context::new(name.clone(), Some((range.clone(), self_context(context.clone(), object))));
+        self.start_generics(class_context.clone(), range.clone(), &generics);
+
+        let mut members = Vec::new();
+        for (field, typ, default) in &fields {
+            if (members.contains(field)) {
+                self.invalid(
+                    exception::ValidatorExceptionType::Name,
+                    format!("Member `{}` of class `{}` is already defined.", field, name),
+                    typ.range.clone(),
+                    context.clone()
+                );
+            }
+            members.push(field.clone());
+            let resolved = self.resolve_type(class_context.clone(), typ.clone());
+            if (let Some(default) = default) {
+                let default_type = self.start_expression(context.clone(), default.clone());
+                self.expect_value(context.clone(), &resolved, &default_type, default);
+            }
+            for (other_field, other_type, _, owner) in self.class_fields(context.clone(), &order[1..]) {
+                if (other_field == *field && other_type != resolved) {
+                    self.invalid(
+                        exception::ValidatorExceptionType::Type,
+                        format!("Field `{}` of class `{}` has type `{}`, but `{}` declares it as `{}`.", field, name, resolved, owner, other_type),
+                        typ.range.clone(),
+                        context.clone()
+                    );
+                }
+            }
+        }
+
+        for method in &methods {
+            if (let data::NodeType::DefineFunction(method_name, method_generics, args, return_type, _) = &method.node) {
+                if (members.contains(method_name)) {
+                    self.invalid(
+                        exception::ValidatorExceptionType::Name,
+                        format!("Member `{}` of class `{}` is already defined.", method_name, name),
+                        method.range.clone(),
+                        context.clone()
+                    );
+                }
+                members.push(method_name.clone());
+                let method_type = self.function_type(class_context.clone(), &method.range, method_generics, args, return_type);
+                for (other_method, other_static, other_type, owner) in self.class_methods(context.clone(), &order[1..].to_vec()) {
+                    if (other_method == *method_name && (other_static != method.headers.has("static") || other_type != method_type)) {
+                        self.invalid(
+                            exception::ValidatorExceptionType::Type,
+                            format!("Method `{}` of class `{}` does not match `{}` of `{}`. Expected {}`{}`, found {}`{}`.",
+                                method_name, name, method_name, owner,
+                                if (other_static) {"static "} else {""}, other_type,
+                                if (method.headers.has("static")) {"static "} else {""}, method_type
+                            ),
+                            method.range.clone(),
+                            context.clone()
+                        );
+                    }
+                }
+                let is_void = match (&method_type) {
+                    data::ValueType::Func(_, return_type) => **return_type == data::ValueType::Void,
+                    data::ValueType::Generic(_, typ)      => matches!(&**typ, data::ValueType::Func(_, return_type) if **return_type == data::ValueType::Void),
+                    _                                     => false
+                };
+                if (method_name == "init" && (method.headers.has("static") || ! is_void)) {
+                    self.invalid(
+                        exception::ValidatorExceptionType::Type,
+                        format!("Constructor `init` of class `{}` must not be static and must return `Void`.", name),
+                        method.range.clone(),
+                        context.clone()
+                    );
+                }
+            }
+        }
+
+        for method in methods {
+            if (let data::NodeType::DefineFunction(method_name, method_generics, args, return_type, body) = method.node) {
+                let receiver = if (method.headers.has("static")) {None} else {Some(data::ValueType::Class(order.clone(), generics.iter().map(|(generic, _)| data::ValueType::Parameter(generic.clone())).collect()))};
+                self.start_define_function(class_context.clone(), method.range, method_name, method_generics, *args, *return_type, *body, receiver);
+            }
+        }
+    }
+
+
+
+    pub fn start_define_enum(&mut self, context : data::Context, range : data::Range, name : String, variants : Vec<(String, Vec<(Option<String>, data::Node)>)>) -> () {
+        let mut names = Vec::new();
+        for (variant, payload) in variants {
+            if (names.contains(&variant)) {
+                self.invalid(
+                    exception::ValidatorExceptionType::Name,
+                    format!("Variant `{}` of enum `{}` is already defined.", variant, name),
+                    range.clone(),
+                    context.clone()
+                );
+            }
+            let mut fields = Vec::new();
+            for (field, typ) in payload {
+                if (let Some(field) = field) {
+                    if (fields.contains(&field)) {
+                        self.invalid(
+                            exception::ValidatorExceptionType::Name,
+                            format!("Field `{}` of variant `{}::{}` is already defined.", field, name, variant),
+                            typ.range,
+                            context.clone()
+                        );
+                    }
+                    fields.push(field);
+                }
+            }
+            names.push(variant);
+        }
+    }
 
-        panic!("");
+
+
+    pub fn start_define_trait(&mut self, context : data::Context, range : data::Range, name : String, methods : Vec<data::Node>) -> () {
+        let trait_context = generic_context(context.clone(), &vec![(String::from("Self"), vec![name.clone()])]);
+        let mut names     = Vec::new();
+        for method in methods {
+            if (let data::NodeType::DefineFunction(method_name, generics, args, return_type, _) = method.node) {
+                if (names.contains(&method_name)) {
+                    self.invalid(
+                        exception::ValidatorExceptionType::Name,
+                        format!("Method `{}` of trait `{}` is already defined.", method_name, name),
+                        method.range.clone(),
+                        context.clone()
+                    );
+                }
+                let method_context = data::Context::new(method_name.clone(), Some((range.clone(), trait_context.clone())));
+                self.start_generics(method_context, method.range.clone(), &generics);
+                self.function_type(trait_context.clone(), &method.range, &generics, &args, &return_type);
+                names.push(method_name);
+            }
+        }
     }
- 
 
 
-    pub fn start_local_import(&mut self, context : data::Context, range : data::Range, name : String) -> data::Object {
-        /*if (self.contains_name(name.clone())) {
-            exception::ValidatorException::new(
+
+    // The methods of an implementation are checked with the rest of its
+    // class, here they are compared to the signatures of the trait.
+    pub fn start_implement_trait(&mut self, context : data::Context, range : data::Range, name : String, class : String, methods : Vec<data::Node>) -> () {
+        let trait_methods = if (let Some(data::ObjectType::Trait(trait_methods, _)) = context.get_name(&name).map(|object| object.value)) {
+            trait_methods
+        } else {
+            return;
+        };
+        let object = context.get_name(&class).map(|object| object.value);
+        let (generics, order) = if (let Some(data::ObjectType::Class(generics, _, order, _, _)) = object.clone()) {
+            (generics, order)
+        } else {
+            return;
+        };
+        let class_context = generic_context(self_context(context.clone(), object), &generics);
+        let trait_context = generic_context(context.clone(), &vec![(String::from("Self"), vec![name.clone()])]);
+        let mut arguments = std::collections::HashMap::new();
+        arguments.insert(String::from("Self"), data::ValueType::Class(order, generics.iter().map(|(generic, _)| data::ValueType::Parameter(generic.clone())).collect()));
+
+        let mut expected = Vec::new();
+        for method in trait_methods {
+            if (let data::NodeType::DefineFunction(method_name, method_generics, args, return_type, _) = method.node) {
+                let method_type = self.function_type(trait_context.clone(), &method.range, &method_generics, &args, &return_type);
+                expected.push((method_name, method_type.substitute(&arguments)));
+            }
+        }
+        for method in &methods {
+            if (let data::NodeType::DefineFunction(method_name, method_generics, args, return_type, _) = &method.node) {
+                let method_type = self.function_type(class_context.clone(), &method.range, method_generics, args, return_type);
+                match (expected.iter().find(|(other, _)| other == method_name)) {
+                    Some((_, expected_type)) => if (*expected_type != method_type || method.headers.has("static")) {
+                        self.invalid(
+                            exception::ValidatorExceptionType::Type,
+                            format!("Method `{}` of class `{}` does not match trait `{}`. Expected `{}`, found {}`{}`.",
+                                method_name, class, name, expected_type,
+                                if (method.headers.has("static")) {"static "} else {""}, method_type
+                            ),
+                            method.range.clone(),
+                            context.clone()
+                        );
+                    },
+                    None                     => {
+                        self.invalid(
+                            exception::ValidatorExceptionType::Name,
+                            format!("Method `{}` is not a member of trait `{}`.", method_name, name),
+                            method.range.clone(),
+                            context.clone()
+                        );
+                    }
+                }
+            }
+        }
+        let missing = expected.iter().filter(|(method_name, _)| ! methods.iter().any(|method| matches!(&method.node, data::NodeType::DefineFunction(other, ..) if other == method_name))).map(|(method_name, _)| format!("`{}`", method_name)).collect::<Vec<String>>();
+        if (missing.len() >= 1) {
+            self.invalid(
                 exception::ValidatorExceptionType::Name,
-                format!("Name `{}` is already defined.", name),
-                range.clone()
-            ).dump_invalid();
-        }*/
+                format!("Class `{}` does not implement {} of trait `{}`.", class, missing.join(", "), name),
+                range,
+                context
+            );
+        }
+    }
+
+
+
+    // The type of the function or method defined at `range`. Its signature
+    // is resolved once, so that its errors are not reported at every use.
+    pub fn function_type(&mut self, context : data::Context, range : &data::Range, generics : &Vec<(String, Vec<String>)>, args : &Vec<(String, data::Node)>, return_type : &data::Node) -> data::ValueType {
+        if (let Some(signature) = self.signatures.get(&check_key(range))) {
+            return signature.clone();
+        }
+        let     context   = generic_context(context, generics);
+        let mut arg_types = Vec::new();
+        for (_, typ) in args {
+            arg_types.push(self.resolve_type(context.clone(), typ.clone()));
+        }
+        let return_type = self.resolve_type(context, return_type.clone());
+        let signature   = generalize(generics.clone(), data::ValueType::Func(arg_types, Box::new(return_type)));
+        self.signatures.insert(check_key(range), signature.clone());
+        return signature;
+    }
+
+
+
+    // Declares the type parameters of a function or class in its context.
+    fn start_generics(&mut self, context : data::Context, range : data::Range, generics : &[(String, Vec<String>)]) -> () {
+        for (i, (generic, bounds)) in generics.iter().enumerate() {
+            if (generics[..i].iter().any(|(other, _)| other == generic)) {
+                self.invalid(
+                    exception::ValidatorExceptionType::Name,
+                    format!("Type parameter `{}` is already defined.", generic),
+                    range.clone(),
+                    context.clone()
+                );
+            }
+            for bound in bounds {
+                if (! matches!(context.get_name(bound).map(|object| object.value), Some(data::ObjectType::Trait(_, _)))) {
+                    self.invalid(
+                        exception::ValidatorExceptionType::Name,
+                        format!("Trait `{}` is not defined.", bound),
+                        range.clone(),
+                        context.clone()
+                    );
+                }
+            }
+            context.set_name(generic.clone(), data::Object::new(data::ObjectType::Parameter(bounds.clone()), range.clone()));
+        }
+    }
 
-        panic!("");
+
+
+    // Checks that the type arguments of `owner` implement the bounds of its
+    // type parameters.
+    fn expect_bounds(&mut self, context : data::Context, generics : &Vec<(String, Vec<String>)>, arguments : &std::collections::HashMap<String, data::ValueType>, owner : &String, range : data::Range) -> () {
+        for (generic, bounds) in generics {
+            if (let Some(argument) = arguments.get(generic)) {
+                for bound in bounds {
+                    if (! implements(&context, argument, bound)) {
+                        self.invalid(
+                            exception::ValidatorExceptionType::Type,
+                            format!("Type `{}` does not implement trait `{}`, required by `{}` of `{}`.", argument, bound, generic, owner),
+                            range.clone(),
+                            context.clone()
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+
+
+    // The fields of the classes in `order`, bases first : name, type, has_default, owner.
+    // The types use the type parameters of their class.
+    pub fn class_fields(&mut self, context : data::Context, order : &[String]) -> Vec<(String, data::ValueType, bool, String)> {
+        let mut fields : Vec<(String, data::ValueType, bool, String)> = Vec::new();
+        for class in order.iter().rev() {
+            let object = self.lookup(&context, class).map(|object| object.value);
+            if (let Some(data::ObjectType::Class(generics, _, _, class_fields, _)) = object.clone()) {
+                let class_context = generic_context(self_context(self.owner(&context, class), object), &generics);
+                for (field, typ, default) in class_fields {
+                    if (! fields.iter().any(|(name, _, _, _)| *name == field)) {
+                        let resolved = self.resolve_type(class_context.clone(), typ);
+                        fields.push((field, resolved, default.is_some(), class.clone()));
+                    }
+                }
+            }
+        }
+        return fields;
     }
- 
 
 
-    pub fn start_define_function(&mut self, context : data::Context, range : data::Range, name : String, args : Vec<(String, data::Node)>, return_type : data::Node, body : Vec<data::Node>) -> data::Object {
-        panic!("");
+
+    // The methods of the classes in `order`, the first definition wins : name, is_static, type, owner.
+    pub fn class_methods(&mut self, context : data::Context, order : &Vec<String>) -> Vec<(String, bool, data::ValueType, String)> {
+        let mut methods : Vec<(String, bool, data::ValueType, String)> = Vec::new();
+        for class in order {
+            let object = self.lookup(&context, class).map(|object| object.value);
+            if (let Some(data::ObjectType::Class(generics, _, _, _, class_methods)) = object.clone()) {
+                let class_context = generic_context(self_context(self.owner(&context, class), object), &generics);
+                for method in class_methods {
+                    if (let data::NodeType::DefineFunction(name, method_generics, args, return_type, _) = method.node
+                        && ! methods.iter().any(|(other, _, _, _)| *other == name)
+                    ) {
+                        let method_type = self.function_type(class_context.clone(), &method.range, &method_generics, &args, &return_type);
+                        methods.push((name, method.headers.has("static"), method_type, class.clone()));
+                    }
+                }
+            }
+        }
+        return methods;
     }
- 
 
 
-    pub fn start_initialize_variable(&mut self, context : data::Context, range : data::Range, mutable : bool, name : String, typ : data::Node, value : Option<data::Node>) -> data::Object {
-        panic!("");
+
+    fn class_generics(&self, context : &data::Context, class : &String) -> Vec<(String, Vec<String>)> {
+        return if (let Some(data::ObjectType::Class(generics, _, _, _, _)) = self.lookup(context, class).map(|object| object.value)) {
+            generics
+        } else {
+            Vec::new()
+        };
+    }
+
+
+
+    // `Name(...)` calls `init` if the class has one, else it takes every
+    // field without a default, bases first.
+    // The type parameters of a generic class are worked out from the arguments.
+    pub fn class_constructor(&mut self, context : data::Context, order : &Vec<String>) -> data::ValueType {
+        let generics = self.class_generics(&context, &order[0]);
+        let instance = data::ValueType::Class(order.clone(), generics.iter().map(|(generic, _)| data::ValueType::Parameter(generic.clone())).collect());
+        let init     = self.class_methods(context.clone(), order).into_iter().find(|(name, _, _, _)| name == "init").map(|(_, _, typ, _)| typ);
+        let (generics, args) = match (init) {
+            Some(data::ValueType::Func(args, _))               => (generics, args),
+            Some(data::ValueType::Generic(init_generics, typ)) => (
+                [generics, init_generics].concat(),
+                if (let data::ValueType::Func(args, _) = *typ) {args} else {Vec::new()}
+            ),
+            _                                                  => (
+                generics,
+                self.class_fields(context, order).into_iter().filter(|(_, _, has_default, _)| ! has_default).map(|(_, typ, _, _)| typ).collect()
+            )
+        };
+        return generalize(generics, data::ValueType::Func(args, Box::new(instance)));
+    }
+
+
+
+    // Returns whether the end of the body can never be reached, because every
+    // path returns or jumps away first. The final statement of a `tail` body
+    // is the result of the function.
+    pub fn start_body(&mut self, context : data::Context, body : Vec<data::Node>, tail : bool) -> bool {
+        let mut returns = false;
+        let     count   = body.len();
+        for (i, node) in body.into_iter().enumerate() {
+            if (self.start_statement(context.clone(), node, tail && i + 1 == count)) {
+                returns = true;
+            }
+        }
+        return returns;
+    }
+
+
+
+    pub fn start_block(&mut self, context : data::Context, range : data::Range, body : Vec<data::Node>, tail : bool) -> bool {
+        let block_context = data::Context::new(context.name.clone(), Some((range, context)));
+        return self.start_body(block_context, body, tail);
+    }
+
+
+    // The type of the final expression of a block, or `Crash` if the block
+    // never ends.
+    pub fn start_block_value(&mut self, context : data::Context, range : data::Range, body : Vec<data::Node>) -> data::ValueType {
+        let block_context = data::Context::new(context.name.clone(), Some((range, context)));
+        return self.start_body_value(block_context, body);
+    }
+
+    pub fn start_body_value(&mut self, block_context : data::Context, body : Vec<data::Node>) -> data::ValueType {
+        let mut body          = body;
+        let     last          = body.pop();
+        let mut returns       = self.start_body(block_context.clone(), body, false);
+        let mut value_type    = data::ValueType::Void;
+        if (let Some(last) = last) {
+            if (let data::NodeType::BlockResult(value) = last.node) {
+                value_type = self.start_expression(block_context, *value);
+            } else {
+                returns |= self.start_statement(block_context, last, false);
+            }
+        }
+        return if (returns) {data::ValueType::Crash} else {value_type};
+    }
+
+
+
+    pub fn start_statement(&mut self, context : data::Context, node : data::Node, tail : bool) -> bool {
+        return match (node.node.clone()) {
+            data::NodeType::InitializeVariable(mutable, name, typ, value) => {
+                self.start_initialize_variable(context, node.range, mutable, name, *typ, *value);
+                false
+            },
+            data::NodeType::If(branches, fallback)                        => self.start_control_if(context, node.range, *branches, *fallback, tail),
+            data::NodeType::While(label, condition, body)                 => self.start_control_while(context, node.range, label, *condition, *body),
+            data::NodeType::Loop(label, body)                             => self.start_control_loop(context, node.range, label, *body),
+            data::NodeType::For(label, name, iterable, body)              => self.start_control_for(context, node.range, label, name, *iterable, *body),
+            data::NodeType::Break(label)                                  => self.start_control_jump(context, node.range, label, true),
+            data::NodeType::Continue(label)                               => self.start_control_jump(context, node.range, label, false),
+            data::NodeType::Return(value)                                 => self.start_control_return(context, node.range, *value),
+            data::NodeType::Match(value, arms)                            => self.start_control_match(context, node.range, *value, *arms, tail),
+            data::NodeType::BlockResult(value)                            => {
+                if (tail) {
+                    self.start_control_return(context, node.range, Some(*value))
+                } else {
+                    self.start_expression(context, *value);
+                    false
+                }
+            },
+            // A call that does not return, like `scope::return(value)`, leaves the function.
+            _                                                             => self.start_expression(context, node) == data::ValueType::Crash
+        };
+    }
+
+
+
+    pub fn start_initialize_variable(&mut self, context : data::Context, range : data::Range, mutable : bool, name : String, typ : data::Node, value : Option<data::Node>) -> () {
+        let mut resolved = if (matches!(typ.node, data::NodeType::Type(data::Type::Inferred, _))) {
+            None
+        } else {
+            Some(self.resolve_type(context.clone(), typ))
+        };
+        if (let Some(value) = value) {
+            let value_type = self.start_expression(context.clone(), value.clone());
+            if (let Some(resolved) = &resolved) {
+                self.expect_value(context.clone(), resolved, &value_type, &value);
+            } else {
+                // Only an immutable name keeps the bounds of its value.
+                resolved = Some(if (mutable) {value_type.widened()} else {value_type});
+            }
+        }
+        context.set_name(name, data::Object::new(data::ObjectType::Variable(mutable, resolved.unwrap_or(data::ValueType::Unknown)), range));
+    }
+
+
+
+    pub fn start_condition(&mut self, context : data::Context, condition : data::Node) -> () {
+        let range = condition.range.clone();
+        let typ   = self.start_expression(context.clone(), condition);
+        self.expect_type(context, &data::ValueType::Bool, &typ, range);
+    }
+
+
+
+    pub fn start_control_if(&mut self, context : data::Context, range : data::Range, branches : Vec<(data::Node, Vec<data::Node>)>, fallback : Option<Vec<data::Node>>, tail : bool) -> bool {
+        let mut returns = true;
+        for (condition, body) in branches {
+            self.start_condition(context.clone(), condition);
+            returns &= self.start_block(context.clone(), range.clone(), body, tail);
+        }
+        return if (let Some(body) = fallback) {
+            self.start_block(context, range, body, tail) && returns
+        } else {
+            false
+        };
+    }
+
+
+
+    pub fn start_control_while(&mut self, context : data::Context, range : data::Range, label : Option<String>, condition : data::Node, body : Vec<data::Node>) -> bool {
+        self.start_condition(context.clone(), condition);
+        self.loops.push((label, false));
+        self.start_block(context, range, body, false);
+        self.loops.pop();
+        return false;
+    }
+
+
+
+    // A `loop` that is never broken out of does not end.
+    pub fn start_control_loop(&mut self, context : data::Context, range : data::Range, label : Option<String>, body : Vec<data::Node>) -> bool {
+        self.loops.push((label, false));
+        self.start_block(context, range, body, false);
+        let (_, broken) = self.loops.pop().unwrap();
+        return ! broken;
+    }
+
+
+
+    pub fn start_control_for(&mut self, context : data::Context, range : data::Range, label : Option<String>, name : String, iterable : data::Node, body : Vec<data::Node>) -> bool {
+        let iterable_range = iterable.range.clone();
+        let iterable_type  = self.start_expression(context.clone(), iterable);
+        let item_type      = match (iterable_type) {
+            data::ValueType::IntRange                              => data::ValueType::Int,
+            data::ValueType::FloatRange                            => data::ValueType::Float,
+            data::ValueType::List(item) | data::ValueType::Array(item, _) => *item,
+            data::ValueType::Dict(key, _)                          => *key,
+            data::ValueType::Unknown                               => data::ValueType::Unknown,
+            _                                                      => {
+                self.invalid(
+                    exception::ValidatorExceptionType::Type,
+                    format!("Can not iterate over `{}`. Expected `IntRange`, `FloatRange`, `List`, `Array`, `Dict`.", iterable_type),
+                    iterable_range.clone(),
+                    context.clone()
+                );
+                data::ValueType::Unknown
+            }
+        };
+        let loop_context = data::Context::new(context.name.clone(), Some((range.clone(), context)));
+        loop_context.set_name(name, data::Object::new(data::ObjectType::Variable(false, item_type), iterable_range));
+        self.loops.push((label, false));
+        self.start_block(loop_context, range, body, false);
+        self.loops.pop();
+        return false;
+    }
+
+
+
+    // Returns whether every arm returns. A match that is not exhaustive is
+    // already reported, so the values it misses are not counted again.
+    pub fn start_control_match(&mut self, context : data::Context, range : data::Range, value : data::Node, arms : Vec<(data::Node, Vec<data::Node>)>, tail : bool) -> bool {
+        let     arms    = self.start_match_arms(context, range.clone(), value, arms);
+        let mut returns = true;
+        for (arm_context, body) in arms {
+            returns &= self.start_block(arm_context, range.clone(), body, tail);
+        }
+        return returns;
+    }
+
+
+    // The arms of a `match` expression give its value. They can not jump
+    // out of it.
+    pub fn start_match_expression(&mut self, context : data::Context, range : data::Range, value : data::Node, arms : Vec<(data::Node, Vec<data::Node>)>) -> data::ValueType {
+        let     arms      = self.start_match_arms(context, range.clone(), value, arms);
+        let     barrier   = self.barrier.replace(self.loops.len());
+        let mut result    : Option<data::ValueType> = None;
+        for (arm_context, body) in arms {
+            let arm_range = body.last().map(|node| node.range.clone()).unwrap_or(range.clone());
+            let arm_type  = self.start_block_value(arm_context.clone(), range.clone(), body);
+            if (let Some(result) = &result && *result != data::ValueType::Crash) {
+                self.expect_type(arm_context, result, &arm_type, arm_range);
+            } else {
+                result = Some(arm_type);
+            }
+        }
+        self.barrier = barrier;
+        return result.unwrap_or(data::ValueType::Void);
+    }
+
+
+    // Checks the value and the patterns, and reports any values that no arm
+    // matches. Returns every arm with the names its pattern binds.
+    fn start_match_arms(&mut self, context : data::Context, range : data::Range, value : data::Node, arms : Vec<(data::Node, Vec<data::Node>)>) -> Vec<(data::Context, Vec<data::Node>)> {
+        let value_type = self.start_expression(context.clone(), value);
+        let mut rows   = Vec::new();
+        let mut result = Vec::new();
+        for (pattern_node, body) in arms {
+            let pattern = if (let data::NodeType::Pattern(pattern) = pattern_node.node.clone()) {
+                pattern
+            } else {
+                exception::InternalException::new(
+                    String::from("Invalid pattern node.")
+                ).dump_critical();
+            };
+            let mut bindings = Vec::new();
+            self.start_pattern(context.clone(), &pattern, &value_type, pattern_node.range.clone(), &mut bindings);
+            let arm_context = data::Context::new(context.name.clone(), Some((range.clone(), context.clone())));
+            for (name, typ) in bindings {
+                arm_context.set_name(name, data::Object::new(data::ObjectType::Variable(false, typ), pattern_node.range.clone()));
+            }
+            rows.push(vec![pattern]);
+            result.push((arm_context, body));
+        }
+        let missing = self.match_witnesses(context.clone(), rows, vec![value_type]);
+        if (missing.len() >= 1) {
+            self.invalid(
+                exception::ValidatorExceptionType::Pattern,
+                format!("Match is not exhaustive. Missing {}.", missing.iter().map(|witness| format!("`{}`", witness[0])).collect::<Vec<String>>().join(", ")),
+                range,
+                context
+            );
+        }
+        return result;
+    }
+
+
+    // Checks that `pattern` can match a value of type `typ`, and collects the
+    // names it binds.
+    fn start_pattern(&mut self, context : data::Context, pattern : &data::Pattern, typ : &data::ValueType, range : data::Range, bindings : &mut Vec<(String, data::ValueType)>) -> () {
+        match (pattern) {
+
+            data::Pattern::Wildcard => {},
+
+            data::Pattern::Binding(name) => {
+                if (bindings.iter().any(|(other, _)| other == name)) {
+                    self.invalid(
+                        exception::ValidatorExceptionType::Name,
+                        format!("Name `{}` is bound more than once in the pattern.", name),
+                        range.clone(),
+                        context.clone()
+                    );
+                }
+                bindings.push((name.clone(), typ.clone()));
+            },
+
+            data::Pattern::Literal(literal) => {
+                let literal_type = literal_type(literal);
+                self.expect_type(context, typ, &literal_type, range);
+            },
+
+            data::Pattern::Range(min, max) => {
+                let min_type = literal_type(min);
+                let max_type = literal_type(max);
+                if (min_type != max_type || ! (min_type.is_numeric() || min_type == data::ValueType::Char)) {
+                    self.invalid(
+                        exception::ValidatorExceptionType::Pattern,
+                        format!("Range pattern bounds must both be `Int`, `Float` or `Char`. Found `{}` and `{}`.", min_type, max_type),
+                        range,
+                        context
+                    );
+                    return;
+                }
+                self.expect_type(context, typ, &min_type, range);
+            },
+
+            data::Pattern::Variant(name, variant, payload, rest) => {
+                let global = self.resolve_global(context.clone(), &name_path(name, range.clone()));
+                let (enumeration, variants) = if (let Some((owner, local, data::ObjectType::Enum(variants))) = global) {
+                    (self.qualify(&owner, &local), variants)
+                } else if (name.contains("::")) {
+                    return;
+                } else {
+                    self.invalid(
+                        exception::ValidatorExceptionType::Name,
+                        format!("Enum `{}` is not defined.", name),
+                        range,
+                        context
+                    );
+                    return;
+                };
+                self.expect_type(context.clone(), typ, &data::ValueType::Enum(enumeration), range.clone());
+                let fields = if (let Some((_, fields)) = variants.into_iter().find(|(other, _)| other == variant)) {
+                    fields
+                } else {
+                    self.invalid(
+                        exception::ValidatorExceptionType::Name,
+                        format!("Enum `{}` has no variant `{}`.", name, variant),
+                        range,
+                        context
+                    );
+                    return;
+                };
+                let named = fields.iter().any(|(field, _)| field.is_some());
+                if (named) {
+                    for (field, sub_pattern) in payload {
+                        let field_type = fields.iter().find(|(other, _)| other == field).map(|(_, typ)| typ.clone());
+                        if (let Some(field_type) = field_type) {
+                            self.start_pattern(context.clone(), sub_pattern, &field_type, range.clone(), bindings);
+                        } else {
+                            self.invalid(
+                                exception::ValidatorExceptionType::Pattern,
+                                format!("Variant `{}::{}` has no field `{}`.", name, variant, field.clone().unwrap_or_default()),
+                                range.clone(),
+                                context.clone()
+                            );
+                        }
+                    }
+                    let missing = fields.iter().filter(|(field, _)| ! payload.iter().any(|(other, _)| other == field)).map(|(field, _)| format!("`{}`", field.clone().unwrap_or_default())).collect::<Vec<String>>();
+                    if (missing.len() >= 1 && ! rest) {
+                        self.invalid(
+                            exception::ValidatorExceptionType::Pattern,
+                            format!("Pattern `{}` is missing the fields {}. Use `..` to leave them out.", pattern, missing.join(", ")),
+                            range,
+                            context
+                        );
+                    }
+                } else {
+                    if (payload.iter().any(|(field, _)| field.is_some())
+                        || payload.len() > fields.len()
+                        || (payload.len() < fields.len() && ! rest)
+                    ) {
+                        self.invalid(
+                            exception::ValidatorExceptionType::Pattern,
+                            format!("Pattern `{}` does not match the payload of `{}::{}`. Expected {} values.", pattern, name, variant, fields.len()),
+                            range,
+                            context
+                        );
+                        return;
+                    }
+                    for ((_, sub_pattern), (_, field_type)) in payload.iter().zip(fields.iter()) {
+                        self.start_pattern(context.clone(), sub_pattern, field_type, range.clone(), bindings);
+                    }
+                }
+            }
+
+        }
+    }
+
+
+    // The values that no row of patterns matches, one pattern per column.
+    // Enums and `Bool` are split into each of their constructors, any other
+    // type can only be covered by a wildcard or a binding.
+    fn match_witnesses(&mut self, context : data::Context, rows : Vec<Vec<data::Pattern>>, types : Vec<data::ValueType>) -> Vec<Vec<data::Pattern>> {
+        if (types.len() <= 0) {
+            return if (rows.len() <= 0) {vec![Vec::new()]} else {Vec::new()};
+        }
+        if (rows.len() <= 0) {
+            return vec![vec![data::Pattern::Wildcard; types.len()]];
+        }
+        if (rows.iter().any(|row| row.iter().all(is_wildcard))) {
+            return Vec::new();
+        }
+
+        // An unchecked value takes the type of the patterns used on it.
+        let mut typ = types[0].clone();
+        if (typ == data::ValueType::Unknown) {
+            for row in &rows {
+                match (&row[0]) {
+                    data::Pattern::Literal(data::Literal::Bool(_)) => typ = data::ValueType::Bool,
+                    data::Pattern::Variant(name, _, _, _)          => {
+                        // The path of an enum of another module is checked with the pattern.
+                        let mut owner = context.clone();
+                        let mut parts = name.split("::").map(|part| part.to_string()).collect::<Vec<String>>();
+                        let local     = parts.pop().unwrap_or_default();
+                        for part in parts {
+                            if (let Some(data::ObjectType::LocalModule(key)) = owner.get_name(&part).map(|object| object.value)) {
+                                owner = self.modules[&key].clone();
+                            }
+                        }
+                        typ = data::ValueType::Enum(self.qualify(&owner, &local));
+                    },
+                    _                                              => continue
+                }
+                break;
+            }
+        }
+        let constructors : Vec<(data::Pattern, Vec<Option<String>>, Vec<data::ValueType>)> = match (&typ) {
+            data::ValueType::Bool       => vec![
+                (data::Pattern::Literal(data::Literal::Bool(true)), Vec::new(), Vec::new()),
+                (data::Pattern::Literal(data::Literal::Bool(false)), Vec::new(), Vec::new())
+            ],
+            data::ValueType::Enum(name) => {
+                if (let Some(data::ObjectType::Enum(variants)) = self.lookup(&context, name).map(|object| object.value)) {
+                    variants.into_iter().map(|(variant, payload)| (
+                        data::Pattern::Variant(name.clone(), variant, Vec::new(), false),
+                        payload.iter().map(|(field, _)| field.clone()).collect(),
+                        payload.into_iter().map(|(_, typ)| typ).collect()
+                    )).collect()
+                } else {
+                    Vec::new()
+                }
+            },
+            _                           => Vec::new()
+        };
+
+        let mut witnesses = Vec::new();
+        // Literals split their type into the values that the same rows
+        // match, and each part that is missing is named.
+        if (let Some(parts) = literal_parts(&rows) && constructors.len() <= 0) {
+            for (head, value) in parts {
+                let rest = rows.iter().filter(|row| pattern_covers(&row[0], &value)).map(|row| row[1..].to_vec()).collect();
+                for witness in self.match_witnesses(context.clone(), rest, types[1..].to_vec()) {
+                    witnesses.push([vec![head.clone()], witness].concat());
+                }
+            }
+            return witnesses;
+        }
+        if (constructors.len() <= 0) {
+            let rest = rows.into_iter().filter(|row| is_wildcard(&row[0])).map(|row| row[1..].to_vec()).collect();
+            for witness in self.match_witnesses(context.clone(), rest, types[1..].to_vec()) {
+                witnesses.push([vec![data::Pattern::Wildcard], witness].concat());
+            }
+            return witnesses;
+        }
+        for (i, (constructor, fields, field_types)) in constructors.iter().cloned().enumerate() {
+            if (constructors[..i].iter().any(|(other, _, _)| other.to_string() == constructor.to_string())) {
+                continue;
+            }
+            let mut specialized = Vec::new();
+            for row in &rows {
+                let arguments = match (&row[0], &constructor) {
+                    (head, _) if is_wildcard(head) => vec![data::Pattern::Wildcard; fields.len()],
+                    (data::Pattern::Literal(data::Literal::Bool(a)), data::Pattern::Literal(data::Literal::Bool(b))) if a == b => Vec::new(),
+                    (data::Pattern::Variant(_, a, payload, _), data::Pattern::Variant(_, b, _, _)) if a == b => {
+                        // Fields that are left out match anything.
+                        fields.iter().enumerate().map(|(i, field)| if (field.is_some()) {
+                            payload.iter().find(|(other, _)| other == field).map(|(_, pattern)| pattern.clone()).unwrap_or(data::Pattern::Wildcard)
+                        } else {
+                            payload.get(i).map(|(_, pattern)| pattern.clone()).unwrap_or(data::Pattern::Wildcard)
+                        }).collect()
+                    },
+                    _ => continue
+                };
+                specialized.push([arguments, row[1..].to_vec()].concat());
+            }
+            let column_types = [field_types, types[1..].to_vec()].concat();
+            for witness in self.match_witnesses(context.clone(), specialized, column_types) {
+                let (arguments, rest) = witness.split_at(fields.len());
+                let head = if (let data::Pattern::Variant(name, variant, _, _) = &constructor) {
+                    data::Pattern::Variant(name.clone(), variant.clone(), fields.iter().cloned().zip(arguments.iter().cloned()).collect(), false)
+                } else {
+                    constructor.clone()
+                };
+                witnesses.push([vec![head], rest.to_vec()].concat());
+            }
+        }
+        return witnesses;
+    }
+
+
+
+    pub fn start_control_jump(&mut self, context : data::Context, range : data::Range, label : Option<String>, is_break : bool) -> bool {
+        let target = if (let Some(name) = &label) {
+            self.loops.iter().rposition(|(label, _)| label.as_ref() == Some(name))
+        } else {
+            self.loops.len().checked_sub(1)
+        };
+        if (let Some(target) = target) {
+            if (let Some(barrier) = self.barrier && target < barrier) {
+                self.invalid(
+                    exception::ValidatorExceptionType::ControlFlow,
+                    String::from("`break` and `continue` can not leave a `match` expression."),
+                    range.clone(),
+                    context.clone()
+                );
+            }
+            if (is_break) {
+                self.loops[target].1 = true;
+            }
+        }
+        else if (self.loops.len() <= 0) {
+            self.invalid(
+                exception::ValidatorExceptionType::ControlFlow,
+                String::from("`break` and `continue` are only allowed inside of a loop."),
+                range,
+                context
+            );
+        }
+        else {
+            self.invalid(
+                exception::ValidatorExceptionType::ControlFlow,
+                format!("Loop label `{}` is not defined.", label.unwrap_or_default()),
+                range,
+                context
+            );
+        }
+        return true;
+    }
+
+
+
+    pub fn start_control_return(&mut self, context : data::Context, range : data::Range, value : Option<data::Node>) -> bool {
+        let value_type = if (let Some(value) = &value) {
+            self.start_expression(context.clone(), value.clone())
+        } else {
+            data::ValueType::Void
+        };
+        if (self.barrier.is_some()) {
+            self.invalid(
+                exception::ValidatorExceptionType::ControlFlow,
+                String::from("`return` can not leave a `match` expression."),
+                range.clone(),
+                context.clone()
+            );
+        }
+        let return_type = self.return_type.clone();
+        if (let Some(value) = &value) {
+            self.expect_value(context, &return_type, &value_type, value);
+        } else {
+            self.expect_type(context, &return_type, &value_type, range);
+        }
+        return true;
+    }
+
+
+
+    pub fn start_expression(&mut self, context : data::Context, node : data::Node) -> data::ValueType {
+        if (let Some((operator, left, right)) = node.node.binary_operation()) {
+            return self.start_binary_operation(context, node.range, operator, left, right);
+        }
+        return match (node.node) {
+
+            data::NodeType::AssignVariable(target, operator, value) => self.start_assign_variable(context, *target, operator, *value),
+
+            data::NodeType::OppositeOperation(value) => {
+                let value_range = value.range.clone();
+                let value_type  = self.start_expression(context.clone(), *value);
+                if (value_type.is_numeric() || value_type == data::ValueType::Unknown) {
+                    value_type.refined_opposite().unwrap_or(value_type.widened())
+                } else {
+                    self.invalid(
+                        exception::ValidatorExceptionType::Type,
+                        format!("Operator `-` can not be applied to `{}`.", value_type),
+                        value_range,
+                        context
+                    );
+                    data::ValueType::Unknown
+                }
+            },
+            data::NodeType::InvertOperation(value) => {
+                let value_range = value.range.clone();
+                let value_type  = self.start_expression(context.clone(), *value);
+                self.expect_type(context, &data::ValueType::Bool, &value_type, value_range);
+                data::ValueType::Bool
+            },
+
+            data::NodeType::ModuleMember(parent, member) => self.start_module_member(context, node.range, *parent, member),
+
+            data::NodeType::ClassMember(parent, member) => self.start_class_member(context, node.range, *parent, member),
+
+            data::NodeType::Slice(parent, index) => self.start_slice(context, *parent, *index),
+
+            data::NodeType::Call(parent, args) => self.start_call(context, node.range, *parent, *args),
+
+            data::NodeType::Match(value, arms) => self.start_match_expression(context, node.range, *value, *arms),
+
+            data::NodeType::Function(args, return_type, body, _) => self.start_function_expression(context, node.range, *args, *return_type, *body),
+
+            data::NodeType::BoundsCheck(value, bounds) => {
+                self.start_expression(context, *value);
+                bounds
+            },
+
+            data::NodeType::Literal(literal) => match (literal) {
+                data::Literal::Name(name)   => self.start_name(context, node.range, name),
+                data::Literal::Bool(_)      => data::ValueType::Bool,
+                data::Literal::Character(_) => data::ValueType::Char,
+                data::Literal::String(_)    => data::ValueType::String,
+                data::Literal::Integer(_)   => data::ValueType::Int,
+                data::Literal::Float(_)     => data::ValueType::Float
+            },
+
+            data::NodeType::Range(min, _, _) => {
+                if (matches!(min.node, data::NodeType::Literal(data::Literal::Integer(_)))) {
+                    data::ValueType::IntRange
+                } else {
+                    data::ValueType::FloatRange
+                }
+            },
+
+            data::NodeType::Tuple(values) => {
+                let mut types = Vec::new();
+                for value in *values {
+                    types.push(self.start_expression(context.clone(), value));
+                }
+                data::ValueType::Tuple(types)
+            },
+
+            data::NodeType::Array(values) => {
+                let length    = values.len();
+                let item_type = self.start_items(context, *values);
+                data::ValueType::Array(Box::new(item_type), length)
+            },
+
+            data::NodeType::Dict(entries) => {
+                let mut keys   = Vec::new();
+                let mut values = Vec::new();
+                for (key, value) in *entries {
+                    keys.push(key);
+                    values.push(value);
+                }
+                let key_type   = self.start_items(context.clone(), keys);
+                let value_type = self.start_items(context, values);
+                data::ValueType::Dict(Box::new(key_type), Box::new(value_type))
+            },
+
+            _ => {
+                exception::InternalException::new(
+                    String::from("Invalid expression node.")
+                ).dump_critical();
+            }
+
+        };
+    }
+
+
+
+    // An anonymous function is checked like a function defined in place. It
+    // can use the names around it, see `capture`.
+    fn start_function_expression(&mut self, context : data::Context, range : data::Range, args : Vec<(String, data::Node)>, return_type : data::Node, body : Vec<data::Node>) -> data::ValueType {
+        let function_context = data::Context::new(String::from("func"), Some((range.clone(), context)));
+        let mut arg_types = Vec::new();
+        for (arg, typ) in args {
+            let resolved = self.resolve_type(function_context.clone(), typ.clone());
+            arg_types.push(resolved.clone());
+            function_context.set_name(arg, data::Object::new(data::ObjectType::Variable(false, resolved), typ.range));
+        }
+        let return_type = self.resolve_type(function_context.clone(), return_type);
+        let loops       = std::mem::take(&mut self.loops);
+        let outer       = std::mem::replace(&mut self.return_type, return_type.clone());
+        let barrier     = self.barrier.take();
+        self.closures.push((function_context.clone(), Vec::new()));
+        let returns     = self.start_body(function_context.clone(), body, true);
+        if (let Some((_, names)) = self.closures.pop()) {
+            self.captures.insert(check_key(&range), names);
+        }
+        self.loops       = loops;
+        self.return_type = outer;
+        self.barrier     = barrier;
+        if (! returns && ! [data::ValueType::Void, data::ValueType::Unknown].contains(&return_type)) {
+            self.invalid(
+                exception::ValidatorExceptionType::ControlFlow,
+                format!("Not every path of the anonymous function returns a value of type `{}`.", return_type),
+                range,
+                function_context
+            );
+        }
+        return data::ValueType::Func(arg_types, Box::new(return_type));
+    }
+
+
+
+    // An anonymous function copies the immutable local names it uses from
+    // around it when it is created. Names declared with `let mut` are shared
+    // with the scope they are declared in instead, and the globals of a
+    // module are not captured at all.
+    fn capture(&mut self, context : data::Context, name : &String) -> () {
+        let owner = if (let Some(owner) = defining_context(&context, name)) {
+            owner
+        } else {
+            return;
+        };
+        if (owner.parent.is_none() || self.modules.values().any(|module| std::rc::Rc::ptr_eq(&module.symbols, &owner.symbols))) {
+            return;
+        }
+        for (closure, names) in self.closures.iter_mut() {
+            if (! encloses(closure, &owner) && ! names.contains(name)) {
+                names.push(name.clone());
+            }
+        }
+    }
+
+
+
+    // The items of an array or dict must all have the type of the first one.
+    fn start_items(&mut self, context : data::Context, items : Vec<data::Node>) -> data::ValueType {
+        let mut item_type = None;
+        for item in items {
+            let range = item.range.clone();
+            let typ   = self.start_expression(context.clone(), item);
+            if (let Some(item_type) = &item_type) {
+                self.expect_type(context.clone(), item_type, &typ, range);
+            } else {
+                item_type = Some(typ);
+            }
+        }
+        return item_type.unwrap_or(data::ValueType::Unknown);
+    }
+
+
+
+    pub fn start_name(&mut self, context : data::Context, range : data::Range, name : String) -> data::ValueType {
+        let definition = context.get_name(&name).map(|object| object.range).unwrap_or(range.clone());
+        return match (context.get_name(&name).map(|object| object.value)) {
+            Some(data::ObjectType::Variable(mutable, typ))          => {
+                if (! mutable) {
+                    self.capture(context, &name);
+                }
+                typ
+            },
+            Some(data::ObjectType::Function(generics, args, return_type)) => self.function_type(context, &definition, &generics, &args, &return_type),
+            Some(data::ObjectType::Class(_, _, order, _, _))        => self.class_constructor(context, &order),
+            Some(data::ObjectType::Enum(_))                         => {
+                self.invalid(
+                    exception::ValidatorExceptionType::Type,
+                    format!("Enum `{}` can not be used as a value. Use one of its variants.", name),
+                    range,
+                    context
+                );
+                data::ValueType::Unknown
+            },
+            Some(data::ObjectType::Module(_))                       => data::ValueType::Unknown,
+            Some(data::ObjectType::BuiltinType(_))                  => {
+                self.invalid(
+                    exception::ValidatorExceptionType::Type,
+                    format!("Type `{}` can not be used as a value.", name),
+                    range,
+                    context
+                );
+                data::ValueType::Unknown
+            },
+            Some(data::ObjectType::Keyword(keyword))                => {
+                self.invalid(
+                    exception::ValidatorExceptionType::Type,
+                    format!("`{}` can only be called.", keyword),
+                    range,
+                    context
+                );
+                data::ValueType::Unknown
+            },
+            Some(data::ObjectType::LocalModule(_))                  => {
+                self.invalid(
+                    exception::ValidatorExceptionType::Type,
+                    format!("Module `{}` can not be used as a value. Use one of its members, like `{}::name`.", name, name),
+                    range,
+                    context
+                );
+                data::ValueType::Unknown
+            },
+            Some(data::ObjectType::Parameter(_))                    => {
+                self.invalid(
+                    exception::ValidatorExceptionType::Type,
+                    format!("Type parameter `{}` can not be used as a value.", name),
+                    range,
+                    context
+                );
+                data::ValueType::Unknown
+            },
+            Some(data::ObjectType::Trait(_, _))                     => {
+                self.invalid(
+                    exception::ValidatorExceptionType::Type,
+                    format!("Trait `{}` can not be used as a value.", name),
+                    range,
+                    context
+                );
+                data::ValueType::Unknown
+            },
+            None                                                    => {
+                self.invalid(
+                    exception::ValidatorExceptionType::Name,
+                    format!("Name `{}` is not defined.", name),
+                    range,
+                    context
+                );
+                data::ValueType::Unknown
+            }
+        };
+    }
+
+
+
+    // What a path like `util::Point` or `std::types::Int` names, and the
+    // context that it is declared in. Aliases of modules are followed.
+    fn find_path(&self, context : &data::Context, path : &[String]) -> Result<(data::Context, data::ObjectType), String> {
+        let mut owner  = context.clone();
+        let mut object = if (let Some(object) = context.get_name(&path[0])) {
+            object.value
+        } else {
+            return Err(format!("Name `{}` is not defined.", path[0]));
+        };
+        for i in 1..(path.len()) {
+            let module = path[..i].join("::");
+            object = match (object) {
+                data::ObjectType::LocalModule(key) => {
+                    owner = self.modules[&key].clone();
+                    let found = owner.symbols.borrow().get(&path[i]).map(|object| object.value.clone());
+                    match (found) {
+                        Some(_) if (! self.public.contains(&data::qualify(&key, &path[i]))) => {
+                            return Err(format!("`{}` is private to module `{}`. Mark it with `#[public]` to use it here.", path[i], key));
+                        },
+                        Some(found) => found,
+                        None        => return Err(format!("Module `{}` has no member `{}`.", key, path[i]))
+                    }
+                },
+                data::ObjectType::Module(members) => {
+                    if (let Some(found) = members.get(&path[i])) {
+                        found.clone()
+                    } else {
+                        return Err(format!("Module `{}` has no member `{}`.", module, path[i]));
+                    }
+                },
+                _ => return Err(format!("`{}` is not a module.", module))
+            };
+        }
+        return Ok((owner, object));
+    }
+
+    fn resolve_path(&mut self, context : data::Context, path : &[String], range : data::Range) -> Option<(data::Context, data::ObjectType)> {
+        return match (self.find_path(&context, path)) {
+            Ok(found)    => Some(found),
+            Err(message) => {
+                self.invalid(exception::ValidatorExceptionType::Name, message, range, context);
+                None
+            }
+        };
+    }
+
+    // The key of the module that a path like `util` names.
+    fn resolve_module(&mut self, context : data::Context, path : &[String], range : data::Range) -> Option<String> {
+        return match (self.resolve_path(context.clone(), path, range.clone())?) {
+            (_, data::ObjectType::LocalModule(key)) => Some(key),
+            _                                       => {
+                self.invalid(
+                    exception::ValidatorExceptionType::Name,
+                    format!("`{}` is not a module.", path.join("::")),
+                    range,
+                    context
+                );
+                None
+            }
+        };
+    }
+
+    // A global like `let stdout = std::stdout;` is an alias of the module or
+    // type that it names, it is not a value.
+    fn alias_of(&self, context : &data::Context, node : &data::Node) -> Option<data::ObjectType> {
+        let value = if (let data::NodeType::InitializeVariable(false, _, typ, value) = &node.node
+            && let data::NodeType::Type(data::Type::Inferred, _) = typ.node
+            && let Some(value) = &**value
+        ) {
+            value
+        } else {
+            return None;
+        };
+        let path = module_path(value)?;
+        return match (self.find_path(context, &path)) {
+            Ok((_, object @ (data::ObjectType::Module(_) | data::ObjectType::LocalModule(_) | data::ObjectType::BuiltinType(_)))) => Some(object),
+            Ok((_, object @ data::ObjectType::Class(..))) if (path.len() >= 2)                                                  => Some(object),
+            _                                                                                                                     => None
+        };
+    }
+
+
+
+    // A global of another module, which that module must mark `#[public]`.
+    fn module_member(&mut self, context : data::Context, range : data::Range, key : &String, member : &String) -> Option<data::ObjectType> {
+        let object = self.modules[key].symbols.borrow().get(member).map(|object| object.value.clone());
+        if (object.is_none()) {
+            self.invalid(
+                exception::ValidatorExceptionType::Name,
+                format!("Module `{}` has no member `{}`.", key, member),
+                range,
+                context
+            );
+            return None;
+        }
+        if (! self.public.contains(&data::qualify(key, member))) {
+            self.invalid(
+                exception::ValidatorExceptionType::Name,
+                format!("`{}` is private to module `{}`. Mark it with `#[public]` to use it here.", member, key),
+                range,
+                context
+            );
+            return None;
+        }
+        return object;
+    }
+
+
+
+    // The class or enum that a name or a path like `util::Point` refers to,
+    // and the context that it is declared in.
+    fn resolve_global(&mut self, context : data::Context, node : &data::Node) -> Option<(data::Context, String, data::ObjectType)> {
+        return match (&node.node) {
+            data::NodeType::Literal(data::Literal::Name(name)) => context.get_name(name).map(|object| (context.clone(), name.clone(), object.value)),
+            data::NodeType::ModuleMember(parent, member)       => {
+                let path = module_path(parent)?;
+                let key  = self.resolve_module(context.clone(), &path, parent.range.clone())?;
+                let object = self.module_member(context, node.range.clone(), &key, member)?;
+                Some((self.modules[&key].clone(), member.clone(), object))
+            },
+            _                                                  => None
+        };
+    }
+
+
+
+    pub fn start_module_member(&mut self, context : data::Context, range : data::Range, parent : data::Node, member : String) -> data::ValueType {
+        // `util::name` is looked up in the module, as if it was used there.
+        if (let data::NodeType::Literal(data::Literal::Name(name)) = &parent.node
+            && let Some(data::ObjectType::LocalModule(key)) = context.get_name(name).map(|object| object.value)
+        ) {
+            if (self.module_member(context, range.clone(), &key, &member).is_none()) {
+                return data::ValueType::Unknown;
+            }
+            let module = self.modules[&key].clone();
+            return self.start_name(module, range, member);
+        }
+        // `std::stdout::writeln` is a member of a native module.
+        if (let Some(data::ObjectType::Module(members)) = native_module(&context, &parent)) {
+            return match (members.get(&member)) {
+                Some(data::ObjectType::Variable(_, typ)) => typ.clone(),
+                Some(data::ObjectType::BuiltinType(_))   => {
+                    self.invalid(
+                        exception::ValidatorExceptionType::Type,
+                        format!("Type `{}` can not be used as a value.", member),
+                        range,
+                        context
+                    );
+                    data::ValueType::Unknown
+                },
+                Some(data::ObjectType::Keyword(keyword)) => {
+                    self.invalid(
+                        exception::ValidatorExceptionType::Type,
+                        format!("`{}` can only be called.", keyword),
+                        range,
+                        context
+                    );
+                    data::ValueType::Unknown
+                },
+                Some(_)                                  => data::ValueType::Unknown,
+                None                                     => {
+                    self.invalid(
+                        exception::ValidatorExceptionType::Name,
+                        format!("Module `{}` has no member `{}`.", parent, member),
+                        range,
+                        context
+                    );
+                    data::ValueType::Unknown
+                }
+            };
+        }
+        let global = if (module_path(&parent).is_some()) {
+            let global = self.resolve_global(context.clone(), &parent);
+            if (global.is_none() && matches!(parent.node, data::NodeType::ModuleMember(_, _))) {
+                return data::ValueType::Unknown;
+            }
+            global
+        } else {
+            None
+        };
+        if (let Some((_, name, data::ObjectType::Class(generics, _, order, _, _))) = global.clone()) {
+            let method = self.class_methods(context.clone(), &order).into_iter().find(|(method, _, _, _)| *method == member);
+            return match (method) {
+                // The type parameters of the class are worked out at each call.
+                Some((_, true, method_type, _)) => generalize(generics, method_type),
+                Some((_, false, _, _))          => {
+                    self.invalid(
+                        exception::ValidatorExceptionType::Name,
+                        format!("Method `{}` of class `{}` is not static. Call it on an instance.", member, name),
+                        range,
+                        context
+                    );
+                    data::ValueType::Unknown
+                },
+                None                            => {
+                    self.invalid(
+                        exception::ValidatorExceptionType::Name,
+                        format!("Class `{}` has no static method `{}`.", name, member),
+                        range,
+                        context
+                    );
+                    data::ValueType::Unknown
+                }
+            };
+        }
+        if (let Some((owner, name, data::ObjectType::Enum(variants))) = global) {
+            // Variants with a payload are built by calling them.
+            let enumeration = self.qualify(&owner, &name);
+            return match (variants.into_iter().find(|(variant, _)| *variant == member)) {
+                Some((_, payload)) if payload.len() <= 0 => data::ValueType::Enum(enumeration),
+                Some((_, payload))                      => data::ValueType::Func(payload.into_iter().map(|(_, typ)| typ).collect(), Box::new(data::ValueType::Enum(enumeration))),
+                None                                    => {
+                    self.invalid(
+                        exception::ValidatorExceptionType::Name,
+                        format!("Enum `{}` has no variant `{}`.", name, member),
+                        range,
+                        context
+                    );
+                    data::ValueType::Unknown
+                }
+            };
+        }
+        self.start_expression(context, parent);
+        return data::ValueType::Unknown;
+    }
+
+
+
+    pub fn start_class_member(&mut self, context : data::Context, range : data::Range, parent : data::Node, member : String) -> data::ValueType {
+        let parent_type = self.start_expression(context.clone(), parent);
+        if (let data::ValueType::Parameter(name) = &parent_type) {
+            return self.start_parameter_member(context, range, name, member);
+        }
+        if (let Some(method_type) = library::method_type(&parent_type, &member)) {
+            return method_type;
+        }
+        let (order, arguments) = if (let data::ValueType::Class(order, arguments) = parent_type) {
+            (order, arguments)
+        } else {
+            if (matches!(parent_type.widened(), data::ValueType::Bool | data::ValueType::Int | data::ValueType::Float | data::ValueType::Char | data::ValueType::String)) {
+                self.invalid(
+                    exception::ValidatorExceptionType::Name,
+                    format!("Type `{}` has no member `{}`.", parent_type, member),
+                    range,
+                    context
+                );
+            }
+            return data::ValueType::Unknown;
+        };
+        let arguments = self.class_generics(&context, &order[0]).into_iter().map(|(generic, _)| generic).zip(arguments).collect();
+        if (let Some((_, typ, _, _)) = self.class_fields(context.clone(), &order).into_iter().find(|(field, _, _, _)| *field == member)) {
+            return typ.substitute(&arguments);
+        }
+        return match (self.class_methods(context.clone(), &order).into_iter().find(|(method, _, _, _)| *method == member)) {
+            Some((_, false, method_type, _)) => method_type.substitute(&arguments),
+            Some((_, true, _, _))            => {
+                self.invalid(
+                    exception::ValidatorExceptionType::Name,
+                    format!("Method `{}` of class `{}` is static. Use `{}::{}`.", member, order[0], order[0], member),
+                    range,
+                    context
+                );
+                data::ValueType::Unknown
+            },
+            None                             => {
+                self.invalid(
+                    exception::ValidatorExceptionType::Name,
+                    format!("Class `{}` has no member `{}`.", order[0], member),
+                    range,
+                    context
+                );
+                data::ValueType::Unknown
+            }
+        };
+    }
+
+
+
+    // A value of a type parameter has the methods of the traits it is bound by.
+    fn start_parameter_member(&mut self, context : data::Context, range : data::Range, name : &String, member : String) -> data::ValueType {
+        let bounds = if (let Some(data::ObjectType::Parameter(bounds)) = context.get_name(name).map(|object| object.value)) {bounds} else {Vec::new()};
+        let mut arguments = std::collections::HashMap::new();
+        arguments.insert(String::from("Self"), data::ValueType::Parameter(name.clone()));
+        for bound in bounds {
+            if (let Some(data::ObjectType::Trait(methods, _)) = context.get_name(&bound).map(|object| object.value)) {
+                for method in methods {
+                    if (let data::NodeType::DefineFunction(method_name, generics, args, return_type, _) = method.node
+                        && method_name == member
+                    ) {
+                        let trait_context = generic_context(context.clone(), &vec![(String::from("Self"), vec![bound.clone()])]);
+                        return self.function_type(trait_context, &method.range, &generics, &args, &return_type).substitute(&arguments);
+                    }
+                }
+            }
+        }
+        self.invalid(
+            exception::ValidatorExceptionType::Name,
+            format!("Type parameter `{}` has no member `{}`. Bound it by a trait with `{}`, like `<{} : Trait>`.", name, member, member, name),
+            range,
+            context
+        );
+        return data::ValueType::Unknown;
+    }
+
+
+
+    pub fn start_binary_operation(&mut self, context : data::Context, range : data::Range, operator : &'static str, left : data::Node, right : data::Node) -> data::ValueType {
+        let left_bounds  = constant_bounds(&left);
+        let right_bounds = constant_bounds(&right);
+        let left_type    = self.start_expression(context.clone(), left);
+        let right_type   = self.start_expression(context.clone(), right);
+        return self.operation_type(context, range, operator, (left_type, left_bounds), (right_type, right_bounds));
+    }
+
+
+    // The type of an operator applied to types, each with the bounds of the
+    // side if it is a constant.
+    fn operation_type(&mut self, context : data::Context, range : data::Range, operator : &str, left : (data::ValueType, Option<data::ValueType>), right : (data::ValueType, Option<data::ValueType>)) -> data::ValueType {
+        let (refined_left_type, left_bounds)   = left;
+        let (refined_right_type, right_bounds) = right;
+        let left_type  = refined_left_type.widened();
+        let right_type = refined_right_type.widened();
+        if (left_type == data::ValueType::Unknown || right_type == data::ValueType::Unknown) {
+            return match (operator) {
+                "==" | "!=" | "<" | "<=" | ">" | ">=" | "&&" | "||" => data::ValueType::Bool,
+                _                                                   => if (left_type == data::ValueType::Unknown) {right_type} else {left_type}
+            };
+        }
+        if (let Some((_, operator_trait, method)) = data::OPERATOR_TRAITS.iter().find(|(symbol, _, _)| *symbol == operator)
+            && matches!(left_type, data::ValueType::Class(_, _) | data::ValueType::Parameter(_))
+        ) {
+            return self.start_operator_trait(context, range, operator, operator_trait, method, left_type, right_type);
+        }
+        // Arithmetic on a refined value gives the bounds of the result, if
+        // the bounds of the other side are known too.
+        if (refined_left_type.is_refined() || refined_right_type.is_refined()) {
+            let left_bounds  = if (refined_left_type.is_refined()) {Some(refined_left_type.clone())} else {left_bounds};
+            let right_bounds = if (refined_right_type.is_refined()) {Some(refined_right_type.clone())} else {right_bounds};
+            if (let (Some(left_bounds), Some(right_bounds)) = (left_bounds, right_bounds)
+                && let Some(result) = left_bounds.refined_operation(operator, &right_bounds)
+            ) {
+                return result;
+            }
+        }
+        let result = if (left_type != right_type) {
+            None
+        } else {
+            match (operator) {
+                "+"                                => if (left_type.is_numeric() || left_type == data::ValueType::String) {Some(left_type.clone())} else {None},
+                "-" | "*" | "/" | "%" | "**"       => if (left_type.is_numeric()) {Some(left_type.clone())} else {None},
+                "==" | "!="                        => Some(data::ValueType::Bool),
+                "<" | "<=" | ">" | ">="            => if (left_type.is_numeric() || [data::ValueType::Char, data::ValueType::String].contains(&left_type)) {Some(data::ValueType::Bool)} else {None},
+                "&&" | "||"                        => if (left_type == data::ValueType::Bool) {Some(data::ValueType::Bool)} else {None},
+                "&" | "|" | "^"                    => if ([data::ValueType::Int, data::ValueType::Bool].contains(&left_type)) {Some(left_type.clone())} else {None},
+                "<<" | ">>"                        => if (left_type == data::ValueType::Int) {Some(left_type.clone())} else {None},
+                _                                  => None
+            }
+        };
+        return if (let Some(result) = result) {
+            result
+        } else {
+            self.invalid(
+                exception::ValidatorExceptionType::Type,
+                format!("Operator `{}` can not be applied to `{}` and `{}`.", operator, left_type, right_type),
+                range,
+                context
+            );
+            data::ValueType::Unknown
+        };
+    }
+
+
+
+    // An operator on a class calls the method of its trait. On a type
+    // parameter it needs the trait as a bound.
+    fn start_operator_trait(&mut self, context : data::Context, range : data::Range, operator : &str, operator_trait : &str, method : &str, left_type : data::ValueType, right_type : data::ValueType) -> data::ValueType {
+        if (! implements(&context, &left_type, &String::from(operator_trait))) {
+            self.invalid(
+                exception::ValidatorExceptionType::Type,
+                format!("Operator `{}` can not be applied to `{}`. It does not implement trait `{}`.", operator, left_type, operator_trait),
+                range,
+                context
+            );
+            return data::ValueType::Unknown;
+        }
+        let method_type = if (let data::ValueType::Class(order, arguments) = &left_type) {
+            let arguments = self.class_generics(&context, &order[0]).into_iter().map(|(generic, _)| generic).zip(arguments.iter().cloned()).collect();
+            self.class_methods(context.clone(), order).into_iter().find(|(name, _, _, _)| name == method).map(|(_, _, typ, _)| typ.substitute(&arguments))
+        } else {
+            None
+        };
+        if (let Some(data::ValueType::Func(args, return_type)) = method_type
+            && args.len() == 1
+        ) {
+            self.expect_type(context, &args[0], &right_type, range);
+            return *return_type;
+        }
+        self.expect_type(context, &left_type, &right_type, range);
+        return left_type;
+    }
+
+
+
+    pub fn start_slice(&mut self, context : data::Context, parent : data::Node, index : data::Node) -> data::ValueType {
+        let parent_range = parent.range.clone();
+        let index_range  = index.range.clone();
+        let parent_type  = self.start_expression(context.clone(), parent);
+        let index_type   = self.start_expression(context.clone(), index.clone());
+        return match (parent_type) {
+            data::ValueType::List(item) => {
+                self.expect_type(context, &data::ValueType::Int, &index_type, index_range);
+                *item
+            },
+            data::ValueType::Array(item, length) => {
+                self.expect_type(context.clone(), &data::ValueType::Int, &index_type, index_range.clone());
+                // Any other index is checked at runtime.
+                let bounds = if (index_type.is_refined()) {Some(index_type.clone())} else {constant_bounds(&index)};
+                if (let Some(bounds) = bounds
+                    && bounds.outside(&data::ValueType::RInt(0, length as i64 - 1))
+                ) {
+                    self.invalid(
+                        exception::ValidatorExceptionType::Type,
+                        match (bounds) {
+                            data::ValueType::RInt(min, max) if (min == max) => format!("Index `{}` is out of bounds for `{}`.", min, data::ValueType::Array(item.clone(), length)),
+                            _                                               => format!("Index in `{}` is always out of bounds for `{}`.", bounds, data::ValueType::Array(item.clone(), length))
+                        },
+                        index_range,
+                        context
+                    );
+                }
+                *item
+            },
+            data::ValueType::String => {
+                self.expect_type(context, &data::ValueType::Int, &index_type, index_range);
+                data::ValueType::Char
+            },
+            data::ValueType::Dict(key, value) => {
+                self.expect_type(context, &key, &index_type, index_range);
+                *value
+            },
+            data::ValueType::Tuple(items) => {
+                if (let data::NodeType::Literal(data::Literal::Integer(i)) = index.node && i >= 0 && (i as usize) < items.len()) {
+                    items[i as usize].clone()
+                } else {
+                    self.invalid(
+                        exception::ValidatorExceptionType::Type,
+                        format!("A `{}` can only be sliced by an Integer from 0 to {}.", data::ValueType::Tuple(items.clone()), items.len() as i64 - 1),
+                        index_range,
+                        context
+                    );
+                    data::ValueType::Unknown
+                }
+            },
+            data::ValueType::Unknown => data::ValueType::Unknown,
+            _ => {
+                self.invalid(
+                    exception::ValidatorExceptionType::Type,
+                    format!("Can not slice `{}`.", parent_type),
+                    parent_range,
+                    context
+                );
+                data::ValueType::Unknown
+            }
+        };
+    }
+
+
+
+    // `scope::return(value)`, the old way of returning from a function. It is
+    // found through `std::scope` like any other name, so it can be anywhere
+    // that a value can.
+    fn start_scope_return(&mut self, context : data::Context, range : data::Range, args : Vec<data::Node>) -> data::ValueType {
+        exception::ValidatorException::new(
+            exception::ValidatorExceptionType::Deprecated,
+            String::from("`scope::return` is deprecated. Use `return` instead."),
+            range.clone(),
+            context.clone()
+        ).dump_warning();
+        if (args.len() > 1) {
+            self.invalid(
+                exception::ValidatorExceptionType::Type,
+                format!("`scope::return` takes 0 or 1 arguments. {} given.", args.len()),
+                range.clone(),
+                context.clone()
+            );
+        }
+        self.start_control_return(context, range, args.into_iter().next());
+        return data::ValueType::Crash;
+    }
+
+
+    pub fn start_call(&mut self, context : data::Context, range : data::Range, parent : data::Node, args : Vec<data::Node>) -> data::ValueType {
+        if (keyword_of(&context, &parent).as_deref() == Some("std::scope::return")) {
+            return self.start_scope_return(context, range, args);
+        }
+        let parent_range = parent.range.clone();
+        let parent_name  = parent.to_string();
+        let parent_type  = self.start_expression(context.clone(), parent);
+        let mut arg_types = Vec::new();
+        for arg in args {
+            arg_types.push((self.start_expression(context.clone(), arg.clone()), arg));
+        }
+        // The type arguments of a generic function are worked out from the
+        // arguments it is called with. One that is in the type of no
+        // argument can not be. One whose argument has an unknown type is
+        // left unchecked. Errors about the call name the types it is called
+        // with, like `first<Int>`.
+        let mut instance = None;
+        let parent_type  = if (let data::ValueType::Generic(parameters, typ) = parent_type) {
+            let     names     = parameters.iter().map(|(parameter, _)| parameter.clone()).collect();
+            let mut arguments = std::collections::HashMap::new();
+            let mut expected  = Vec::new();
+            if (let data::ValueType::Func(expected_types, _) = &*typ) {
+                for (expected, (found, _)) in expected_types.iter().zip(arg_types.iter()) {
+                    expected.infer(found, &names, &mut arguments);
+                }
+                expected = expected_types.clone();
+            }
+            for (name, _) in &parameters {
+                if (! arguments.contains_key(name) && ! expected.iter().any(|typ| typ.mentions(name))) {
+                    self.invalid(
+                        exception::ValidatorExceptionType::Type,
+                        format!("Type argument `{}` of `{}` can not be worked out from the arguments. Use `{}` in the type of an argument.", name, parent_name, name),
+                        range.clone(),
+                        context.clone()
+                    );
+                }
+                arguments.entry(name.clone()).or_insert(data::ValueType::Unknown);
+            }
+            let name = format!("{}<{}>", parent_name, parameters.iter().map(|(parameter, _)| arguments[parameter].to_string()).collect::<Vec<String>>().join(", "));
+            self.expect_bounds(context.clone(), &parameters, &arguments, &name, range.clone());
+            self.instances.insert(check_key(&range), name.clone());
+            instance = Some(name);
+            typ.substitute(&arguments)
+        } else {
+            parent_type
+        };
+        return match (parent_type) {
+            data::ValueType::Func(expected_types, return_type) => {
+                if (expected_types.len() != arg_types.len()) {
+                    self.invalid(
+                        exception::ValidatorExceptionType::Type,
+                        format!("Function takes {} arguments. {} given.", expected_types.len(), arg_types.len()),
+                        range,
+                        context
+                    );
+                } else {
+                    for (expected, (found, arg)) in expected_types.iter().zip(arg_types.iter()) {
+                        if (let Some(instance) = &instance && ! expected.accepts(found)) {
+                            self.invalid(
+                                exception::ValidatorExceptionType::Type,
+                                format!("Expected `{}`, found `{}`. It is passed to `{}`.", expected, found, instance),
+                                arg.range.clone(),
+                                context.clone()
+                            );
+                        } else {
+                            self.expect_value(context.clone(), expected, found, arg);
+                        }
+                    }
+                }
+                *return_type
+            },
+            data::ValueType::Unknown => data::ValueType::Unknown,
+            _ => {
+                self.invalid(
+                    exception::ValidatorExceptionType::Type,
+                    format!("Can not call `{}`.", parent_type),
+                    parent_range,
+                    context
+                );
+                data::ValueType::Unknown
+            }
+        };
+    }
+
+
+
+    // `a += b` checks `a` once, then the type of `a + b`. Its bounds are
+    // checked where it is assigned, at `a`.
+    pub fn start_assign_variable(&mut self, context : data::Context, target : data::Node, operator : Option<&'static str>, value : data::Node) -> data::ValueType {
+        let value_type  = self.start_expression(context.clone(), value.clone());
+        let target_type = self.start_expression(context.clone(), target.clone());
+        if (let Some(operator) = operator) {
+            let result_type = self.operation_type(context.clone(), data::Range::new(target.range.min.clone(), value.range.max.clone()), operator, (target_type.clone(), None), (value_type, constant_bounds(&value)));
+            self.expect_value(context.clone(), &target_type, &result_type, &target);
+        } else {
+            self.expect_value(context.clone(), &target_type, &value_type, &value);
+        }
+
+        // Fields and slices are only assignable through a mutable name.
+        let mut root = target.clone();
+        while (let data::NodeType::ClassMember(parent, _) | data::NodeType::Slice(parent, _) = root.node.clone()) {
+            root = *parent;
+        }
+        if (let data::NodeType::Literal(data::Literal::Name(name)) = root.node.clone()
+            && let Some(object) = context.get_name(&name)
+        ) {
+            match (object.value) {
+                data::ObjectType::Variable(true, _)  => {},
+                data::ObjectType::Variable(false, _) => {
+                    self.invalid(
+                        exception::ValidatorExceptionType::Mutability,
+                        format!("Can not assign to immutable name `{}`. Declare it with `let mut`.", name),
+                        target.range,
+                        context
+                    );
+                },
+                _                                    => {
+                    self.invalid(
+                        exception::ValidatorExceptionType::Mutability,
+                        format!("Can not assign to `{}`, it is not a variable.", name),
+                        target.range,
+                        context
+                    );
+                }
+            }
+        }
+
+        return data::ValueType::Void;
+    }
+
+}
+
+
+
+// The native module that a path like `std::stdout` names.
+fn native_module(context : &data::Context, node : &data::Node) -> Option<data::ObjectType> {
+    return match (&node.node) {
+        data::NodeType::Literal(data::Literal::Name(name)) => context.get_name(name).map(|object| object.value).filter(|object| matches!(object, data::ObjectType::Module(_))),
+        data::NodeType::ModuleMember(parent, member)       => {
+            if (let Some(data::ObjectType::Module(members)) = native_module(context, parent)) {
+                members.get(member).cloned().filter(|object| matches!(object, data::ObjectType::Module(_)))
+            } else {
+                None
+            }
+        },
+        _                                                  => None
+    };
+}
+
+// The native keyword that a path like `scope::return` names.
+fn keyword_of(context : &data::Context, node : &data::Node) -> Option<String> {
+    if (let data::NodeType::ModuleMember(parent, member) = &node.node
+        && let Some(data::ObjectType::Module(members)) = native_module(context, parent)
+        && let Some(data::ObjectType::Keyword(keyword)) = members.get(member)
+    ) {
+        return Some(keyword.clone());
+    }
+    return None;
+}
+
+// The node of a name like `util::Color` that is written as one.
+fn name_path(name : &str, range : data::Range) -> data::Node {
+    let mut parts = name.split("::").map(|part| part.to_string());
+    let mut node  = data::Node::new(data::NodeType::Literal(data::Literal::Name(parts.next().unwrap_or_default())), range.clone());
+    for part in parts {
+        node = data::Node::new(data::NodeType::ModuleMember(Box::new(node), part), range.clone());
+    }
+    return node;
+}
+
+// The names of a path like `util::helpers`.
+fn module_path(node : &data::Node) -> Option<Vec<String>> {
+    return match (&node.node) {
+        data::NodeType::Literal(data::Literal::Name(name)) => Some(vec![name.clone()]),
+        data::NodeType::ModuleMember(parent, member)       => {
+            let mut path = module_path(parent)?;
+            path.push(member.clone());
+            Some(path)
+        },
+        _                                                  => None
+    };
+}
+
+// The closest context that declares the name.
+fn defining_context(context : &data::Context, name : &String) -> Option<data::Context> {
+    if (context.contains_name(name)) {
+        return Some(context.clone());
+    }
+    if (let Some((_, parent)) = &*context.parent) {
+        return defining_context(parent, name);
+    }
+    return None;
+}
+
+// Whether `inner` is `outer` or one of the contexts inside of it.
+fn encloses(outer : &data::Context, inner : &data::Context) -> bool {
+    if (std::rc::Rc::ptr_eq(&outer.symbols, &inner.symbols)) {
+        return true;
+    }
+    if (let Some((_, parent)) = &*inner.parent) {
+        return encloses(outer, parent);
+    }
+    return false;
+}
+
+
+
+// A context in which `generics` are types.
+fn generic_context(context : data::Context, generics : &Vec<(String, Vec<String>)>) -> data::Context {
+    if (generics.len() <= 0) {
+        return context;
+    }
+    let generic_context = data::Context::new(context.name.clone(), Some((data::Range::new_void(), context)));
+    for (generic, bounds) in generics {
+        generic_context.set_name(generic.clone(), data::Object::new(data::ObjectType::Parameter(bounds.clone()), data::Range::new_void()));
+    }
+    return generic_context;
+}
+
+
+
+// A context in which `Self` is the class, for its fields and methods and
+// those of its trait implementations.
+fn self_context(context : data::Context, class : Option<data::ObjectType>) -> data::Context {
+    let self_context = data::Context::new(context.name.clone(), Some((data::Range::new_void(), context)));
+    if (let Some(class) = class) {
+        self_context.set_name(String::from("Self"), data::Object::new(class, data::Range::new_void()));
+    }
+    return self_context;
+}
+
+// `typ`, with the arguments of `parameters` worked out wherever it is used.
+fn generalize(parameters : Vec<(String, Vec<String>)>, typ : data::ValueType) -> data::ValueType {
+    if (parameters.len() <= 0) {
+        return typ;
+    }
+    return match (typ) {
+        data::ValueType::Generic(inner, typ) => data::ValueType::Generic([parameters, inner].concat(), typ),
+        typ                                  => data::ValueType::Generic(parameters, Box::new(typ))
+    };
+}
+
+// The type that `generalize` wrapped, with its type parameters left free.
+fn generic_body(typ : data::ValueType) -> data::ValueType {
+    return if (let data::ValueType::Generic(_, typ) = typ) {*typ} else {typ};
+}
+
+
+
+// Whether `typ` implements the trait `name`. Numbers have the operator
+// traits built in, and `String` has `Add`.
+fn implements(context : &data::Context, typ : &data::ValueType, name : &String) -> bool {
+    return match (typ) {
+        data::ValueType::Unknown              => true,
+        data::ValueType::Parameter(parameter) => matches!(context.get_name(parameter).map(|object| object.value), Some(data::ObjectType::Parameter(bounds)) if bounds.contains(name)),
+        data::ValueType::Class(order, _)      => matches!(context.get_name(name).map(|object| object.value), Some(data::ObjectType::Trait(_, implementors)) if order.iter().any(|class| implementors.contains(class))),
+        data::ValueType::String               => name == "Add",
+        typ                                   => typ.is_numeric() && data::OPERATOR_TRAITS.iter().any(|(_, operator_trait, _)| operator_trait == name)
+    };
+}
+
+
+
+// The names that every module has : the built in traits.
+fn builtins() -> data::Context {
+    let builtins = data::Context::new(String::from("Builtins"), None);
+    declare_operator_traits(builtins.clone());
+    return builtins;
+}
+
+// The traits of the operators, like `trait Add { func add(other : Self) : Self; }`.
+fn declare_operator_traits(context : data::Context) -> () {
+    let self_type = || data::Node::new(data::NodeType::Type(data::Type::Base(vec![String::from("Self")]), Vec::new()), data::Range::new_void());
+    for (_, name, method) in data::OPERATOR_TRAITS {
+        let signature = data::Node::new(data::NodeType::DefineFunction(
+            String::from(method), Vec::new(), Box::new(vec![(String::from("other"), self_type())]), Box::new(self_type()), Box::default()
+        ), data::Range::new_void());
+        context.set_name(String::from(name), data::Object::new(data::ObjectType::Trait(vec![signature], Vec::new()), data::Range::new_void()));
+    }
+}
+
+
+
+fn literal_type(literal : &data::Literal) -> data::ValueType {
+    return match (literal) {
+        data::Literal::Name(_)      => data::ValueType::Unknown,
+        data::Literal::Bool(_)      => data::ValueType::Bool,
+        data::Literal::Character(_) => data::ValueType::Char,
+        data::Literal::String(_)    => data::ValueType::String,
+        data::Literal::Integer(_)   => data::ValueType::Int,
+        data::Literal::Float(_)     => data::ValueType::Float
+    };
+}
+
+
+
+fn is_wildcard(pattern : &data::Pattern) -> bool {
+    return matches!(pattern, data::Pattern::Wildcard | data::Pattern::Binding(_));
+}
+
+// Whether the pattern matches the value of a literal.
+fn pattern_covers(pattern : &data::Pattern, value : &data::Literal) -> bool {
+    return match (pattern, literal_order(value)) {
+        (data::Pattern::Literal(literal), _)           => literal.to_string() == value.to_string(),
+        (data::Pattern::Range(min, max), Some(value))  => literal_order(min).is_some_and(|min| min <= value) && literal_order(max).is_some_and(|max| value < max),
+        (data::Pattern::Range(_, _), None)             => false,
+        (pattern, _)                                   => is_wildcard(pattern)
+    };
+}
+
+// Where an `Int` or a `Char` is in the order of its type.
+fn literal_order(literal : &data::Literal) -> Option<i128> {
+    return match (literal) {
+        data::Literal::Integer(value)  => Some(*value as i128),
+        data::Literal::Character(ch)   => Some(*ch as i128),
+        _                              => None
+    };
+}
+
+// The parts of an `Int`, `Char` or `String` that each row either matches
+// whole or not at all, if the first column has literals. Each part is the
+// pattern to name it by, with a value inside of it.
+fn literal_parts(rows : &Vec<Vec<data::Pattern>>) -> Option<Vec<(data::Pattern, data::Literal)>> {
+    let literals = rows.iter().filter_map(|row| match (&row[0]) {
+        data::Pattern::Literal(literal) | data::Pattern::Range(literal, _) => Some(literal.clone()),
+        _                                                                    => None
+    }).collect::<Vec<data::Literal>>();
+    let first = literals.first()?;
+    if (let data::Literal::String(_) = first) {
+        let mut parts = Vec::new();
+        for literal in &literals {
+            if (! parts.iter().any(|(_, other) : &(data::Pattern, data::Literal)| other.to_string() == literal.to_string())) {
+                parts.push((data::Pattern::Literal(literal.clone()), literal.clone()));
+            }
+        }
+        // Any string that no literal is, named by one of them.
+        let mut other = String::new();
+        while (parts.iter().any(|(_, literal)| literal.to_string() == data::Literal::String(other.clone()).to_string())) {
+            other.push('_');
+        }
+        parts.push((data::Pattern::Literal(data::Literal::String(other.clone())), data::Literal::String(other)));
+        return Some(parts);
+    }
+    let (min, max, from) : (i128, i128, fn(i128) -> Option<data::Literal>) = match (first) {
+        data::Literal::Integer(_)   => (i64::MIN as i128, i64::MAX as i128 + 1, |value| i64::try_from(value).ok().map(data::Literal::Integer)),
+        data::Literal::Character(_) => (0, char::MAX as i128 + 1, |value| u32::try_from(value).ok().and_then(char::from_u32).map(data::Literal::Character)),
+        _                           => return None
+    };
+    let mut bounds = vec![min, max];
+    for row in rows {
+        match (&row[0]) {
+            data::Pattern::Literal(literal)  => bounds.extend(literal_order(literal).map(|value| vec![value, value + 1]).unwrap_or_default()),
+            data::Pattern::Range(low, high)  => bounds.extend([literal_order(low), literal_order(high)].into_iter().flatten()),
+            _                                => {}
+        }
+    }
+    bounds.retain(|bound| min <= *bound && *bound <= max);
+    bounds.sort();
+    bounds.dedup();
+    let mut parts = Vec::new();
+    for pair in bounds.windows(2) {
+        let (low, high) = (pair[0], pair[1]);
+        // A part without a bound on one side is named by the value next to
+        // the other side. `Char` skips the values that are not characters.
+        let value = if (low == min) {(low..high).rev().find_map(from)} else {(low..high).find_map(from)};
+        let value = if (let Some(value) = value) {value} else {continue};
+        let head  = if (high - low <= 1 || low == min || high == max) {
+            data::Pattern::Literal(value.clone())
+        } else if (let (Some(low), Some(high)) = (from(low), from(high))) {
+            data::Pattern::Range(low, high)
+        } else {
+            data::Pattern::Literal(value.clone())
+        };
+        parts.push((head, value));
+    }
+    return Some(parts);
+}
+
+
+
+// The bounds of a value that is worked out from literals alone.
+fn constant_bounds(node : &data::Node) -> Option<data::ValueType> {
+    if (let Some((operator, left, right)) = node.node.binary_operation()) {
+        return constant_bounds(&left)?.refined_operation(operator, &constant_bounds(&right)?);
+    }
+    return match (&node.node) {
+        data::NodeType::Literal(data::Literal::Integer(value)) => Some(data::ValueType::RInt(*value, *value)),
+        data::NodeType::Literal(data::Literal::Float(value))   => Some(data::ValueType::RFloat(*value, *value)),
+        data::NodeType::OppositeOperation(value)               => constant_bounds(value)?.refined_opposite(),
+        _                                                      => None
+    };
+}
+
+
+
+fn check_key(range : &data::Range) -> (String, usize, usize) {
+    return (range.min.filename.clone(), range.min.index, range.max.index);
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run::exit_code;
+
+    #[test]
+    fn array_keeps_its_length_in_its_type() {
+        let script = "
+            func last(values : Array<Int, 3>) : Int {
+                values[2]
+            }
+            #[entry]
+            func main() : Int {
+                let values : Array<Int, 3> = [1, 2, 3];
+                last(values) + values.to_list()[0]
+            }
+        ";
+        assert!(exit_code(script) == 4);
+    }
+
+    #[test]
+    fn array_of_another_length_is_rejected() {
+        let script = "
+            #[entry]
+            func main() : Int {
+                let values : Array<Int, 3> = [1, 2];
+                values[0]
+            }
+        ";
+        assert!(exit_code(script) == exception::EXIT_ERROR);
+    }
+
+    #[test]
+    fn constant_index_outside_of_an_array_is_rejected() {
+        let script = "
+            #[entry]
+            func main() : Int {
+                let values = [1, 2, 3];
+                values[3]
+            }
+        ";
+        assert!(exit_code(script) == exception::EXIT_ERROR);
+    }
+
+    #[test]
+    fn other_index_outside_of_an_array_crashes() {
+        let script = "
+            func at(index : Int) : Int {
+                [1, 2, 3][index]
+            }
+            #[entry]
+            func main() : Int {
+                at(3)
+            }
+        ";
+        assert!(exit_code(script) == exception::EXIT_CRASH);
+    }
+
+    // The patterns that name the values which no row covers.
+    fn uncovered(rows : Vec<data::Pattern>) -> Vec<String> {
+        let rows = rows.into_iter().map(|pattern| vec![pattern]).collect::<Vec<Vec<data::Pattern>>>();
+        return literal_parts(&rows).unwrap().into_iter()
+            .filter(|(_, value)| ! rows.iter().any(|row| pattern_covers(&row[0], value)))
+            .map(|(pattern, _)| pattern.to_string())
+            .collect();
+    }
+
+    #[test]
+    fn match_names_the_integers_that_literals_leave_out() {
+        let rows = vec![
+            data::Pattern::Range(data::Literal::Integer(0), data::Literal::Integer(5)),
+            data::Pattern::Literal(data::Literal::Integer(6)),
+            data::Pattern::Range(data::Literal::Integer(8), data::Literal::Integer(10))
+        ];
+        assert!(uncovered(rows) == vec!["-1", "5", "7", "10"]);
+        let rows = vec![
+            data::Pattern::Range(data::Literal::Integer(0), data::Literal::Integer(5)),
+            data::Pattern::Range(data::Literal::Integer(8), data::Literal::Integer(10))
+        ];
+        assert!(uncovered(rows) == vec!["-1", "5..8", "10"]);
+    }
+
+    #[test]
+    fn match_names_a_string_that_literals_leave_out() {
+        let rows = vec![
+            data::Pattern::Literal(data::Literal::String(String::new())),
+            data::Pattern::Literal(data::Literal::String(String::from("_")))
+        ];
+        assert!(uncovered(rows) == vec!["\"__\""]);
+    }
+
+    #[test]
+    fn match_over_every_variant_is_accepted() {
+        let script = "
+            enum Shape {
+                Empty,
+                Square(Int),
+                Rect { width : Int, height : Int }
+            }
+            func area(shape : Shape) : Int {
+                match (shape) {
+                    Shape::Empty              => 0,
+                    Shape::Square(side)       => side * side,
+                    Shape::Rect { width, .. } => width
+                }
+            }
+            #[entry]
+            func main() : Int {
+                area(Shape::Square(3)) + area(Shape::Rect(4, 5)) + area(Shape::Empty)
+            }
+        ";
+        assert!(exit_code(script) == 13);
+    }
+
+    #[test]
+    fn match_that_leaves_out_a_variant_is_rejected() {
+        let script = "
+            enum Shape {
+                Empty,
+                Square(Int)
+            }
+            func area(shape : Shape) : Int {
+                match (shape) {
+                    Shape::Square(side) => side * side
+                }
+            }
+            #[entry]
+            func main() : Int {
+                area(Shape::Empty)
+            }
+        ";
+        assert!(exit_code(script) == exception::EXIT_ERROR);
+    }
+
+    #[test]
+    fn match_that_leaves_out_an_integer_is_rejected() {
+        let script = "
+            func sign(value : Int) : Int {
+                match (value) {
+                    0    => 0,
+                    1..9 => 1
+                }
+            }
+            #[entry]
+            func main() : Int {
+                sign(3)
+            }
+        ";
+        assert!(exit_code(script) == exception::EXIT_ERROR);
     }
 
-}
\ No newline at end of file
+}